@@ -11,7 +11,7 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     config_cmd, db, dump_genesis, import, init_cmd, init_state,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, stage, wvm,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -182,6 +182,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::Wvm(command) => runner.run_until_ctrl_c(command.execute()),
         }
     }
 
@@ -237,6 +238,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// WVM specific utilities
+    #[command(name = "wvm")]
+    Wvm(wvm::Command),
 }
 
 #[cfg(test)]
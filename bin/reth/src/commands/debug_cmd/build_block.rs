@@ -149,6 +149,7 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
         let validator =
             TransactionValidationTaskExecutor::eth_builder(provider_factory.chain_spec())
                 .with_head_timestamp(best_block.timestamp)
+                .with_head_number(best_block.number)
                 .kzg_settings(self.kzg_settings()?)
                 .with_additional_tasks(1)
                 .build_with_tasks(
@@ -1,4 +1,4 @@
-use crate::{ChainSpec, DepositContract};
+use crate::{max_tx_input_bytes_for_gas_limit, BlockSchedule, ChainSpec, DepositContract};
 use alloc::{boxed::Box, vec::Vec};
 use alloy_chains::Chain;
 use alloy_eips::eip1559::BaseFeeParams;
@@ -28,6 +28,51 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
     /// Get the [`BaseFeeParams`] for the chain at the given timestamp.
     fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams;
 
+    /// Get the minimum base fee of the given block, `0` if the chain has none.
+    ///
+    /// See [`crate::WvmChainParams::base_fee_floor_at_block`].
+    fn base_fee_floor_at_block(&self, _block_number: u64) -> u64 {
+        0
+    }
+
+    /// Returns the schedule of the minimum base fee, if the chain has one.
+    fn base_fee_floor_schedule(&self) -> Option<&BlockSchedule> {
+        None
+    }
+
+    /// Get the gas limit of the given block.
+    fn block_gas_limit_at_block(&self, _block_number: u64) -> u64 {
+        self.max_gas_limit()
+    }
+
+    /// Get the gas limit blocks converge to from the given block on, `None` if the chain doesn't
+    /// schedule one.
+    fn scheduled_gas_limit_at_block(&self, _block_number: u64) -> Option<u64> {
+        None
+    }
+
+    /// Get the minimum gas limit of a transaction at the given block, if the chain requires one.
+    fn min_transaction_gas_at_block(&self, _block_number: u64) -> Option<u64> {
+        None
+    }
+
+    /// Get the maximum size of the input of a transaction at the given block.
+    fn max_tx_input_bytes_at_block(&self, block_number: u64) -> usize {
+        max_tx_input_bytes_for_gas_limit(self.block_gas_limit_at_block(block_number))
+    }
+
+    /// Get the maximum encoded size of the transactions of the given block, if the chain limits
+    /// it.
+    fn max_block_size_at_block(&self, _block_number: u64) -> Option<usize> {
+        None
+    }
+
+    /// Get the number of blocks between an archived block and the given block recording its
+    /// Arweave id in the archive registry, `None` if the registry is not written.
+    fn archive_registry_delay_at_block(&self, _block_number: u64) -> Option<u64> {
+        None
+    }
+
     /// Returns the deposit contract data for the chain, if it's present
     fn deposit_contract(&self) -> Option<&DepositContract>;
 
@@ -76,6 +121,38 @@ impl EthChainSpec for ChainSpec {
         self.base_fee_params_at_timestamp(timestamp)
     }
 
+    fn base_fee_floor_at_block(&self, block_number: u64) -> u64 {
+        self.wvm.base_fee_floor_at_block(block_number)
+    }
+
+    fn base_fee_floor_schedule(&self) -> Option<&BlockSchedule> {
+        Some(&self.wvm.base_fee_floor)
+    }
+
+    fn block_gas_limit_at_block(&self, block_number: u64) -> u64 {
+        self.block_gas_limit_at_block(block_number)
+    }
+
+    fn scheduled_gas_limit_at_block(&self, block_number: u64) -> Option<u64> {
+        self.wvm.gas_limit.at_block(block_number)
+    }
+
+    fn min_transaction_gas_at_block(&self, block_number: u64) -> Option<u64> {
+        self.min_transaction_gas_at_block(block_number)
+    }
+
+    fn max_tx_input_bytes_at_block(&self, block_number: u64) -> usize {
+        self.max_tx_input_bytes_at_block(block_number)
+    }
+
+    fn max_block_size_at_block(&self, block_number: u64) -> Option<usize> {
+        self.max_block_size_at_block(block_number)
+    }
+
+    fn archive_registry_delay_at_block(&self, block_number: u64) -> Option<u64> {
+        self.archive_registry_delay_at_block(block_number)
+    }
+
     fn deposit_contract(&self) -> Option<&DepositContract> {
         self.deposit_contract.as_ref()
     }
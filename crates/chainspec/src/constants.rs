@@ -3,7 +3,7 @@ use alloy_eips::eip6110::MAINNET_DEPOSIT_CONTRACT_ADDRESS;
 use alloy_primitives::b256;
 
 /// Gas per transaction not creating a contract.
-/// WVM: Raised from 21k to 500_000
+/// WVM: Raised from 21k to 500_000, chains can schedule their own in the chain spec
 pub const MIN_TRANSACTION_GAS: u64 = 500_000u64;
 /// Deposit contract address: `0x00000000219ab540356cbb839cbe05303d7705fa`
 pub(crate) const MAINNET_DEPOSIT_CONTRACT: DepositContract = DepositContract::new(
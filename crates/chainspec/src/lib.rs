@@ -26,6 +26,8 @@ mod api;
 mod info;
 /// The chain spec module.
 mod spec;
+/// WVM parameters of the chain spec.
+mod wvm;
 
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
 /// Re-export for convenience
@@ -39,6 +41,10 @@ pub use spec::{
     BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecProvider,
     DepositContract, ForkBaseFeeParams, DEV, HOLESKY, MAINNET, SEPOLIA,
 };
+pub use wvm::{
+    max_tx_input_bytes_for_gas_limit, BlockSchedule, ScheduledValue, WvmChainParams,
+    WVM_CHAIN_PARAMS_KEY,
+};
 
 /// Simple utility to create a thread-safe sync cell with a value set.
 pub fn once_cell_set<T>(value: T) -> OnceLock<T> {
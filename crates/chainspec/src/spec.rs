@@ -26,7 +26,10 @@ use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
 use reth_primitives_traits::{constants::HOLESKY_GENESIS_HASH, Header, SealedHeader};
 use reth_trie_common::root::state_root_ref_unhashed;
 
-use crate::{constants::MAINNET_DEPOSIT_CONTRACT, once_cell_set, EthChainSpec, LazyLock, OnceLock};
+use crate::{
+    constants::MAINNET_DEPOSIT_CONTRACT, max_tx_input_bytes_for_gas_limit, once_cell_set,
+    EthChainSpec, LazyLock, OnceLock, WvmChainParams,
+};
 
 /// The Ethereum mainnet spec
 pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
@@ -49,8 +52,9 @@ pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
-        max_gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+        max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 20000,
+        wvm: Default::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -74,8 +78,9 @@ pub static SEPOLIA: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
-        max_gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+        max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
+        wvm: Default::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -97,8 +102,9 @@ pub static HOLESKY: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
-        max_gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+        max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
+        wvm: Default::default(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -210,6 +216,9 @@ pub struct ChainSpec {
 
     /// The delete limit for pruner, per run.
     pub prune_delete_limit: usize,
+
+    /// The WVM parameters, read from the genesis `config`
+    pub wvm: WvmChainParams,
 }
 
 impl Default for ChainSpec {
@@ -223,8 +232,9 @@ impl Default for ChainSpec {
             hardforks: Default::default(),
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
-            max_gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+            max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             prune_delete_limit: MAINNET.prune_delete_limit,
+            wvm: Default::default(),
         }
     }
 }
@@ -360,6 +370,40 @@ impl ChainSpec {
         }
     }
 
+    /// Get the gas limit of the given block, [`Self::max_gas_limit`] unless scheduled in
+    /// [`WvmChainParams::gas_limit`]
+    pub fn block_gas_limit_at_block(&self, block_number: u64) -> u64 {
+        self.wvm.gas_limit.at_block(block_number).unwrap_or(self.max_gas_limit)
+    }
+
+    /// Get the minimum gas limit of a transaction at the given block, `None` before the first
+    /// minimum scheduled in [`WvmChainParams::min_transaction_gas`]
+    pub fn min_transaction_gas_at_block(&self, block_number: u64) -> Option<u64> {
+        self.wvm.min_transaction_gas.at_block(block_number)
+    }
+
+    /// Get the maximum size of the input of a transaction at the given block, derived from the
+    /// block gas limit unless scheduled in [`WvmChainParams::max_tx_input_bytes`]
+    pub fn max_tx_input_bytes_at_block(&self, block_number: u64) -> usize {
+        self.wvm.max_tx_input_bytes.at_block(block_number).map_or_else(
+            || max_tx_input_bytes_for_gas_limit(self.block_gas_limit_at_block(block_number)),
+            |bytes| bytes as usize,
+        )
+    }
+
+    /// Get the maximum encoded size of the transactions of the given block, `None` before the
+    /// first size scheduled in [`WvmChainParams::max_block_size`]
+    pub fn max_block_size_at_block(&self, block_number: u64) -> Option<usize> {
+        self.wvm.max_block_size.at_block(block_number).map(|size| size as usize)
+    }
+
+    /// Get the number of blocks between an archived block and the given block recording its
+    /// Arweave id in the archive registry, as scheduled in
+    /// [`WvmChainParams::archive_registry_delay`].
+    pub fn archive_registry_delay_at_block(&self, block_number: u64) -> Option<u64> {
+        self.wvm.archive_registry_delay.at_block(block_number)
+    }
+
     /// Get the hash of the genesis block.
     pub fn genesis_hash(&self) -> B256 {
         *self.genesis_hash.get_or_init(|| self.genesis_header().hash_slow())
@@ -575,8 +619,11 @@ impl ChainSpec {
     }
 }
 
-impl From<Genesis> for ChainSpec {
-    fn from(genesis: Genesis) -> Self {
+impl TryFrom<Genesis> for ChainSpec {
+    type Error = serde_json::Error;
+
+    /// Fails if the WVM parameters of the genesis `config` are malformed.
+    fn try_from(genesis: Genesis) -> Result<Self, Self::Error> {
         // Block-based hardforks
         let hardfork_opts = [
             (EthereumHardfork::Homestead.boxed(), genesis.config.homestead_block),
@@ -653,15 +700,18 @@ impl From<Genesis> for ChainSpec {
             DepositContract { address, block: 0, topic: MAINNET_DEPOSIT_CONTRACT.topic }
         });
 
-        Self {
+        let wvm = WvmChainParams::from_genesis(&genesis)?;
+
+        Ok(Self {
             chain: genesis.config.chain_id.into(),
             genesis,
             genesis_hash: OnceLock::new(),
             hardforks: ChainHardforks::new(ordered_hardforks),
             paris_block_and_final_difficulty,
             deposit_contract,
+            wvm,
             ..Default::default()
-        }
+        })
     }
 }
 
@@ -713,6 +763,7 @@ pub struct ChainSpecBuilder {
     chain: Option<Chain>,
     genesis: Option<Genesis>,
     hardforks: ChainHardforks,
+    wvm: WvmChainParams,
 }
 
 impl ChainSpecBuilder {
@@ -722,6 +773,7 @@ impl ChainSpecBuilder {
             chain: Some(MAINNET.chain),
             genesis: Some(MAINNET.genesis.clone()),
             hardforks: MAINNET.hardforks.clone(),
+            wvm: MAINNET.wvm.clone(),
         }
     }
 }
@@ -739,6 +791,12 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Set the WVM parameters, they aren't read from the genesis `config`.
+    pub fn wvm(mut self, wvm: WvmChainParams) -> Self {
+        self.wvm = wvm;
+        self
+    }
+
     /// Add the given fork with the given activation condition to the spec.
     pub fn with_fork<H: Hardfork>(mut self, fork: H, condition: ForkCondition) -> Self {
         self.hardforks.insert(fork, condition);
@@ -897,6 +955,7 @@ impl ChainSpecBuilder {
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
             deposit_contract: None,
+            wvm: self.wvm,
             ..Default::default()
         }
     }
@@ -908,6 +967,7 @@ impl From<&Arc<ChainSpec>> for ChainSpecBuilder {
             chain: Some(value.chain),
             genesis: Some(value.genesis.clone()),
             hardforks: value.hardforks.clone(),
+            wvm: value.wvm.clone(),
         }
     }
 }
@@ -1747,7 +1807,7 @@ Post-merge hard forks (timestamp based):
         "#;
 
         let genesis: Genesis = serde_json::from_str(geth_genesis).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
 
         // assert a bunch of hardforks that should be set
         assert_eq!(
@@ -1895,7 +1955,7 @@ Post-merge hard forks (timestamp based):
         "#;
 
         let genesis = serde_json::from_str::<Genesis>(hive_json).unwrap();
-        let chainspec: ChainSpec = genesis.into();
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
         assert_eq!(chainspec.genesis_hash.get(), None);
         assert_eq!(chainspec.chain, Chain::from_named(NamedChain::Optimism));
         let expected_state_root: B256 =
@@ -2038,7 +2098,7 @@ Post-merge hard forks (timestamp based):
 
         // check that it deserializes properly
         let genesis: Genesis = serde_json::from_str(hive_paris).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from(genesis).unwrap();
 
         // make sure we are at ForkHash("bc0c2605") with Head post-cancun
         let expected_forkid = ForkId { hash: ForkHash([0xbc, 0x0c, 0x26, 0x05]), next: 0 };
@@ -2279,7 +2339,7 @@ Post-merge hard forks (timestamp based):
             ..Default::default()
         };
 
-        let chain_spec: ChainSpec = genesis.into();
+        let chain_spec = ChainSpec::try_from(genesis).unwrap();
 
         let hardforks: Vec<_> = chain_spec.hardforks.forks_iter().map(|(h, _)| h).collect();
         let expected_hardforks = vec![
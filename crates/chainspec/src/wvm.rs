@@ -0,0 +1,192 @@
+//! WVM parameters of the chain spec.
+
+use alloc::vec::Vec;
+use alloy_genesis::Genesis;
+use serde::{Deserialize, Serialize};
+
+/// Key of the [`WvmChainParams`] object in the genesis `config`.
+pub const WVM_CHAIN_PARAMS_KEY: &str = "wvm";
+
+/// WVM specific consensus parameters, committed to in the genesis `config`.
+///
+/// Every parameter is a schedule of values activated at a block:
+///
+/// ```json
+/// "wvm": {
+///   "baseFeeFloor": [{ "block": 1000000, "value": 500000 }],
+///   "gasLimit": [{ "block": 0, "value": 500000000 }],
+///   "minTransactionGas": [{ "block": 0, "value": 500000 }],
+///   "maxTxInputBytes": [{ "block": 0, "value": 3200000 }],
+///   "maxBlockSize": [{ "block": 0, "value": 16777216 }],
+///   "archiveRegistryDelay": [{ "block": 0, "value": 64 }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WvmChainParams {
+    /// Minimum base fee of blocks, `0` when unscheduled.
+    pub base_fee_floor: BlockSchedule,
+    /// Gas limit blocks converge to, the gas limit of the parent is kept when unscheduled.
+    pub gas_limit: BlockSchedule,
+    /// Gas of a transaction not creating a contract.
+    pub min_transaction_gas: BlockSchedule,
+    /// Maximum size of the input of a transaction, derived from the gas limit when unscheduled.
+    pub max_tx_input_bytes: BlockSchedule,
+    /// Maximum encoded size of the transactions of a block.
+    pub max_block_size: BlockSchedule,
+    /// Number of blocks between a block and the block recording its Arweave id in the archive
+    /// registry, the registry is not written when unscheduled.
+    pub archive_registry_delay: BlockSchedule,
+}
+
+impl WvmChainParams {
+    /// Reads the parameters from the genesis `config`, defaulting to no WVM specific rule.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Self, serde_json::Error> {
+        let mut params: Self = genesis
+            .config
+            .extra_fields
+            .get_deserialized(WVM_CHAIN_PARAMS_KEY)
+            .transpose()?
+            .unwrap_or_default();
+        params.base_fee_floor.sort();
+        params.gas_limit.sort();
+        params.min_transaction_gas.sort();
+        params.max_tx_input_bytes.sort();
+        params.max_block_size.sort();
+        params.archive_registry_delay.sort();
+
+        Ok(params)
+    }
+
+    /// Minimum base fee of the block `block_number`, `0` before the first scheduled floor.
+    ///
+    /// The base fee of a block is the EIP-1559 base fee raised to this floor, block builders,
+    /// consensus and the fee estimates of the RPC all apply it.
+    pub fn base_fee_floor_at_block(&self, block_number: u64) -> u64 {
+        self.base_fee_floor.at_block(block_number).unwrap_or_default()
+    }
+}
+
+/// Values of a parameter, sorted by activation block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BlockSchedule(pub Vec<ScheduledValue>);
+
+/// Value of a parameter from `block` on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledValue {
+    /// First block the value applies to.
+    pub block: u64,
+    /// Value of the parameter.
+    pub value: u64,
+}
+
+impl BlockSchedule {
+    /// Value of the parameter at the block `block_number`, `None` before the first scheduled value.
+    pub fn at_block(&self, block_number: u64) -> Option<u64> {
+        self.scheduled_at(block_number).map(|scheduled| scheduled.value)
+    }
+
+    /// The scheduled value in effect at the block `block_number`.
+    pub fn scheduled_at(&self, block_number: u64) -> Option<ScheduledValue> {
+        self.0.iter().rev().find(|scheduled| scheduled.block <= block_number).copied()
+    }
+
+    /// The first value scheduled after the block `block_number`.
+    pub fn next_after(&self, block_number: u64) -> Option<ScheduledValue> {
+        self.0.iter().find(|scheduled| scheduled.block > block_number).copied()
+    }
+
+    fn sort(&mut self) {
+        self.0.sort_by_key(|scheduled| scheduled.block);
+    }
+}
+
+impl<const N: usize> From<[(u64, u64); N]> for BlockSchedule {
+    fn from(values: [(u64, u64); N]) -> Self {
+        let mut schedule = Self(
+            values.into_iter().map(|(block, value)| ScheduledValue { block, value }).collect(),
+        );
+        schedule.sort();
+        schedule
+    }
+}
+
+/// Maximum transaction input size allowed by a block gas limit, `128 KB` per `20k` gas.
+pub const fn max_tx_input_bytes_for_gas_limit(gas_limit: u64) -> usize {
+    (gas_limit * 128 / 20_000) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_wvm_params() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{
+                "config": {
+                    "chainId": 9496,
+                    "wvm": {
+                        "baseFeeFloor": [
+                            { "block": 200, "value": 700000 },
+                            { "block": 100, "value": 500000 }
+                        ],
+                        "gasLimit": [{ "block": 0, "value": 300000000 }]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let params = WvmChainParams::from_genesis(&genesis).unwrap();
+
+        assert_eq!(params.base_fee_floor_at_block(0), 0);
+        assert_eq!(params.base_fee_floor_at_block(99), 0);
+        assert_eq!(params.base_fee_floor_at_block(100), 500_000);
+        assert_eq!(params.base_fee_floor_at_block(199), 500_000);
+        assert_eq!(params.base_fee_floor_at_block(u64::MAX), 700_000);
+        assert_eq!(
+            params.base_fee_floor.next_after(100),
+            Some(ScheduledValue { block: 200, value: 700_000 })
+        );
+        assert_eq!(params.base_fee_floor.next_after(200), None);
+        assert_eq!(
+            params.base_fee_floor.scheduled_at(150),
+            Some(ScheduledValue { block: 100, value: 500_000 })
+        );
+
+        assert_eq!(params.gas_limit, BlockSchedule::from([(0, 300_000_000)]));
+        assert_eq!(params.min_transaction_gas.at_block(0), None);
+        assert_eq!(params.max_tx_input_bytes.at_block(0), None);
+        assert_eq!(params.max_block_size.at_block(0), None);
+        assert_eq!(params.archive_registry_delay.at_block(0), None);
+    }
+
+    #[test]
+    fn genesis_without_wvm_params() {
+        let genesis: Genesis = serde_json::from_str(r#"{"config":{"chainId":9496}}"#).unwrap();
+        assert_eq!(WvmChainParams::from_genesis(&genesis).unwrap(), WvmChainParams::default());
+
+        let genesis: Genesis =
+            serde_json::from_str(r#"{"config":{"chainId":9496,"wvm":{"baseFeeFloor":1}}}"#)
+                .unwrap();
+        assert!(WvmChainParams::from_genesis(&genesis).is_err());
+        assert!(crate::ChainSpec::try_from(genesis).is_err());
+    }
+
+    #[test]
+    fn testnet_base_fee_floor() {
+        let genesis: Genesis =
+            serde_json::from_str(include_str!("../res/genesis/weave_vm_testnet_v0.json")).unwrap();
+        let params = WvmChainParams::from_genesis(&genesis).unwrap();
+
+        // floor the testnet ran with before it was scheduled in the chain spec
+        assert_eq!(params.base_fee_floor_at_block(0), 640_000_000);
+        assert_eq!(params.base_fee_floor.next_after(0), None);
+    }
+
+    #[test]
+    fn max_tx_input_bytes() {
+        assert_eq!(max_tx_input_bytes_for_gas_limit(500_000_000), 3_200_000);
+    }
+}
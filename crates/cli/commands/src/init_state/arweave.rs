@@ -0,0 +1,372 @@
+//! State snapshots archived to Arweave.
+//!
+//! A snapshot is a state dump, as read by `init-state`, split on account boundaries into zstd
+//! compressed chunks. Each chunk is uploaded as its own data item and listed, with its hash, by a
+//! JSON manifest carrying the block and state root of the snapshot.
+
+use alloy_primitives::{keccak256, B256};
+use reth_primitives::SealedHeader;
+use reth_provider::{BlockNumReader, HeaderProvider, ProviderError};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Default Arweave gateway snapshots are downloaded from.
+pub const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net";
+
+/// `Type` tag of snapshot manifests.
+pub const MANIFEST_TYPE: &str = "WeaveVM:State-Snapshot-Manifest";
+
+/// `Type` tag of snapshot chunks.
+pub const CHUNK_TYPE: &str = "WeaveVM:State-Snapshot-Chunk";
+
+/// Encoding of snapshot chunks.
+pub const SNAPSHOT_ENCODING: &str = "JSONL-Zstd";
+
+/// Version of [`StateSnapshotManifest`].
+pub const MANIFEST_VERSION: u8 = 1;
+
+/// Default uncompressed size of a chunk.
+pub const DEFAULT_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Largest manifest accepted.
+pub const MAX_MANIFEST_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Manifest of a state snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshotManifest {
+    /// Version of the manifest format.
+    pub version: u8,
+    /// Block the snapshot is the state of.
+    pub block_number: u64,
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// State root of the block.
+    pub state_root: B256,
+    /// Encoding of the chunks.
+    pub encoding: String,
+    /// Number of accounts in the snapshot.
+    pub accounts: usize,
+    /// Chunks, in the order of the state dump.
+    pub chunks: Vec<SnapshotChunk>,
+}
+
+/// Chunk of a state snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotChunk {
+    /// Arweave id of the chunk.
+    pub arweave_id: String,
+    /// Compressed size of the chunk.
+    pub size: u64,
+    /// Keccak256 hash of the compressed chunk.
+    pub hash: B256,
+}
+
+impl SnapshotChunk {
+    /// Checks that `data` is the content of the chunk.
+    pub fn verify(&self, data: &[u8]) -> eyre::Result<()> {
+        if data.len() as u64 != self.size || keccak256(data) != self.hash {
+            eyre::bail!("Snapshot chunk {} doesn't match the manifest", self.arweave_id);
+        }
+
+        Ok(())
+    }
+}
+
+impl StateSnapshotManifest {
+    /// Checks that the snapshot is the state of `header`.
+    pub fn verify_header(&self, header: &SealedHeader) -> eyre::Result<()> {
+        if header.number != self.block_number {
+            eyre::bail!(
+                "Snapshot is the state of block {}, the database head is block {}",
+                self.block_number,
+                header.number
+            );
+        }
+        if header.hash() != self.block_hash || header.state_root != self.state_root {
+            eyre::bail!(
+                "Snapshot of block {} doesn't match the local block {}",
+                self.block_hash,
+                header.hash()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the snapshot is the state of the last block of `provider`.
+    pub fn verify_head<P: BlockNumReader + HeaderProvider>(
+        &self,
+        provider: &P,
+    ) -> eyre::Result<()> {
+        let block = provider.last_block_number()?;
+        let header = provider
+            .sealed_header(block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+
+        self.verify_header(&header)
+    }
+}
+
+/// Splits a state dump into zstd compressed chunks, of at least `chunk_size` uncompressed bytes
+/// but the last one.
+#[derive(Debug)]
+pub struct SnapshotChunks<R> {
+    reader: R,
+    chunk_size: usize,
+    level: i32,
+    done: bool,
+}
+
+impl<R: BufRead> SnapshotChunks<R> {
+    /// Chunks of the state dump read from `reader`, compressed with zstd `level`.
+    pub const fn new(reader: R, chunk_size: usize, level: i32) -> Self {
+        Self { reader, chunk_size, level, done: false }
+    }
+
+    fn read_chunk(&mut self) -> io::Result<Vec<u8>> {
+        let mut chunk = Vec::new();
+        let mut line = String::new();
+
+        while chunk.len() < self.chunk_size {
+            if self.reader.read_line(&mut line)? == 0 {
+                self.done = true;
+                break
+            }
+            chunk.extend_from_slice(line.as_bytes());
+            line.clear();
+        }
+
+        Ok(chunk)
+    }
+}
+
+impl<R: BufRead> Iterator for SnapshotChunks<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+
+        match self.read_chunk() {
+            Ok(chunk) if chunk.is_empty() => None,
+            Ok(chunk) => Some(zstd::encode_all(chunk.as_slice(), self.level)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Downloads the snapshot of the manifest `manifest_id` into `dir`, checking every chunk against
+/// the manifest.
+///
+/// Returns the manifest and the path of the state dump.
+pub async fn download_snapshot(
+    gateway: &str,
+    manifest_id: &str,
+    dir: &Path,
+) -> eyre::Result<(StateSnapshotManifest, PathBuf)> {
+    let gateway = gateway.trim_end_matches('/');
+    let client = reqwest::Client::new();
+
+    let response =
+        client.get(format!("{gateway}/{manifest_id}")).send().await?.error_for_status()?;
+    let manifest: StateSnapshotManifest =
+        serde_json::from_slice(&read_body(response, MAX_MANIFEST_SIZE).await?)?;
+    if manifest.version != MANIFEST_VERSION || manifest.encoding != SNAPSHOT_ENCODING {
+        eyre::bail!(
+            "Unsupported snapshot manifest version {} with encoding {}",
+            manifest.version,
+            manifest.encoding
+        );
+    }
+    info!(target: "reth::cli",
+        block_number = manifest.block_number,
+        state_root = %manifest.state_root,
+        chunks = manifest.chunks.len(),
+        "Downloading state snapshot"
+    );
+
+    let path = dir.join(format!("state-snapshot-{}.jsonl", manifest.block_number));
+    let mut writer = BufWriter::new(File::create(&path)?);
+
+    for (index, chunk) in manifest.chunks.iter().enumerate() {
+        let response = client
+            .get(format!("{gateway}/{}", chunk.arweave_id))
+            .send()
+            .await?
+            .error_for_status()?;
+        if response.content_length().is_some_and(|len| len != chunk.size) {
+            eyre::bail!("Snapshot chunk {} doesn't match the manifest", chunk.arweave_id);
+        }
+
+        let data = read_body(response, chunk.size).await?;
+        chunk.verify(&data)?;
+        zstd::stream::copy_decode(data.as_ref(), &mut writer)?;
+
+        info!(target: "reth::cli",
+            chunk = index + 1,
+            total = manifest.chunks.len(),
+            "Downloaded snapshot chunk"
+        );
+    }
+    writer.flush()?;
+
+    Ok((manifest, path))
+}
+
+/// Reads the body of `response`, failing as soon as more than `limit` bytes are received.
+async fn read_body(mut response: reqwest::Response, limit: u64) -> eyre::Result<Vec<u8>> {
+    let mut body = Vec::new();
+
+    while let Some(bytes) = response.chunk().await? {
+        if body.len() as u64 + bytes.len() as u64 > limit {
+            eyre::bail!("Response of {} is larger than {} bytes", response.url(), limit);
+        }
+        body.extend_from_slice(&bytes);
+    }
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{Address, U256};
+    use reth_chainspec::{Chain, ChainSpec};
+    use reth_db_common::init::{export_state_dump, init_genesis};
+    use reth_provider::{test_utils::create_test_provider_factory_with_chain_spec, DBProvider};
+    use std::{collections::BTreeMap, sync::Arc};
+
+    fn dump() -> String {
+        (0..100).map(|i| format!("{{\"address\":\"{i}\",\"balance\":\"0x{i:x}\"}}\n")).collect()
+    }
+
+    #[test]
+    fn chunks_split_on_lines() {
+        let dump = dump();
+        let chunks = SnapshotChunks::new(dump.as_bytes(), 256, 3)
+            .map(|chunk| zstd::decode_all(chunk.unwrap().as_slice()).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.ends_with(b"\n")));
+        assert!(chunks[..chunks.len() - 1].iter().all(|chunk| chunk.len() >= 256));
+        assert_eq!(chunks.concat(), dump.into_bytes());
+
+        assert_eq!(SnapshotChunks::new(&[][..], 256, 3).count(), 0);
+    }
+
+    #[test]
+    fn verify_chunks() {
+        let data =
+            SnapshotChunks::new(dump().as_bytes(), DEFAULT_CHUNK_SIZE, 3).next().unwrap().unwrap();
+        let chunk = SnapshotChunk {
+            arweave_id: String::from("chunk"),
+            size: data.len() as u64,
+            hash: keccak256(&data),
+        };
+        assert!(chunk.verify(&data).is_ok());
+
+        let mut tampered = data.clone();
+        tampered[0] ^= 1;
+        assert!(chunk.verify(&tampered).is_err());
+        assert!(chunk.verify(&data[1..]).is_err());
+    }
+
+    #[test]
+    fn verify_snapshot_header() {
+        let header = SealedHeader::new(
+            reth_primitives::Header {
+                number: 10,
+                state_root: B256::with_last_byte(1),
+                ..Default::default()
+            },
+            B256::with_last_byte(2),
+        );
+        let manifest = StateSnapshotManifest {
+            version: MANIFEST_VERSION,
+            block_number: 10,
+            block_hash: header.hash(),
+            state_root: header.state_root,
+            encoding: SNAPSHOT_ENCODING.to_string(),
+            accounts: 0,
+            chunks: vec![],
+        };
+        assert!(manifest.verify_header(&header).is_ok());
+
+        let other_root = StateSnapshotManifest { state_root: B256::ZERO, ..manifest.clone() };
+        assert!(other_root.verify_header(&header).is_err());
+        let other_block = StateSnapshotManifest { block_number: 11, ..manifest };
+        assert!(other_block.verify_header(&header).is_err());
+    }
+
+    #[test]
+    fn export_chunk_verify_roundtrip() {
+        let alloc = (1..=64u8)
+            .map(|i| {
+                let account = GenesisAccount {
+                    nonce: Some(i as u64),
+                    balance: U256::from(i),
+                    storage: Some(BTreeMap::from([(
+                        B256::with_last_byte(i),
+                        B256::with_last_byte(i),
+                    )])),
+                    ..Default::default()
+                };
+                (Address::with_last_byte(i), account)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let chain_spec = Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc, ..Default::default() },
+            ..Default::default()
+        });
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(&factory).unwrap();
+
+        let mut provider = factory.provider().unwrap();
+        let header = provider.sealed_header(0).unwrap().unwrap();
+        let mut dump = Vec::new();
+        let accounts = export_state_dump(provider.tx_mut(), header.state_root, &mut dump).unwrap();
+        assert_eq!(accounts, 64);
+
+        let compressed =
+            SnapshotChunks::new(dump.as_slice(), 1024, 3).collect::<Result<Vec<_>, _>>().unwrap();
+        let manifest = StateSnapshotManifest {
+            version: MANIFEST_VERSION,
+            block_number: header.number,
+            block_hash: header.hash(),
+            state_root: header.state_root,
+            encoding: SNAPSHOT_ENCODING.to_string(),
+            accounts,
+            chunks: compressed
+                .iter()
+                .enumerate()
+                .map(|(index, data)| SnapshotChunk {
+                    arweave_id: format!("chunk-{index}"),
+                    size: data.len() as u64,
+                    hash: keccak256(data),
+                })
+                .collect(),
+        };
+        assert!(manifest.chunks.len() > 1);
+        manifest.verify_head(&provider).unwrap();
+
+        // Same checks as the download of a snapshot
+        let mut restored = Vec::new();
+        for (chunk, data) in manifest.chunks.iter().zip(&compressed) {
+            chunk.verify(data).unwrap();
+            zstd::stream::copy_decode(data.as_slice(), &mut restored).unwrap();
+        }
+        assert_eq!(restored, dump);
+        assert!(manifest.chunks[0].verify(&compressed[1]).is_err());
+    }
+}
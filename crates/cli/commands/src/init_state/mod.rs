@@ -12,9 +12,15 @@ use reth_provider::{
     BlockNumReader, DatabaseProviderFactory, StaticFileProviderFactory, StaticFileWriter,
 };
 
-use std::{fs::File, io::BufReader, path::PathBuf, str::FromStr};
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+    str::FromStr,
+};
 use tracing::info;
 
+pub mod arweave;
 pub mod without_evm;
 
 /// Initializes the database with the genesis block.
@@ -40,8 +46,23 @@ pub struct InitStateCommand<C: ChainSpecParser> {
     ///
     /// Allows init at a non-genesis block. Caution! Blocks must be manually imported up until
     /// and including the non-genesis block to init chain at. See 'import' command.
-    #[arg(value_name = "STATE_DUMP_FILE", verbatim_doc_comment)]
-    pub state: PathBuf,
+    #[arg(
+        value_name = "STATE_DUMP_FILE",
+        verbatim_doc_comment,
+        required_unless_present = "arweave_manifest"
+    )]
+    pub state: Option<PathBuf>,
+
+    /// Arweave id of the manifest of a state snapshot, downloaded instead of a state dump file.
+    ///
+    /// Every chunk is checked against the manifest, and the manifest against the last block of
+    /// the database.
+    #[arg(long, value_name = "ARWEAVE_ID", conflicts_with = "state")]
+    pub arweave_manifest: Option<String>,
+
+    /// Arweave gateway the state snapshot is downloaded from.
+    #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = arweave::DEFAULT_ARWEAVE_GATEWAY)]
+    pub arweave_gateway: String,
 
     /// Specifies whether to initialize the state without relying on EVM historical data.
     ///
@@ -73,7 +94,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
     ) -> eyre::Result<()> {
         info!(target: "reth::cli", "Reth init-state starting");
 
-        let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let Environment { config, provider_factory, data_dir } =
+            self.env.init::<N>(AccessRights::RW)?;
+
+        // the snapshot is downloaded before opening the write transaction
+        let snapshot = match &self.arweave_manifest {
+            Some(manifest_id) => Some(
+                arweave::download_snapshot(&self.arweave_gateway, manifest_id, data_dir.data_dir())
+                    .await?,
+            ),
+            None => None,
+        };
 
         let static_file_provider = provider_factory.static_file_provider();
         let provider_rw = provider_factory.database_provider_rw()?;
@@ -117,15 +148,27 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InitStateC
             }
         }
 
+        let state = match &snapshot {
+            Some((manifest, path)) => {
+                manifest.verify_head(&provider_rw)?;
+                path.clone()
+            }
+            None => self.state.ok_or_else(|| eyre::eyre!("State dump file must be provided"))?,
+        };
+
         info!(target: "reth::cli", "Initiating state dump");
 
-        let file = File::open(self.state)?;
+        let file = File::open(&state)?;
         let reader = BufReader::new(file);
 
         let hash = init_from_state_dump(reader, &provider_rw, config.stages.etl)?;
 
         provider_rw.commit()?;
 
+        if snapshot.is_some() {
+            fs::remove_file(state)?;
+        }
+
         info!(target: "reth::cli", hash = ?hash, "Genesis block written");
         Ok(())
     }
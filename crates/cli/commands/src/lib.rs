@@ -22,5 +22,6 @@ pub mod recover;
 pub mod stage;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
+pub mod wvm;
 
 pub use node::NodeCommand;
@@ -0,0 +1,60 @@
+//! Command managing the LoadDb schema of the configured driver.
+
+use clap::{Parser, Subcommand};
+use load_db::{
+    config::LoadDbConfig,
+    migrations::{SchemaStatus, LOADDB_SCHEMA_VERSION},
+};
+use tracing::info;
+
+/// `reth wvm loaddb` command
+///
+/// The driver is selected the same way as for the node, through `LOADDB_DRIVER` and the driver
+/// specific variables.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth wvm loaddb` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Applies the pending schema migrations
+    Migrate,
+    /// Shows the schema version of the database and the one required by this node
+    Status,
+}
+
+impl Command {
+    /// Execute `wvm loaddb` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let config = LoadDbConfig::from_env()?;
+        info!(target: "reth::cli", driver = config.driver_name(), "Connecting to LoadDb");
+        let db = config.connect()?;
+
+        let current = db.schema_version().await?;
+
+        match self.command {
+            Subcommands::Migrate => {
+                if let SchemaStatus::Newer { current } = SchemaStatus::from_version(current) {
+                    eyre::bail!(
+                        "LoadDb schema version {} is newer than the supported version {}",
+                        current,
+                        LOADDB_SCHEMA_VERSION
+                    );
+                }
+
+                let version = db.migrate().await?;
+                info!(target: "reth::cli", from = ?current, to = version, "LoadDb schema migrated");
+            }
+            Subcommands::Status => {
+                println!("Schema version: {}", current.unwrap_or_default());
+                println!("Required version: {}", LOADDB_SCHEMA_VERSION);
+                println!("Status: {:?}", SchemaStatus::from_version(current));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//! WVM specific commands.
+
+use clap::{Parser, Subcommand};
+
+pub mod loaddb;
+
+/// `reth wvm` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth wvm` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// LoadDb schema management
+    #[command(name = "loaddb")]
+    LoadDb(loaddb::Command),
+}
+
+impl Command {
+    /// Execute `wvm` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::LoadDb(command) => command.execute().await,
+        }
+    }
+}
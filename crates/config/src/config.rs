@@ -249,7 +249,7 @@ impl Default for ExecutionConfig {
             max_changes: Some(5_000_000),
             // 50k full blocks of 30M gas
             // WVM: 300kk
-            max_cumulative_gas: Some((*ETHEREUM_BLOCK_GAS_LIMIT) * 50_000),
+            max_cumulative_gas: Some(ETHEREUM_BLOCK_GAS_LIMIT * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
         }
@@ -411,6 +411,7 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    wvm_tags,
                     receipts_log_filter,
                 },
         } = other;
@@ -426,6 +427,7 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.wvm_tags = self.segments.wvm_tags.or(wvm_tags);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -945,6 +947,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                wvm_tags: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -960,6 +963,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                wvm_tags: Some(PruneMode::Distance(4000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -978,6 +982,7 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.wvm_tags, Some(PruneMode::Distance(4000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -280,7 +280,9 @@ impl StorageInner {
     {
         // check previous block for base fee
         let base_fee_per_gas = self.headers.get(&self.best_block).and_then(|parent| {
-            parent.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
+            parent.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp)).map(
+                |base_fee| base_fee.max(chain_spec.base_fee_floor_at_block(self.best_block + 1)),
+            )
         });
 
         let blob_gas_used = chain_spec.is_cancun_active_at_timestamp(timestamp).then(|| {
@@ -298,7 +300,7 @@ impl StorageInner {
             withdrawals_root: withdrawals.map(|w| proofs::calculate_withdrawals_root(w)),
             difficulty: U256::from(2),
             number: self.best_block + 1,
-            gas_limit: chain_spec.max_gas_limit(),
+            gas_limit: chain_spec.block_gas_limit_at_block(self.best_block + 1),
             timestamp,
             base_fee_per_gas,
             blob_gas_used,
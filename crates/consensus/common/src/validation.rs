@@ -1,7 +1,11 @@
 //! Collection of methods for block validation.
 
-use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
-use alloy_eips::eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK};
+use alloy_consensus::{constants::MAXIMUM_EXTRA_DATA_SIZE, Transaction as _};
+use alloy_eips::{
+    eip2718::Encodable2718,
+    eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
+};
+use alloy_primitives::B256;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_consensus::ConsensusError;
 use reth_primitives::{EthereumHardfork, GotExpected, Header, SealedBlock, SealedHeader};
@@ -79,7 +83,7 @@ pub fn validate_cancun_gas(block: &SealedBlock) -> Result<(), ConsensusError> {
 /// - Compares the transactions root in the block header to the block body
 /// - Pre-execution transaction validation
 /// - (Optionally) Compares the receipts root in the block header to the block body
-pub fn validate_block_pre_execution<ChainSpec: EthereumHardforks>(
+pub fn validate_block_pre_execution<ChainSpec: EthChainSpec + EthereumHardforks>(
     block: &SealedBlock,
     chain_spec: &ChainSpec,
 ) -> Result<(), ConsensusError> {
@@ -105,6 +109,45 @@ pub fn validate_block_pre_execution<ChainSpec: EthereumHardforks>(
         validate_cancun_gas(block)?;
     }
 
+    validate_block_size(block, chain_spec)?;
+    validate_min_transaction_gas(block, chain_spec)?;
+
+    Ok(())
+}
+
+/// WVM: validates that every transaction of the block has a gas limit of at least the minimum
+/// transaction gas scheduled in the chain spec. Blocks before the first scheduled minimum are not
+/// checked.
+#[inline]
+pub fn validate_min_transaction_gas<ChainSpec: EthChainSpec>(
+    block: &SealedBlock,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    let Some(min_gas) = chain_spec.min_transaction_gas_at_block(block.number) else {
+        return Ok(())
+    };
+    if let Some(gas_limit) =
+        block.body.transactions.iter().map(|tx| tx.gas_limit()).find(|gas| *gas < min_gas)
+    {
+        return Err(ConsensusError::TransactionGasLimitBelowMinimum { gas_limit, min_gas })
+    }
+
+    Ok(())
+}
+
+/// WVM: validates that the encoded size of the transactions of the block doesn't exceed the maximum
+/// block size scheduled in the chain spec. Blocks before the first scheduled size are not limited.
+#[inline]
+pub fn validate_block_size<ChainSpec: EthChainSpec>(
+    block: &SealedBlock,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    let Some(max_size) = chain_spec.max_block_size_at_block(block.number) else { return Ok(()) };
+    let size: usize = block.body.transactions.iter().map(Encodable2718::encode_2718_len).sum();
+    if size > max_size {
+        return Err(ConsensusError::BlockSizeExceedsMax { size, max_size })
+    }
+
     Ok(())
 }
 
@@ -164,6 +207,29 @@ pub fn validate_header_extradata(header: &Header) -> Result<(), ConsensusError>
     }
 }
 
+/// WVM: validates that while the archive registry is written, the extra data of the header is
+/// either empty or the non-zero 32 bytes Arweave id the pre-block system call records.
+#[inline]
+pub fn validate_archive_registry_extra_data<ChainSpec: EthChainSpec>(
+    header: &Header,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    let Some(delay) = chain_spec.archive_registry_delay_at_block(header.number) else {
+        return Ok(())
+    };
+
+    let len = header.extra_data.len();
+    // blocks before the delay have no archived block to record
+    let is_id = header.number >= delay &&
+        len == B256::len_bytes() &&
+        header.extra_data.iter().any(|byte| *byte != 0);
+    if len != 0 && !is_id {
+        return Err(ConsensusError::ArchiveRegistryIdInvalid { len })
+    }
+
+    Ok(())
+}
+
 /// Validates against the parent hash and number.
 ///
 /// This function ensures that the header block number is sequential and that the hash of the parent
@@ -210,6 +276,8 @@ pub fn validate_against_parent_eip1559_base_fee<ChainSpec: EthChainSpec + Ethere
                     .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp))
                     .ok_or(ConsensusError::BaseFeeMissing)?
             };
+        let expected_base_fee =
+            expected_base_fee.max(chain_spec.base_fee_floor_at_block(header.number));
         if expected_base_fee != base_fee {
             return Err(ConsensusError::BaseFeeDiff(GotExpected {
                 expected: expected_base_fee,
@@ -281,7 +349,9 @@ mod tests {
     };
     use mockall::mock;
     use rand::Rng;
-    use reth_chainspec::ChainSpecBuilder;
+    use reth_chainspec::{
+        BlockSchedule, ChainSpec, ChainSpecBuilder, WvmChainParams, MIN_TRANSACTION_GAS,
+    };
     use reth_primitives::{
         proofs, Account, BlockBody, Transaction, TransactionSigned, Withdrawals,
     };
@@ -542,4 +612,138 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn base_fee_floor() {
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                base_fee_floor: BlockSchedule::from([(2, 1_000)]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::mainnet().london_activated().build()
+        };
+        let parent = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+
+        let header = Header { number: 2, base_fee_per_gas: Some(7), ..Default::default() };
+        assert_eq!(
+            validate_against_parent_eip1559_base_fee(&header, &parent, &chain_spec),
+            Err(ConsensusError::BaseFeeDiff(GotExpected { got: 7, expected: 1_000 }))
+        );
+
+        let header = Header { base_fee_per_gas: Some(1_000), ..header };
+        assert_eq!(validate_against_parent_eip1559_base_fee(&header, &parent, &chain_spec), Ok(()));
+
+        // blocks before the floor keep the EIP-1559 base fee
+        let header = Header { number: 1, base_fee_per_gas: Some(7), ..Default::default() };
+        assert_eq!(validate_against_parent_eip1559_base_fee(&header, &parent, &chain_spec), Ok(()));
+    }
+
+    #[test]
+    fn max_block_size() {
+        let transaction = mock_blob_tx(1, 1);
+        let size = transaction.encode_2718_len();
+        let block = SealedBlock::new(
+            SealedHeader::default(),
+            BlockBody { transactions: vec![transaction], ..Default::default() },
+        );
+
+        // not limited while no size is scheduled
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        assert_eq!(validate_block_size(&block, &chain_spec), Ok(()));
+
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                max_block_size: BlockSchedule::from([(0, size as u64 - 1), (1, size as u64)]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::mainnet().build()
+        };
+        assert_eq!(
+            validate_block_size(&block, &chain_spec),
+            Err(ConsensusError::BlockSizeExceedsMax { size, max_size: size - 1 })
+        );
+
+        let block = SealedBlock::new(
+            SealedHeader::new(Header { number: 1, ..Default::default() }, Default::default()),
+            block.body,
+        );
+        assert_eq!(validate_block_size(&block, &chain_spec), Ok(()));
+    }
+
+    #[test]
+    fn min_transaction_gas() {
+        // tx gas limit is 10
+        let block = SealedBlock::new(
+            SealedHeader::default(),
+            BlockBody { transactions: vec![mock_blob_tx(1, 1)], ..Default::default() },
+        );
+
+        // no minimum scheduled on the chain
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        assert_eq!(validate_min_transaction_gas(&block, &chain_spec), Ok(()));
+
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                min_transaction_gas: BlockSchedule::from([(1, MIN_TRANSACTION_GAS)]),
+                ..Default::default()
+            },
+            ..chain_spec
+        };
+        // before the first scheduled minimum
+        assert_eq!(validate_min_transaction_gas(&block, &chain_spec), Ok(()));
+
+        let block = SealedBlock::new(
+            SealedHeader::new(Header { number: 1, ..Default::default() }, Default::default()),
+            block.body,
+        );
+        assert_eq!(
+            validate_min_transaction_gas(&block, &chain_spec),
+            Err(ConsensusError::TransactionGasLimitBelowMinimum {
+                gas_limit: 10,
+                min_gas: MIN_TRANSACTION_GAS
+            })
+        );
+    }
+
+    #[test]
+    fn archive_registry_extra_data() {
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                archive_registry_delay: BlockSchedule::from([(10, 2)]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::mainnet().build()
+        };
+        let header = |number, extra_data: &[u8]| Header {
+            number,
+            extra_data: Bytes::copy_from_slice(extra_data),
+            ..Default::default()
+        };
+        let arweave_id = [1; 32];
+
+        // any extra data before the registry is written
+        assert_eq!(
+            validate_archive_registry_extra_data(&header(9, b"reth/v1.1.1"), &chain_spec),
+            Ok(())
+        );
+
+        assert_eq!(validate_archive_registry_extra_data(&header(10, &[]), &chain_spec), Ok(()));
+        assert_eq!(
+            validate_archive_registry_extra_data(&header(10, &arweave_id), &chain_spec),
+            Ok(())
+        );
+        assert_eq!(
+            validate_archive_registry_extra_data(&header(10, b"reth/v1.1.1"), &chain_spec),
+            Err(ConsensusError::ArchiveRegistryIdInvalid { len: 11 })
+        );
+        assert_eq!(
+            validate_archive_registry_extra_data(&header(10, &[0; 32]), &chain_spec),
+            Err(ConsensusError::ArchiveRegistryIdInvalid { len: 32 })
+        );
+    }
 }
@@ -145,6 +145,35 @@ pub enum ConsensusError {
         gas_spent_by_tx: Vec<(u64, u64)>,
     },
 
+    /// Error when the encoded size of the transactions of a block exceeds the maximum block size.
+    #[display("block size ({size}) is greater than the maximum block size ({max_size})")]
+    BlockSizeExceedsMax {
+        /// The encoded size of the transactions of the block.
+        size: usize,
+        /// The maximum block size scheduled in the chain spec.
+        max_size: usize,
+    },
+
+    /// Error when a transaction of the block has a gas limit below the minimum transaction gas
+    /// scheduled in the chain spec.
+    #[display(
+        "transaction gas limit ({gas_limit}) is below the minimum transaction gas ({min_gas})"
+    )]
+    TransactionGasLimitBelowMinimum {
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The minimum transaction gas of the block.
+        min_gas: u64,
+    },
+
+    /// Error when the extra data of a block recording an id in the archive registry is neither
+    /// empty nor a 32 bytes Arweave id.
+    #[display("extra data of {len} bytes is not an Arweave id")]
+    ArchiveRegistryIdInvalid {
+        /// The length of the extra data.
+        len: usize,
+    },
+
     /// Error when the hash of block ommer is different from the expected hash.
     #[display("mismatched block ommer hash: {_0}")]
     BodyOmmersHashDiff(GotExpectedBoxed<B256>),
@@ -335,6 +364,11 @@ pub enum ConsensusError {
     #[display("block base fee mismatch: {_0}")]
     BaseFeeDiff(GotExpected<u64>),
 
+    /// Error when the block's gas limit doesn't converge to the gas limit scheduled in the chain
+    /// spec.
+    #[display("block gas limit mismatch: {_0}")]
+    GasLimitDiff(GotExpected<u64>),
+
     /// Error when there is an invalid excess blob gas.
     #[display(
         "invalid excess blob gas: {diff}; \
@@ -15,17 +15,39 @@ use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_consensus_common::validation::{
     validate_4844_header_standalone, validate_against_parent_4844,
     validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
-    validate_against_parent_timestamp, validate_block_pre_execution, validate_header_base_fee,
-    validate_header_extradata, validate_header_gas,
+    validate_against_parent_timestamp, validate_archive_registry_extra_data,
+    validate_block_pre_execution, validate_header_base_fee, validate_header_extradata,
+    validate_header_gas,
 };
 use reth_primitives::{
-    constants::MINIMUM_GAS_LIMIT, BlockWithSenders, Header, SealedBlock, SealedHeader,
+    constants::MINIMUM_GAS_LIMIT, BlockWithSenders, GotExpected, Header, SealedBlock, SealedHeader,
 };
 use std::{fmt::Debug, sync::Arc, time::SystemTime};
 
 /// The bound divisor of the gas limit, used in update calculations.
 pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
 
+/// Calculates the gas limit of the next block, moving from the parent's gas limit towards
+/// `desired_gas_limit` by at most the bound allowed by [`GAS_LIMIT_BOUND_DIVISOR`].
+pub fn calculate_next_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let min_gas_limit = (parent_gas_limit - delta).max(MINIMUM_GAS_LIMIT);
+    let max_gas_limit = parent_gas_limit + delta;
+
+    desired_gas_limit.clamp(min_gas_limit.min(max_gas_limit), max_gas_limit)
+}
+
+/// WVM: gas limit of the block after `parent`, converging to the gas limit scheduled in the chain
+/// spec. The parent's gas limit is kept when the chain doesn't schedule one.
+pub fn next_block_gas_limit<ChainSpec: EthChainSpec>(
+    chain_spec: &ChainSpec,
+    parent: &Header,
+) -> u64 {
+    chain_spec.scheduled_gas_limit_at_block(parent.number + 1).map_or(parent.gas_limit, |desired| {
+        calculate_next_block_gas_limit(parent.gas_limit, desired)
+    })
+}
+
 mod validation;
 pub use validation::validate_block_post_execution;
 
@@ -87,6 +109,17 @@ impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec>
             return Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: header.gas_limit })
         }
 
+        // WVM: once scheduled, the gas limit converges to the one of the chain spec
+        if self.chain_spec.scheduled_gas_limit_at_block(header.number).is_some() {
+            let expected = next_block_gas_limit(&*self.chain_spec, parent);
+            if header.gas_limit != expected {
+                return Err(ConsensusError::GasLimitDiff(GotExpected {
+                    got: header.gas_limit,
+                    expected,
+                }))
+            }
+        }
+
         Ok(())
     }
 }
@@ -97,6 +130,7 @@ impl<ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug> Consensu
     fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
         validate_header_gas(header)?;
         validate_header_base_fee(header, &self.chain_spec)?;
+        validate_archive_registry_extra_data(header, &self.chain_spec)?;
 
         // EIP-4895: Beacon chain push withdrawals as operations
         if self.chain_spec.is_shanghai_active_at_timestamp(header.timestamp) &&
@@ -229,7 +263,7 @@ impl<ChainSpec: Send + Sync + EthChainSpec + EthereumHardforks + Debug> Consensu
 mod tests {
     use super::*;
     use alloy_primitives::{Sealable, B256};
-    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+    use reth_chainspec::{BlockSchedule, ChainSpec, ChainSpecBuilder, WvmChainParams};
     use reth_primitives::proofs;
 
     fn header_with_gas_limit(gas_limit: u64) -> SealedHeader {
@@ -278,6 +312,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_next_block_gas_limit() {
+        let consensus = EthBeaconConsensus::new(Arc::new(ChainSpec::default()));
+        let parent = header_with_gas_limit(300_000_000);
+
+        let increased = calculate_next_block_gas_limit(parent.gas_limit, 500_000_000);
+        assert_eq!(increased, parent.gas_limit + parent.gas_limit / GAS_LIMIT_BOUND_DIVISOR - 1);
+        assert_eq!(
+            consensus.validate_against_parent_gas_limit(&header_with_gas_limit(increased), &parent),
+            Ok(())
+        );
+
+        let decreased = calculate_next_block_gas_limit(parent.gas_limit, 30_000_000);
+        assert_eq!(decreased, parent.gas_limit - parent.gas_limit / GAS_LIMIT_BOUND_DIVISOR + 1);
+        assert_eq!(
+            consensus.validate_against_parent_gas_limit(&header_with_gas_limit(decreased), &parent),
+            Ok(())
+        );
+
+        assert_eq!(calculate_next_block_gas_limit(parent.gas_limit, 300_000_100), 300_000_100);
+        assert_eq!(calculate_next_block_gas_limit(MINIMUM_GAS_LIMIT, 0), MINIMUM_GAS_LIMIT);
+    }
+
+    #[test]
+    fn test_scheduled_gas_limit() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .wvm(WvmChainParams {
+                gas_limit: BlockSchedule::from([(11, 500_000_000)]),
+                ..Default::default()
+            })
+            .build();
+        let consensus = EthBeaconConsensus::new(Arc::new(chain_spec));
+        let header = |number, gas_limit| {
+            SealedHeader::new(Header { number, gas_limit, ..Default::default() }, B256::ZERO)
+        };
+
+        // not scheduled yet, any change within bounds is valid
+        let parent = header(9, 300_000_000);
+        assert_eq!(next_block_gas_limit(consensus.chain_spec.as_ref(), &parent), 300_000_000);
+        assert_eq!(
+            consensus.validate_against_parent_gas_limit(&header(10, 300_000_005), &parent),
+            Ok(())
+        );
+
+        // once scheduled, the gas limit must converge to the scheduled one
+        let parent = header(10, 300_000_000);
+        let expected = calculate_next_block_gas_limit(parent.gas_limit, 500_000_000);
+        assert_eq!(next_block_gas_limit(consensus.chain_spec.as_ref(), &parent), expected);
+        assert_eq!(
+            consensus.validate_against_parent_gas_limit(&header(11, expected), &parent),
+            Ok(())
+        );
+        assert_eq!(
+            consensus.validate_against_parent_gas_limit(&header(11, 300_000_005), &parent),
+            Err(ConsensusError::GasLimitDiff(GotExpected { got: 300_000_005, expected }))
+        );
+    }
+
     #[test]
     fn test_valid_gas_limit_decrease_within_limit() {
         let parent = header_with_gas_limit(GAS_LIMIT_BOUND_DIVISOR * 10);
@@ -158,7 +158,19 @@ where
 
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.transactions.len());
+        // WVM: every transaction must cover the chain's minimum transaction gas, once scheduled
+        let min_transaction_gas = self.chain_spec.min_transaction_gas_at_block(block.header.number);
         for (sender, transaction) in block.transactions_with_sender() {
+            if let Some(min_transaction_gas) =
+                min_transaction_gas.filter(|min_gas| transaction.gas_limit() < *min_gas)
+            {
+                return Err(BlockValidationError::TransactionGasLimitBelowMinimum {
+                    transaction_gas_limit: transaction.gas_limit(),
+                    min_transaction_gas,
+                }
+                .into())
+            }
+
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
             let block_available_gas = block.header.gas_limit - cumulative_gas_used;
@@ -303,10 +315,13 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
         eip7685::EMPTY_REQUESTS_HASH,
     };
-    use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256};
-    use reth_chainspec::{ChainSpecBuilder, ForkCondition};
-    use reth_evm::execute::{
-        BasicBlockExecutorProvider, BatchExecutor, BlockExecutorProvider, Executor,
+    use alloy_primitives::{b256, fixed_bytes, keccak256, Address, Bytes, TxKind, B256};
+    use reth_chainspec::{
+        BlockSchedule, ChainSpecBuilder, ForkCondition, WvmChainParams, MIN_TRANSACTION_GAS,
+    };
+    use reth_evm::{
+        execute::{BasicBlockExecutorProvider, BatchExecutor, BlockExecutorProvider, Executor},
+        system_calls::{ARCHIVE_REGISTRY_ADDRESS, ARCHIVE_REGISTRY_CODE},
     };
     use reth_execution_types::BlockExecutionOutput;
     use reth_primitives::{public_key_to_address, Account, Block, BlockBody, Transaction};
@@ -718,6 +733,99 @@ mod tests {
 
         db
     }
+
+    #[test]
+    fn archive_registry_call() {
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                archive_registry_delay: BlockSchedule::from([(0, 2)]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build()
+        });
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            ARCHIVE_REGISTRY_ADDRESS,
+            Account {
+                balance: U256::ZERO,
+                bytecode_hash: Some(keccak256(ARCHIVE_REGISTRY_CODE.clone())),
+                nonce: 1,
+            },
+            Some(ARCHIVE_REGISTRY_CODE.clone()),
+            HashMap::default(),
+        );
+
+        let provider = executor_provider(chain_spec);
+        let mut executor = provider.batch_executor(StateProviderDatabase::new(&db));
+
+        let arweave_id = b256!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+        for (number, extra_data) in [
+            (1, Bytes::copy_from_slice(arweave_id.as_slice())),
+            (5, Bytes::copy_from_slice(arweave_id.as_slice())),
+            (6, Bytes::new()),
+        ] {
+            let header = Header { number, extra_data, ..Header::default() };
+            executor
+                .execute_and_verify_one(
+                    (
+                        &BlockWithSenders {
+                            block: Block { header, body: BlockBody::default() },
+                            senders: vec![],
+                        },
+                        U256::ZERO,
+                    )
+                        .into(),
+                )
+                .unwrap();
+        }
+
+        // only the id carried by block 5 is recorded, for block 3
+        for (archived_block, expected) in [(3, U256::from_be_bytes(arweave_id.0)), (4, U256::ZERO)]
+        {
+            let storage = executor.with_state_mut(|state| {
+                state.storage(ARCHIVE_REGISTRY_ADDRESS, U256::from(archived_block)).unwrap()
+            });
+            assert_eq!(storage, expected);
+        }
+        let system_account = executor.with_state_mut(|state| state.basic(SYSTEM_ADDRESS).unwrap());
+        assert!(system_account.is_none());
+    }
+
+    #[test]
+    fn archive_registry_contract_missing() {
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                archive_registry_delay: BlockSchedule::from([(0, 2)]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build()
+        });
+
+        let db = StateProviderTest::default();
+        let provider = executor_provider(chain_spec);
+        let mut executor = provider.batch_executor(StateProviderDatabase::new(&db));
+
+        let header = Header { number: 5, ..Header::default() };
+        let err = executor
+            .execute_and_verify_one(
+                (
+                    &BlockWithSenders {
+                        block: Block { header, body: BlockBody::default() },
+                        senders: vec![],
+                    },
+                    U256::ZERO,
+                )
+                    .into(),
+            )
+            .expect_err("Recording an archive without the registry contract should fail");
+
+        assert_eq!(
+            err.as_validation().unwrap().clone(),
+            BlockValidationError::ArchiveRegistryContractMissing
+        );
+    }
+
     #[test]
     fn eip_2935_pre_fork() {
         let db = create_state_provider_with_block_hashes(1);
@@ -1201,4 +1309,70 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn min_transaction_gas() {
+        let chain_spec = ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build();
+
+        let mut db = StateProviderTest::default();
+
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+
+        db.insert_account(
+            sender_address,
+            Account { nonce: 1, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::default(),
+        );
+
+        let mut header = chain_spec.genesis_header().clone();
+        header.gas_limit = 1_500_000;
+        header.gas_used = 21_000;
+
+        let tx = sign_tx_with_key_pair(
+            sender_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 1,
+                gas_price: header.base_fee_per_gas.unwrap().into(),
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(1)),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+        );
+        let block =
+            Block { header, body: BlockBody { transactions: vec![tx], ..Default::default() } }
+                .with_recovered_senders()
+                .unwrap();
+
+        // without a scheduled minimum a plain transfer is valid
+        let provider = executor_provider(Arc::new(chain_spec.clone()));
+        let executor = provider.executor(StateProviderDatabase::new(&db));
+        let BlockExecutionOutput { receipts, .. } =
+            executor.execute((&block, U256::ZERO).into()).unwrap();
+        assert!(receipts.first().unwrap().success);
+
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                min_transaction_gas: BlockSchedule::from([(0, MIN_TRANSACTION_GAS)]),
+                ..Default::default()
+            },
+            ..chain_spec
+        };
+        let provider = executor_provider(Arc::new(chain_spec));
+        let executor = provider.executor(StateProviderDatabase::new(&db));
+        let err = executor
+            .execute((&block, U256::ZERO).into())
+            .expect_err("A transaction below the scheduled minimum gas should fail");
+        assert_eq!(
+            *err.as_validation().unwrap(),
+            BlockValidationError::TransactionGasLimitBelowMinimum {
+                transaction_gas_limit: 21_000,
+                min_transaction_gas: MIN_TRANSACTION_GAS,
+            }
+        );
+    }
 }
@@ -22,10 +22,9 @@ use core::convert::Infallible;
 use alloc::{sync::Arc, vec::Vec};
 use alloy_primitives::{Address, Bytes, TxKind, U256};
 use reth_chainspec::{ChainSpec, Head};
+use reth_ethereum_consensus::next_block_gas_limit;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv, NextBlockEnvAttributes};
-use reth_primitives::{
-    constants::ETHEREUM_BLOCK_GAS_LIMIT, transaction::FillTxEnv, Header, TransactionSigned,
-};
+use reth_primitives::{transaction::FillTxEnv, Header, TransactionSigned};
 use revm_primitives::{
     AnalysisKind, BlobExcessGasAndPrice, BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, Env, SpecId, TxEnv,
 };
@@ -82,8 +81,8 @@ impl ConfigureEvmEnv for EthEvmConfig {
             transact_to: TxKind::Call(contract),
             // Explicitly set nonce to None so revm does not do any nonce checks
             nonce: None,
-            // WVM: 500_000_000 gas limit
-            gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+            // WVM: the system call may use the whole gas limit of the chain
+            gas_limit: self.chain_spec.max_gas_limit,
             value: U256::ZERO,
             data,
             // Setting the gas price to zero enforces that no value is transferred as part of the
@@ -155,7 +154,7 @@ impl ConfigureEvmEnv for EthEvmConfig {
             self.chain_spec.base_fee_params_at_timestamp(attributes.timestamp),
         );
 
-        let mut gas_limit = U256::from(parent.gas_limit);
+        let mut gas_limit = U256::from(next_block_gas_limit(&*self.chain_spec, parent));
 
         // If we are on the London fork boundary, we need to multiply the parent's gas limit by the
         // elasticity multiplier to get the new gas limit.
@@ -172,6 +171,9 @@ impl ConfigureEvmEnv for EthEvmConfig {
             basefee = Some(INITIAL_BASE_FEE)
         }
 
+        let base_fee_floor = self.chain_spec.wvm.base_fee_floor_at_block(parent.number + 1);
+        let basefee = basefee.map(|basefee| basefee.max(base_fee_floor));
+
         let block_env = BlockEnv {
             number: U256::from(parent.number + 1),
             coinbase: attributes.suggested_fee_recipient,
@@ -31,8 +31,8 @@ use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, TransactionPool, TransactionValidationTaskExecutor,
+    WvmTransactionPool,
 };
 use reth_trie_db::MerklePatriciaTrie;
 
@@ -169,25 +169,30 @@ where
     Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
     Node: FullNodeTypes<Types = Types>,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = WvmTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
+            .with_head_number(ctx.head().number)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
-            .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
-            .build_with_tasks(
-                ctx.provider().clone(),
-                ctx.task_executor().clone(),
-                blob_store.clone(),
-            );
+            .with_additional_tasks(ctx.config().txpool.additional_validation_tasks);
+        // WVM: an explicit limit overrides the one of the chain spec
+        if let Some(max_tx_input_bytes) = ctx.config().txpool.max_tx_input_bytes {
+            validator = validator.with_max_tx_input_bytes(max_tx_input_bytes);
+        }
+        let validator = validator.build_with_tasks(
+            ctx.provider().clone(),
+            ctx.task_executor().clone(),
+            blob_store.clone(),
+        );
 
         let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+            reth_transaction_pool::Pool::wvm_pool(validator, blob_store, pool_config);
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
 
@@ -10,7 +10,9 @@
 #![allow(clippy::useless_let_if_seq)]
 
 use alloy_consensus::EMPTY_OMMER_ROOT_HASH;
-use alloy_eips::{eip4844::MAX_DATA_GAS_PER_BLOCK, eip7685::Requests, merge::BEACON_NONCE};
+use alloy_eips::{
+    eip2718::Encodable2718, eip4844::MAX_DATA_GAS_PER_BLOCK, eip7685::Requests, merge::BEACON_NONCE,
+};
 use alloy_primitives::U256;
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder,
@@ -25,6 +27,7 @@ use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_primitives::{PayloadBuilderAttributes, PayloadBuilderError};
 use reth_primitives::{
+    constants::DEFAULT_BLOCK_SIZE_BUDGET,
     proofs::{self},
     revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg},
     Block, BlockBody, EthereumHardforks, Header, Receipt,
@@ -166,6 +169,7 @@ where
 
     debug!(target: "payload_builder", id=%attributes.id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
     let mut cumulative_gas_used = 0;
+    let mut cumulative_size = 0;
     let mut sum_blob_gas_used = 0;
     let block_gas_limit: u64 = initialized_block_env.gas_limit.to::<u64>();
     let base_fee = initialized_block_env.basefee.to::<u64>();
@@ -180,6 +184,8 @@ where
     let mut total_fees = U256::ZERO;
 
     let block_number = initialized_block_env.number.to::<u64>();
+    let max_block_size =
+        chain_spec.max_block_size_at_block(block_number).unwrap_or(DEFAULT_BLOCK_SIZE_BUDGET);
 
     let mut system_caller = SystemCaller::new(evm_config.clone(), chain_spec.clone());
 
@@ -212,6 +218,23 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    // WVM: record the Arweave id carried by the extra data in the archive registry
+    system_caller
+        .pre_block_archive_registry_contract_call(
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+            &extra_data,
+        )
+        .map_err(|err| {
+            warn!(target: "payload_builder",
+                parent_hash=%parent_header.hash(),
+                %err,
+                "failed to apply archive registry contract call for payload"
+            );
+            PayloadBuilderError::Internal(err.into())
+        })?;
+
     let mut receipts = Vec::new();
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
@@ -231,6 +254,15 @@ where
         // convert tx to a signed transaction
         let tx = pool_tx.to_recovered_transaction();
 
+        // WVM: blocks are also packed by the encoded size of their transactions, bounded by the
+        // maximum block size scheduled in the chain spec or by the default budget
+        let tx_size = tx.encode_2718_len();
+        if cumulative_size + tx_size > max_block_size {
+            trace!(target: "payload_builder", tx=?tx.hash, ?cumulative_size, ?tx_size, "skipping transaction because it would exceed the max block size");
+            best_txs.mark_invalid(&pool_tx);
+            continue
+        }
+
         // There's only limited amount of blob space available per block, so we need to check if
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
@@ -299,6 +331,7 @@ where
 
         // add gas used by the transaction to cumulative gas used, before creating the receipt
         cumulative_gas_used += gas_used;
+        cumulative_size += tx_size;
 
         // Push transaction changeset and calculate header bloom filter for receipt.
         #[allow(clippy::needless_update)] // side-effect of optimism fields
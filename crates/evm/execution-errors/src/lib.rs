@@ -53,6 +53,16 @@ pub enum BlockValidationError {
         /// The available block gas
         block_available_gas: u64,
     },
+    /// WVM: Error when a transaction's gas limit is below the chain's minimum transaction gas
+    #[display(
+        "transaction gas limit {transaction_gas_limit} is below the minimum transaction gas {min_transaction_gas}"
+    )]
+    TransactionGasLimitBelowMinimum {
+        /// The transaction's gas limit
+        transaction_gas_limit: u64,
+        /// The minimum transaction gas at the block
+        min_transaction_gas: u64,
+    },
     /// Error for pre-merge block
     #[display("block {hash} is pre merge")]
     BlockPreMerge {
@@ -96,6 +106,15 @@ pub enum BlockValidationError {
         /// The error message.
         message: String,
     },
+    /// WVM: EVM error during the archive registry contract call.
+    #[display("failed to apply archive registry contract call: {message}")]
+    ArchiveRegistryContractCall {
+        /// The error message.
+        message: String,
+    },
+    /// WVM: the archive registry is scheduled but its contract is not deployed.
+    #[display("archive registry contract is not deployed")]
+    ArchiveRegistryContractMissing,
     /// EVM error during withdrawal requests contract call [EIP-7002]
     ///
     /// [EIP-7002]: https://eips.ethereum.org/EIPS/eip-7002
@@ -0,0 +1,100 @@
+//! WVM: archive registry system call implementation.
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+use crate::ConfigureEvm;
+use alloy_eips::eip4788::SYSTEM_ADDRESS;
+use alloy_primitives::{address, bytes, Address, Bytes, B256, U256};
+use reth_chainspec::EthChainSpec;
+use reth_execution_errors::{BlockExecutionError, BlockValidationError};
+use reth_primitives::Header;
+use revm::{interpreter::Host, Database, Evm};
+use revm_primitives::ResultAndState;
+
+/// Address of the archive registry contract.
+///
+/// The storage slot of an archived block number holds the Arweave id of that block.
+pub const ARCHIVE_REGISTRY_ADDRESS: Address = address!("000000000000000000000000000000000000a4c1");
+
+/// Runtime code of the archive registry contract, it must be allocated at
+/// [`ARCHIVE_REGISTRY_ADDRESS`] in the genesis of chains scheduling the registry.
+///
+/// A call from the system address stores the second calldata word under the first one, any other
+/// call returns the value stored under the first calldata word.
+pub const ARCHIVE_REGISTRY_CODE: Bytes = bytes!(
+    "3373fffffffffffffffffffffffffffffffffffffffe146026576000355460005260206000f35b6020356000355500"
+);
+
+/// Applies the pre-block call to the archive registry contract, recording the Arweave id carried
+/// by the `extra_data` of the block as the id of the block `archive_registry_delay` blocks before.
+///
+/// Note: this does not commit the state changes to the database, it only transact the call.
+///
+/// Returns `None` if the registry is not scheduled, no block is old enough to be recorded or the
+/// `extra_data` is not an Arweave id, otherwise returns the result of the call. Fails if the
+/// registry is scheduled but its contract is not deployed.
+#[inline]
+pub(crate) fn transact_archive_registry_contract_call<EvmConfig, EXT, DB, Spec>(
+    evm_config: &EvmConfig,
+    chain_spec: &Spec,
+    block_number: u64,
+    extra_data: &Bytes,
+    evm: &mut Evm<'_, EXT, DB>,
+) -> Result<Option<ResultAndState>, BlockExecutionError>
+where
+    DB: Database,
+    DB::Error: core::fmt::Display,
+    EvmConfig: ConfigureEvm<Header = Header>,
+    Spec: EthChainSpec,
+{
+    let Some(delay) = chain_spec.archive_registry_delay_at_block(block_number) else {
+        return Ok(None)
+    };
+    let Some(archived_block) = block_number.checked_sub(delay) else { return Ok(None) };
+
+    // a chain scheduling the registry without deploying it would never record any id
+    let deployed = evm
+        .db_mut()
+        .basic(ARCHIVE_REGISTRY_ADDRESS)
+        .map_err(|e| BlockValidationError::ArchiveRegistryContractCall { message: e.to_string() })?
+        .is_some_and(|account| !account.is_empty_code_hash());
+    if !deployed {
+        return Err(BlockValidationError::ArchiveRegistryContractMissing.into())
+    }
+
+    if extra_data.len() != B256::len_bytes() {
+        return Ok(None)
+    }
+
+    // get previous env
+    let previous_env = Box::new(evm.context.env().clone());
+
+    let mut data = Vec::with_capacity(2 * B256::len_bytes());
+    data.extend_from_slice(B256::from(U256::from(archived_block)).as_slice());
+    data.extend_from_slice(extra_data);
+
+    // modify env for pre block call
+    evm_config.fill_tx_env_system_contract_call(
+        &mut evm.context.evm.env,
+        SYSTEM_ADDRESS,
+        ARCHIVE_REGISTRY_ADDRESS,
+        data.into(),
+    );
+
+    let mut res = match evm.transact() {
+        Ok(res) => res,
+        Err(e) => {
+            evm.context.evm.env = previous_env;
+            return Err(
+                BlockValidationError::ArchiveRegistryContractCall { message: e.to_string() }.into()
+            )
+        }
+    };
+
+    res.state.remove(&SYSTEM_ADDRESS);
+    res.state.remove(&evm.block().coinbase);
+
+    // re-set the previous env
+    evm.context.evm.env = previous_env;
+
+    Ok(Some(res))
+}
@@ -5,12 +5,15 @@ use alloc::{boxed::Box, sync::Arc, vec};
 use alloy_eips::eip7685::Requests;
 use alloy_primitives::Bytes;
 use core::fmt::Display;
-use reth_chainspec::EthereumHardforks;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_execution_errors::BlockExecutionError;
 use reth_primitives::{Block, Header};
 use revm::{Database, DatabaseCommit, Evm};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState, B256};
 
+mod archive_registry;
+pub use archive_registry::{ARCHIVE_REGISTRY_ADDRESS, ARCHIVE_REGISTRY_CODE};
+
 mod eip2935;
 mod eip4788;
 mod eip7002;
@@ -89,7 +92,7 @@ where
 impl<EvmConfig, Chainspec> SystemCaller<EvmConfig, Chainspec>
 where
     EvmConfig: ConfigureEvm<Header = Header>,
-    Chainspec: EthereumHardforks,
+    Chainspec: EthChainSpec + EthereumHardforks,
 {
     /// Apply pre execution changes.
     pub fn apply_pre_execution_changes<DB, Ext>(
@@ -113,10 +116,69 @@ where
             block.parent_beacon_block_root,
             evm,
         )?;
+        self.apply_archive_registry_contract_call(block.number, &block.extra_data, evm)?;
+
+        Ok(())
+    }
+
+    /// WVM: Applies the pre-block call to the archive registry contract.
+    pub fn pre_block_archive_registry_contract_call<DB>(
+        &mut self,
+        db: &mut DB,
+        initialized_cfg: &CfgEnvWithHandlerCfg,
+        initialized_block_env: &BlockEnv,
+        extra_data: &Bytes,
+    ) -> Result<(), BlockExecutionError>
+    where
+        DB: Database + DatabaseCommit,
+        DB::Error: Display,
+    {
+        let mut evm = initialize_evm(db, initialized_cfg, initialized_block_env);
+
+        self.apply_archive_registry_contract_call(
+            initialized_block_env.number.to(),
+            extra_data,
+            &mut evm,
+        )?;
+
+        Ok(())
+    }
+
+    /// WVM: Applies the pre-block call to the archive registry contract.
+    pub fn apply_archive_registry_contract_call<DB, Ext>(
+        &mut self,
+        block_number: u64,
+        extra_data: &Bytes,
+        evm: &mut Evm<'_, Ext, DB>,
+    ) -> Result<(), BlockExecutionError>
+    where
+        DB: Database + DatabaseCommit,
+        DB::Error: Display,
+    {
+        let result_and_state = archive_registry::transact_archive_registry_contract_call(
+            &self.evm_config,
+            &self.chain_spec,
+            block_number,
+            extra_data,
+            evm,
+        )?;
+
+        if let Some(res) = result_and_state {
+            if let Some(ref mut hook) = self.hook {
+                hook.on_state(&res);
+            }
+            evm.context.evm.db.commit(res.state);
+        }
 
         Ok(())
     }
+}
 
+impl<EvmConfig, Chainspec> SystemCaller<EvmConfig, Chainspec>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    Chainspec: EthereumHardforks,
+{
     /// Apply post execution changes.
     pub fn apply_post_execution_changes<DB, Ext>(
         &mut self,
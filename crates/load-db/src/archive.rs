@@ -0,0 +1,38 @@
+//! Arweave view of the archives LoadDb points to.
+//!
+//! LoadDb only knows the Arweave id of an archived block, bundles, tags, confirmations and the
+//! archived data itself are served by an Arweave gateway.
+
+/// Arweave transaction or bundled data item, as indexed by a gateway.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveItem {
+    pub arweave_id: String,
+    /// Bundle holding the item, `None` for a layer one transaction.
+    pub bundle_id: Option<String>,
+    pub tags: Vec<(String, String)>,
+    /// Arweave block the item (or its bundle) was mined in, `None` while it's pending.
+    pub block_height: Option<u64>,
+}
+
+/// Block decoded from an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedArchive {
+    pub block_number: u64,
+    /// Hash computed from the decoded header, hex encoded.
+    pub block_hash: String,
+}
+
+/// Arweave gateway serving the archives of blocks, see `wvm_getArweaveStorageProof`.
+pub trait ArchiveGateway: std::fmt::Debug + Send + Sync + 'static {
+    /// Item `arweave_id`, `None` while the gateway doesn't index it yet.
+    fn item(&self, arweave_id: &str) -> eyre::Result<Option<ArchiveItem>>;
+
+    /// Id of the attestation uploaded along with the archive `arweave_id`, if any.
+    fn attestation_id(&self, arweave_id: &str) -> eyre::Result<Option<String>>;
+
+    /// Current height of the Arweave network.
+    fn network_height(&self) -> eyre::Result<u64>;
+
+    /// Downloads and decodes the archive `item`, within the size limits of the gateway.
+    fn decode(&self, item: &ArchiveItem) -> eyre::Result<DecodedArchive>;
+}
@@ -0,0 +1,164 @@
+use crate::{ArchivedBlock, LoadDbConnection};
+use eyre::eyre;
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+use tracing::{error, warn};
+
+pub const DEFAULT_BATCH_SIZE: usize = 32;
+pub const DEFAULT_BATCH_DELAY: Duration = Duration::from_millis(500);
+
+type BatchEntry = (ArchivedBlock, oneshot::Sender<Result<(), String>>);
+
+/// Groups the writes of concurrently archived blocks, so they are stored with a single
+/// [`LoadDbConnection::save_archived_blocks`] call. A batch is flushed when it reaches
+/// `max_batch` blocks or `max_delay` after its first block was queued. If the batch fails, its
+/// blocks are stored one by one so a single bad block only fails its own save.
+#[derive(Clone)]
+pub struct LoadDbBatchWriter {
+    sender: mpsc::Sender<BatchEntry>,
+}
+
+impl LoadDbBatchWriter {
+    pub fn spawn(
+        db: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+        max_batch: usize,
+        max_delay: Duration,
+    ) -> Self {
+        let max_batch = max_batch.max(1);
+        let (sender, receiver) = mpsc::channel(max_batch * 4);
+
+        tokio::spawn(run_batches(db, receiver, max_batch, max_delay));
+
+        Self { sender }
+    }
+
+    /// Queues the block and waits until the batch containing it has been stored.
+    pub async fn save(&self, block: ArchivedBlock) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.sender
+            .send((block, tx))
+            .await
+            .map_err(|_| eyre!("LoadDb batch writer is not running"))?;
+
+        rx.await.map_err(|_| eyre!("LoadDb batch writer dropped the block"))?.map_err(|e| eyre!(e))
+    }
+}
+
+async fn run_batches(
+    db: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+    mut receiver: mpsc::Receiver<BatchEntry>,
+    max_batch: usize,
+    max_delay: Duration,
+) {
+    while let Some(first) = receiver.recv().await {
+        let deadline = Instant::now() + max_delay;
+        let mut batch = vec![first];
+
+        while batch.len() < max_batch {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(entry)) => batch.push(entry),
+                _ => break,
+            }
+        }
+
+        let (blocks, waiters): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let result = db.save_archived_blocks(&blocks).await;
+
+        if let Err(e) = &result {
+            warn!(target: "wvm::loaddb", error = %e, blocks = blocks.len(), "Failed to store batch, storing its blocks one by one");
+        }
+
+        for (block, waiter) in blocks.iter().zip(waiters) {
+            let block_result = match &result {
+                Ok(()) => Ok(()),
+                Err(e) if blocks.len() == 1 => Err(e.to_string()),
+                Err(_) => save_block(db.as_ref(), block).await,
+            };
+            let _ = waiter.send(block_result);
+        }
+    }
+}
+
+async fn save_block(
+    db: &(dyn LoadDbConnection + Send + Sync + 'static),
+    block: &ArchivedBlock,
+) -> Result<(), String> {
+    db.save_archived_blocks(std::slice::from_ref(block)).await.map_err(|e| {
+        error!(target: "wvm::loaddb", error = %e, block_number = block.block_number, "Failed to store block");
+        e.to_string()
+    })
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use crate::{
+        batch::LoadDbBatchWriter, drivers::sqlite::SqliteDriver, ArchivedBlock, LoadDbConnection,
+    };
+    use std::{sync::Arc, time::Duration};
+
+    #[tokio::test]
+    pub async fn test_batch_writer_stores_all_blocks() {
+        let db = Arc::new(SqliteDriver::open_in_memory().unwrap());
+        db.migrate().await.unwrap();
+        let writer = LoadDbBatchWriter::spawn(db.clone(), 4, Duration::from_millis(50));
+
+        let saves = (0..10u64).map(|block_number| {
+            let writer = writer.clone();
+            tokio::spawn(async move {
+                writer
+                    .save(ArchivedBlock {
+                        block_number,
+                        arweave_id: "a".repeat(43),
+                        block_hash: format!("0x{:064x}", block_number),
+                        tx_hashes: vec![],
+                    })
+                    .await
+            })
+        });
+
+        for save in saves.collect::<Vec<_>>() {
+            save.await.unwrap().unwrap();
+        }
+
+        for block_number in 0..10u64 {
+            assert!(db.query_raw_state(block_number.to_string()).await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_batch_writer_isolates_failed_blocks() {
+        let db = Arc::new(SqliteDriver::open_in_memory().unwrap());
+        db.migrate().await.unwrap();
+        let writer = LoadDbBatchWriter::spawn(db.clone(), 4, Duration::from_millis(50));
+
+        let saves = (0..4u64).map(|block_number| {
+            let writer = writer.clone();
+            tokio::spawn(async move {
+                // block 2 carries an invalid transaction hash, failing the whole batch
+                let tx_hashes =
+                    if block_number == 2 { vec!["not-a-hash".to_string()] } else { vec![] };
+                writer
+                    .save(ArchivedBlock {
+                        block_number,
+                        arweave_id: "a".repeat(43),
+                        block_hash: format!("0x{:064x}", block_number),
+                        tx_hashes,
+                    })
+                    .await
+            })
+        });
+
+        for (block_number, save) in saves.collect::<Vec<_>>().into_iter().enumerate() {
+            let result = save.await.unwrap();
+            assert_eq!(result.is_err(), block_number == 2, "block {block_number}");
+        }
+
+        for block_number in 0..4u64 {
+            let stored = db.query_raw_state(block_number.to_string()).await.unwrap().is_some();
+            assert_eq!(stored, block_number != 2, "block {block_number}");
+        }
+    }
+}
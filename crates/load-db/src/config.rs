@@ -0,0 +1,82 @@
+use crate::{
+    drivers::{
+        planetscale::PlanetScaleDriver,
+        postgres::{PostgresDriver, DEFAULT_POSTGRES_POOL_SIZE},
+        sqlite::SqliteDriver,
+    },
+    LoadDbConnection,
+};
+use eyre::eyre;
+use std::{path::PathBuf, sync::Arc};
+
+pub const DEFAULT_SQLITE_PATH: &str = "wvm-loaddb.sqlite";
+
+/// LoadDb backend selected by the node operator through `LOADDB_DRIVER`.
+#[derive(Clone, PartialEq, Eq)]
+pub enum LoadDbConfig {
+    /// `LOADDB_DRIVER=planetscale` (default), configured by `PS_HOST`, `PS_USERNAME` and
+    /// `PS_PASSWORD`.
+    PlanetScale { host: String, username: String, password: String },
+    /// `LOADDB_DRIVER=sqlite`, stored at `LOADDB_SQLITE_PATH`.
+    Sqlite { path: PathBuf },
+    /// `LOADDB_DRIVER=postgres`, connecting to `LOADDB_POSTGRES_URL` with at most
+    /// `LOADDB_POSTGRES_POOL_SIZE` connections. TLS follows the `sslmode` of the url, the server
+    /// certificate is verified against the system roots and `LOADDB_POSTGRES_CA_CERT` if set.
+    Postgres { url: String, pool_size: usize, ca_cert: Option<PathBuf> },
+}
+
+impl LoadDbConfig {
+    pub fn from_env() -> eyre::Result<Self> {
+        let driver = std::env::var("LOADDB_DRIVER").unwrap_or("planetscale".to_string());
+
+        match driver.to_lowercase().as_str() {
+            "planetscale" => Ok(LoadDbConfig::PlanetScale {
+                host: std::env::var("PS_HOST").unwrap_or_default(),
+                username: std::env::var("PS_USERNAME").unwrap_or_default(),
+                password: std::env::var("PS_PASSWORD").unwrap_or_default(),
+            }),
+            "sqlite" => Ok(LoadDbConfig::Sqlite {
+                path: std::env::var("LOADDB_SQLITE_PATH")
+                    .unwrap_or(DEFAULT_SQLITE_PATH.to_string())
+                    .into(),
+            }),
+            "postgres" => Ok(LoadDbConfig::Postgres {
+                url: std::env::var("LOADDB_POSTGRES_URL").map_err(|_| {
+                    eyre!("LOADDB_POSTGRES_URL must be set for the postgres driver")
+                })?,
+                pool_size: std::env::var("LOADDB_POSTGRES_POOL_SIZE")
+                    .ok()
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(DEFAULT_POSTGRES_POOL_SIZE),
+                ca_cert: std::env::var("LOADDB_POSTGRES_CA_CERT").ok().map(PathBuf::from),
+            }),
+            other => Err(eyre!("Unknown LoadDb driver {}", other)),
+        }
+    }
+
+    pub const fn driver_name(&self) -> &'static str {
+        match self {
+            LoadDbConfig::PlanetScale { .. } => "planetscale",
+            LoadDbConfig::Sqlite { .. } => "sqlite",
+            LoadDbConfig::Postgres { .. } => "postgres",
+        }
+    }
+
+    pub fn connect(self) -> eyre::Result<Arc<dyn LoadDbConnection + Send + Sync + 'static>> {
+        match self {
+            LoadDbConfig::PlanetScale { host, username, password } => {
+                if host.is_empty() || username.is_empty() || password.is_empty() {
+                    return Err(eyre!(
+                        "PlanetScale LoadDb requires PS_HOST, PS_USERNAME and PS_PASSWORD, set LOADDB_DRIVER=sqlite to run without it"
+                    ));
+                }
+
+                Ok(Arc::new(PlanetScaleDriver::new(host, username, password)))
+            }
+            LoadDbConfig::Sqlite { path } => Ok(Arc::new(SqliteDriver::open(path)?)),
+            LoadDbConfig::Postgres { url, pool_size, ca_cert } => {
+                Ok(Arc::new(PostgresDriver::new(&url, pool_size, ca_cert.as_deref())?))
+            }
+        }
+    }
+}
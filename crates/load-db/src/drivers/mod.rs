@@ -1 +1,3 @@
 pub mod planetscale;
+pub mod postgres;
+pub mod sqlite;
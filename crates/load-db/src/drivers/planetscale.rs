@@ -1,4 +1,9 @@
-use crate::{LoadDbConnection, RawState};
+use crate::{
+    migrations::{pending_migrations, Migration},
+    query::{TagQuery, TagQueryPage, TaggedTransaction},
+    sql::{ensure_hex_hashes, is_address, is_hex_hash, is_valid_tag, BlockLookup, SqlQuery},
+    ArchivedBlock, LoadDbConnection, RawState, TxHashRow,
+};
 use async_trait::async_trait;
 use eyre::eyre;
 use planetscale_driver::{query, Database, PSConnection};
@@ -8,6 +13,130 @@ use std::{
     fmt::Debug,
     time::{SystemTime, UNIX_EPOCH},
 };
+use tracing::debug;
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "state, tags and confirmed_tags tables",
+        statements: &[
+            "CREATE TABLE IF NOT EXISTS state (
+                block_number BIGINT UNSIGNED NOT NULL PRIMARY KEY,
+                arweave_id VARCHAR(64) NOT NULL,
+                timestamp BIGINT NOT NULL DEFAULT (UNIX_TIMESTAMP() * 1000),
+                block_hash VARCHAR(66) NOT NULL,
+                KEY state_arweave_id_idx (arweave_id),
+                KEY state_block_hash_idx (block_hash)
+            )",
+            "CREATE TABLE IF NOT EXISTS tags (
+                hash VARCHAR(66) NOT NULL PRIMARY KEY,
+                tags JSON NOT NULL,
+                created_at BIGINT UNSIGNED NOT NULL
+            )",
+            "CREATE TABLE IF NOT EXISTS confirmed_tags (
+                hash VARCHAR(66) NOT NULL,
+                tags JSON NOT NULL,
+                block_id BIGINT UNSIGNED NOT NULL,
+                created_at BIGINT UNSIGNED NOT NULL,
+                KEY confirmed_tags_hash_idx (hash)
+            )",
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "index confirmed tags by block",
+        statements: &["CREATE INDEX confirmed_tags_block_id_idx ON confirmed_tags (block_id)"],
+    },
+    Migration {
+        version: 3,
+        description: "transaction senders and tag query indexes",
+        statements: &[
+            "ALTER TABLE tags ADD COLUMN sender VARCHAR(42) NULL",
+            "ALTER TABLE confirmed_tags ADD COLUMN sender VARCHAR(42) NULL",
+            "CREATE INDEX confirmed_tags_block_hash_idx ON confirmed_tags (block_id, hash)",
+            "CREATE INDEX confirmed_tags_sender_idx ON confirmed_tags (sender, block_id, hash)",
+        ],
+    },
+    Migration {
+        version: 4,
+        description: "pending tags status",
+        // Rerunning either statement fails with errno 1060 or 1061, see `is_already_applied`.
+        statements: &[
+            "ALTER TABLE tags ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'pending'",
+            "CREATE INDEX tags_status_created_at_idx ON tags (status, created_at)",
+        ],
+    },
+    Migration {
+        version: 5,
+        description: "drop the legacy sealed_block_with_senders column",
+        statements: &["ALTER TABLE state DROP COLUMN sealed_block_with_senders"],
+    },
+];
+
+/// MySQL errors of DDL statements whose change is already in place: duplicate column (1060),
+/// duplicate index (1061) and missing column or index to drop (1091). MySQL has no `IF NOT
+/// EXISTS` for these, a migration interrupted before being recorded is resumed by skipping them.
+const ALREADY_APPLIED_ERRNOS: &[&str] = &["errno 1060", "errno 1061", "errno 1091"];
+
+fn is_already_applied(error: &str) -> bool {
+    ALREADY_APPLIED_ERRNOS.iter().any(|errno| error.contains(errno))
+}
+
+/// MySQL error of an insert whose primary key already exists.
+fn is_duplicate_key(error: &str) -> bool {
+    error.contains("errno 1062")
+}
+
+// Keyset pagination over `(block_id, hash)`. The subquery counts how many of the query tags the
+// transaction carries, the last but one value is the number of tags required to match.
+const SELECT_TXS_BY_TAGS: &str = "SELECT
+  t.hash hash,
+  t.block_id block_number,
+  COALESCE(t.sender, '') sender,
+  CAST(t.tags AS CHAR) tags
+FROM confirmed_tags t
+WHERE (? IS NULL OR t.block_id >= ?)
+  AND (? IS NULL OR t.block_id <= ?)
+  AND (? IS NULL OR t.sender = ?)
+  AND (? IS NULL OR t.block_id > ? OR (t.block_id = ? AND t.hash > ?))
+  AND (
+    SELECT COUNT(DISTINCT q.idx)
+    FROM JSON_TABLE(
+      ?, '$[*]'
+      COLUMNS (
+        idx FOR ORDINALITY,
+        tag_key VARCHAR(255) PATH '$[0]',
+        tag_value VARCHAR(255) PATH '$[1]'
+      )
+    ) AS q
+    JOIN JSON_TABLE(
+      t.tags, '$[*]'
+      COLUMNS (
+        tag_key VARCHAR(255) PATH '$[0]',
+        tag_value VARCHAR(255) PATH '$[1]'
+      )
+    ) AS tags_flat ON tags_flat.tag_key = q.tag_key AND tags_flat.tag_value = q.tag_value
+  ) >= ?
+ORDER BY t.block_id, t.hash
+LIMIT ?;";
+
+#[derive(Serialize, Deserialize, Database)]
+struct CountRow {
+    count: u64,
+}
+
+#[derive(Serialize, Deserialize, Database)]
+struct VersionRow {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize, Database)]
+struct TaggedTxRow {
+    hash: String,
+    block_number: u64,
+    sender: String,
+    tags: String,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct PlanetScaleDriver {
@@ -24,62 +153,72 @@ impl PlanetScaleDriver {
     pub fn get_conn(&self) -> PSConnection {
         PSConnection::new(&self.host, &self.username, &self.password)
     }
+
+    async fn execute(&self, sql: SqlQuery) -> eyre::Result<()> {
+        let conn = self.get_conn();
+        conn.execute(&sql.render()?).await.map_err(|e| eyre!(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn fetch_one<T: Database>(&self, sql: SqlQuery) -> eyre::Result<T> {
+        let conn = self.get_conn();
+        query(sql.render()?.as_str()).fetch_one::<T>(&conn).await.map_err(|e| eyre!(e.to_string()))
+    }
+
+    async fn fetch_all<T: Database>(&self, sql: SqlQuery) -> eyre::Result<Vec<T>> {
+        let conn = self.get_conn();
+        query(sql.render()?.as_str()).fetch_all::<T>(&conn).await.map_err(|e| eyre!(e.to_string()))
+    }
 }
 
 #[async_trait]
 impl LoadDbConnection for PlanetScaleDriver {
-    async fn query_raw_state(&self, block_id: String) -> Option<RawState> {
-        let table_name = "state";
-        let select_clause =
-            format!("SELECT block_number, arweave_id, timestamp, block_hash FROM {}", table_name);
-
-        let get_query = if let Ok(num) = block_id.parse::<i128>() {
-            format!("{} WHERE block_number = {}", select_clause, num)
-        } else {
-            format!(
-                "{} WHERE arweave_id = '{}' OR block_hash = '{}'",
-                select_clause, block_id, block_id
+    async fn query_raw_state(&self, block_id: String) -> eyre::Result<Option<RawState>> {
+        let Some(lookup) = BlockLookup::parse(&block_id) else {
+            return Ok(None);
+        };
+        let sql = match lookup {
+            BlockLookup::Number(number) => SqlQuery::new(
+                "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE block_number = ?",
+            )
+            .bind(number),
+            BlockLookup::ArweaveId(id) => SqlQuery::new(
+                "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE arweave_id = ?",
             )
+            .bind(id),
+            BlockLookup::BlockHash(hash) => SqlQuery::new(
+                "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE block_hash = ?",
+            )
+            .bind(hash),
         };
 
-        let conn = self.get_conn();
-        let fetch = query(get_query.as_str()).fetch_one::<RawState>(&conn).await;
-
-        fetch.ok()
-    }
-
-    async fn query_state(&self, block_id: String) -> Option<String> {
-        self.query_raw_state(block_id).await.map(|e| e.sealed_block_with_senders)
+        // `fetch_one` doesn't tell a missing row from a failed query.
+        Ok(self.fetch_all::<RawState>(sql).await?.into_iter().next())
     }
 
     async fn save_hashes(&self, hashes: &[String], block_number: u64) -> eyre::Result<()> {
+        ensure_hex_hashes(hashes)?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?;
 
         let timestamp = timestamp.as_millis();
 
-        let in_clause = hashes
-            .iter()
-            .map(|hash| format!("'{}'", hash.replace('\'', "''")))
-            .collect::<Vec<String>>()
-            .join(", ");
-
-        let insert_query = format!(
-            "INSERT INTO {} (hash, tags, block_id, created_at)
-     SELECT t.hash, t.tags, {}, {}
-     FROM {} t
-     WHERE t.hash IN ({}) AND t.created_at <= {}",
-            "confirmed_tags", // e.g. "tags"
-            block_number,     // e.g. 123
-            timestamp,        // e.g. 1712800000
-            "tags",           // e.g. "temp_tags"
-            in_clause,        // e.g. "'abc', 'def', 'ghi'"
-            timestamp         // e.g. 1712799999
-        );
+        let sql = SqlQuery::new(
+            "INSERT INTO confirmed_tags (hash, tags, sender, block_id, created_at)
+     SELECT t.hash, t.tags, t.sender, ?, ?
+     FROM tags t
+     WHERE t.hash IN (?) AND t.created_at <= ?",
+        )
+        .bind(block_number)
+        .bind(timestamp)
+        .bind_list(hashes)
+        .bind(timestamp);
 
-        let conn = self.get_conn();
-        conn.execute(&insert_query).await.map_err(|e| eyre!(e.to_string()))?;
-
-        Ok(())
+        self.execute(sql).await
     }
 
     async fn save_block(
@@ -89,23 +228,43 @@ impl LoadDbConnection for PlanetScaleDriver {
         arweave_id: String,
         block_hash: String,
     ) -> eyre::Result<()> {
-        let insert_query = format!(
+        let sql = SqlQuery::new(
             "INSERT INTO state (
                 block_number,
                 arweave_id,
                 block_hash
-            ) VALUES (
-                {},
-                '{}',
-                '{}'
-            );",
-            block_number,
-            arweave_id.replace('\'', "''"),
-            block_hash.replace('\'', "''")
-        );
+            ) VALUES (?, ?, ?);",
+        )
+        .bind(block_number)
+        .bind(arweave_id)
+        .bind(block_hash);
 
-        let conn = self.get_conn();
-        conn.execute(&insert_query).await.map_err(|e| eyre!(e.to_string()))?;
+        self.execute(sql).await
+    }
+
+    async fn save_archived_blocks(&self, blocks: &[ArchivedBlock]) -> eyre::Result<()> {
+        ensure_hex_hashes(blocks.iter().flat_map(|block| &block.tx_hashes))?;
+
+        // The HTTP API has no multi statement transactions, tags are confirmed first so a stored
+        // block always has its tags.
+        for block in blocks {
+            self.save_hashes(&block.tx_hashes, block.block_number).await?;
+        }
+
+        for block in blocks {
+            let sql = SqlQuery::new(
+                "INSERT INTO state (
+                block_number,
+                arweave_id,
+                block_hash
+            ) VALUES (?, ?, ?);",
+            )
+            .bind(block.block_number)
+            .bind(&block.arweave_id)
+            .bind(&block.block_hash);
+
+            self.execute(sql).await?;
+        }
 
         Ok(())
     }
@@ -113,40 +272,143 @@ impl LoadDbConnection for PlanetScaleDriver {
     async fn save_tx_tag(
         &self,
         tx_hash: String,
+        sender: Option<String>,
         tags: Vec<(String, String)>,
         created_at: u128,
-    ) -> eyre::Result<()> {
-        let sealed_json = serde_json::to_string(&tags)?;
-        let escaped_json_tags = sealed_json.replace('\'', "''");
-
-        let insert_query = format!(
-            "INSERT INTO tags (
-                hash,
-                tags,
-                created_at
-            ) VALUES (
-                '{}',
-                '{}',
-                {}
-            );",
-            tx_hash, escaped_json_tags, created_at
-        );
+    ) -> eyre::Result<bool> {
+        if !is_hex_hash(&tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
 
-        let conn = self.get_conn();
-        conn.execute(&insert_query).await.map_err(|e| eyre!(e.to_string()))?;
+        if sender.as_deref().is_some_and(|sender| !is_address(sender)) {
+            return Err(eyre!("Invalid sender for transaction {}", tx_hash));
+        }
 
-        Ok(())
+        if !tags.iter().all(is_valid_tag) {
+            return Err(eyre!("Invalid tags for transaction {}", tx_hash));
+        }
+
+        let tags = serde_json::to_string(&tags)?;
+        let sender = sender.map(|s| s.to_lowercase());
+
+        // The HTTP API doesn't report affected rows, a duplicate key tells an existing row apart.
+        let insert =
+            SqlQuery::new("INSERT INTO tags (hash, tags, sender, created_at) VALUES (?, ?, ?, ?)")
+                .bind(&tx_hash)
+                .bind(&tags)
+                .bind(sender.clone())
+                .bind(created_at);
+
+        match self.execute(insert).await {
+            Ok(()) => Ok(true),
+            Err(e) if is_duplicate_key(&e.to_string()) => {
+                self.execute(
+                    SqlQuery::new(
+                        "UPDATE tags SET tags = ?, sender = ?, created_at = ?
+                        WHERE hash = ? AND status = 'pending'",
+                    )
+                    .bind(tags)
+                    .bind(sender)
+                    .bind(created_at)
+                    .bind(tx_hash),
+                )
+                .await?;
+
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn mark_tx_tag_mined(&self, tx_hash: &str) -> eyre::Result<()> {
+        if !is_hex_hash(tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
+
+        self.execute(SqlQuery::new("UPDATE tags SET status = 'mined' WHERE hash = ?").bind(tx_hash))
+            .await
+    }
+
+    async fn revert_mined_tx_tags(
+        &self,
+        tx_hashes: &[String],
+        created_at: u128,
+    ) -> eyre::Result<Vec<String>> {
+        ensure_hex_hashes(tx_hashes)?;
+        if tx_hashes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The HTTP API has no `RETURNING`, look the mined tags up first. Only the RPC of this
+        // node marks them mined, so they can't change meanwhile.
+        let mined = self
+            .fetch_all::<TxHashRow>(
+                SqlQuery::new("SELECT hash FROM tags WHERE hash IN (?) AND status = 'mined'")
+                    .bind_list(tx_hashes),
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.hash)
+            .collect::<Vec<_>>();
+        if mined.is_empty() {
+            return Ok(mined);
+        }
+
+        self.execute(
+            SqlQuery::new(
+                "UPDATE tags SET status = 'pending', created_at = ? WHERE hash IN (?) AND status = 'mined'",
+            )
+            .bind(created_at)
+            .bind_list(&mined),
+        )
+        .await?;
+
+        Ok(mined)
+    }
+
+    async fn delete_tx_tag(&self, tx_hash: &str) -> eyre::Result<()> {
+        if !is_hex_hash(tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
+
+        self.execute(
+            SqlQuery::new("DELETE FROM tags WHERE hash = ? AND status = 'pending'").bind(tx_hash),
+        )
+        .await
+    }
+
+    async fn expire_tx_tags(&self, created_before: u128) -> eyre::Result<u64> {
+        // The HTTP API doesn't report affected rows, count them first. Rows past the cutoff can
+        // only be mined or deleted meanwhile, so the count is an upper bound.
+        let expired = self
+            .fetch_one::<CountRow>(
+                SqlQuery::new(
+                    "SELECT COUNT(*) AS count FROM tags WHERE status = 'pending' AND created_at < ?",
+                )
+                .bind(created_before),
+            )
+            .await?;
+
+        self.execute(
+            SqlQuery::new("DELETE FROM tags WHERE status = 'pending' AND created_at < ?")
+                .bind(created_before),
+        )
+        .await?;
+
+        Ok(expired.count)
     }
 
-    async fn query_transaction_by_tags(&self, tag: (String, String)) -> Option<String> {
-        #[derive(Serialize, Deserialize, Database)]
-        struct QueryTxByTagResult {
-            pub hash: String,
+    async fn query_transaction_by_tags(
+        &self,
+        tag: (String, String),
+    ) -> eyre::Result<Option<String>> {
+        if !is_valid_tag(&tag) {
+            return Ok(None);
         }
 
-        let query_str = format!(
+        let sql = SqlQuery::new(
             "SELECT t.hash hash
-FROM {} t,
+FROM confirmed_tags t,
 JSON_TABLE(
   t.tags, '$[*]'
   COLUMNS (
@@ -154,16 +416,137 @@ JSON_TABLE(
     tag_value VARCHAR(255) PATH '$[1]'
   )
 ) AS tags_flat
-WHERE tags_flat.tag_key = '{}'
-  AND tags_flat.tag_value = '{}'
+WHERE tags_flat.tag_key = ?
+  AND tags_flat.tag_value = ?
 LIMIT 1;",
-            "confirmed_tags", tag.0, tag.1
-        );
+        )
+        .bind(tag.0)
+        .bind(tag.1);
 
-        let conn = self.get_conn();
+        Ok(self.fetch_all::<TxHashRow>(sql).await?.into_iter().next().map(|row| row.hash))
+    }
+
+    async fn query_transactions(&self, tag_query: &TagQuery) -> eyre::Result<TagQueryPage> {
+        let page_size = tag_query.page_size();
+        let cursor_block = tag_query.cursor.as_ref().map(|c| c.block_number);
+        let cursor_hash = tag_query.cursor.as_ref().map(|c| c.hash.as_str());
+
+        let sql = SqlQuery::new(SELECT_TXS_BY_TAGS)
+            .bind(tag_query.from_block)
+            .bind(tag_query.from_block)
+            .bind(tag_query.to_block)
+            .bind(tag_query.to_block)
+            .bind(tag_query.sender.as_deref())
+            .bind(tag_query.sender.as_deref())
+            .bind(cursor_block)
+            .bind(cursor_block)
+            .bind(cursor_block)
+            .bind(cursor_hash)
+            .bind(tag_query.tags_json()?)
+            .bind(tag_query.required_matches() as u64)
+            .bind(page_size as u64 + 1);
+
+        let rows = self
+            .fetch_all::<TaggedTxRow>(sql)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(TaggedTransaction {
+                    hash: row.hash,
+                    block_number: row.block_number,
+                    sender: Some(row.sender).filter(|s| !s.is_empty()),
+                    tags: serde_json::from_str(&row.tags)?,
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(TagQueryPage::from_rows(rows, page_size))
+    }
+
+    async fn schema_version(&self) -> eyre::Result<Option<u32>> {
+        let exists = self
+            .fetch_one::<CountRow>(SqlQuery::new(
+                "SELECT COUNT(*) AS count FROM information_schema.tables
+                 WHERE table_schema = DATABASE() AND table_name = 'loaddb_migrations'",
+            ))
+            .await?;
+
+        if exists.count == 0 {
+            return Ok(None);
+        }
 
-        let fetch_tag = query(query_str.as_str()).fetch_one::<QueryTxByTagResult>(&conn).await;
+        let row = self
+            .fetch_one::<VersionRow>(SqlQuery::new(
+                "SELECT COALESCE(MAX(version), 0) AS version FROM loaddb_migrations",
+            ))
+            .await?;
+
+        Ok(Some(row.version).filter(|v| *v > 0))
+    }
+
+    async fn migrate(&self) -> eyre::Result<u32> {
+        self.execute(SqlQuery::new(
+            "CREATE TABLE IF NOT EXISTS loaddb_migrations (
+                version INT UNSIGNED NOT NULL PRIMARY KEY,
+                description VARCHAR(255) NOT NULL,
+                applied_at BIGINT UNSIGNED NOT NULL
+            )",
+        ))
+        .await?;
+
+        // The HTTP API can't run DDL inside a transaction, every migration is recorded right
+        // after its statements succeed so a failed run can be resumed.
+        let current = self.schema_version().await?;
+        for migration in pending_migrations(MIGRATIONS, current) {
+            for statement in migration.statements {
+                match self.execute(SqlQuery::new(*statement)).await {
+                    Err(e) if is_already_applied(&e.to_string()) => {
+                        debug!(target: "wvm::loaddb", version = migration.version, %e, "Migration statement already applied");
+                    }
+                    result => result?,
+                }
+            }
+
+            let applied_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+            self.execute(
+                SqlQuery::new(
+                    "INSERT INTO loaddb_migrations (version, description, applied_at) VALUES (?, ?, ?)",
+                )
+                .bind(migration.version as u64)
+                .bind(migration.description)
+                .bind(applied_at),
+            )
+            .await?;
+        }
+
+        Ok(self.schema_version().await?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod planetscale_driver_tests {
+    use crate::drivers::planetscale::{is_already_applied, is_duplicate_key};
+
+    #[test]
+    pub fn test_duplicate_keys_are_detected() {
+        assert!(is_duplicate_key(
+            "target: loaddb.-.primary: vttablet: rpc error: code = AlreadyExists desc = Duplicate entry '0xe0201f1e' for key 'tags.PRIMARY' (errno 1062) (sqlstate 23000)"
+        ));
+        assert!(!is_duplicate_key("Duplicate key name 'tags_status_created_at_idx' (errno 1061)"));
+    }
 
-        fetch_tag.ok().map(|e| e.hash)
+    #[test]
+    pub fn test_already_applied_statements_are_detected() {
+        assert!(is_already_applied(
+            "target: loaddb.-.primary: vttablet: rpc error: code = AlreadyExists desc = Duplicate key name 'confirmed_tags_block_id_idx' (errno 1061) (sqlstate 42000)"
+        ));
+        assert!(is_already_applied("Duplicate column name 'status' (errno 1060) (sqlstate 42S21)"));
+        assert!(is_already_applied(
+            "Duplicate key name 'tags_status_created_at_idx' (errno 1061) (sqlstate 42000)"
+        ));
+        assert!(is_already_applied(
+            "Can't DROP 'sealed_block_with_senders'; check that column/key exists (errno 1091) (sqlstate 42000)"
+        ));
+        assert!(!is_already_applied("Table 'state' doesn't exist (errno 1146) (sqlstate 42S02)"));
     }
 }
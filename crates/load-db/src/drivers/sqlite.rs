@@ -0,0 +1,759 @@
+use crate::{
+    migrations::{pending_migrations, Migration, MIGRATIONS_TABLE},
+    query::{TagQuery, TagQueryPage, TaggedTransaction},
+    sql::{ensure_hex_hashes, is_address, is_hex_hash, is_valid_tag, BlockLookup},
+    ArchivedBlock, LoadDbConnection, RawState,
+};
+use async_trait::async_trait;
+use eyre::eyre;
+use reth_primitives::SealedBlockWithSenders;
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "state, tags and confirmed_tags tables",
+        statements: &[
+            "CREATE TABLE IF NOT EXISTS state (
+                block_number INTEGER PRIMARY KEY,
+                arweave_id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                block_hash TEXT NOT NULL
+            )",
+            "CREATE INDEX IF NOT EXISTS state_arweave_id_idx ON state (arweave_id)",
+            "CREATE INDEX IF NOT EXISTS state_block_hash_idx ON state (block_hash)",
+            "CREATE TABLE IF NOT EXISTS tags (
+                hash TEXT PRIMARY KEY,
+                tags TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            "CREATE TABLE IF NOT EXISTS confirmed_tags (
+                hash TEXT NOT NULL,
+                tags TEXT NOT NULL,
+                block_id INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            "CREATE INDEX IF NOT EXISTS confirmed_tags_hash_idx ON confirmed_tags (hash)",
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "index confirmed tags by block",
+        statements: &[
+            "CREATE INDEX IF NOT EXISTS confirmed_tags_block_id_idx ON confirmed_tags (block_id)",
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "transaction senders and tag query indexes",
+        statements: &[
+            "ALTER TABLE tags ADD COLUMN sender TEXT",
+            "ALTER TABLE confirmed_tags ADD COLUMN sender TEXT",
+            "CREATE INDEX IF NOT EXISTS confirmed_tags_block_hash_idx ON confirmed_tags (block_id, hash)",
+            "CREATE INDEX IF NOT EXISTS confirmed_tags_sender_idx ON confirmed_tags (sender, block_id, hash)",
+        ],
+    },
+    Migration {
+        version: 4,
+        description: "pending tags status",
+        statements: &[
+            "ALTER TABLE tags ADD COLUMN status TEXT NOT NULL DEFAULT 'pending'",
+            "CREATE INDEX IF NOT EXISTS tags_status_created_at_idx ON tags (status, created_at)",
+        ],
+    },
+    // SQLite databases were never created with the legacy column, the version is kept in line
+    // with the other drivers.
+    Migration {
+        version: 5,
+        description: "drop the legacy sealed_block_with_senders column",
+        statements: &[],
+    },
+];
+
+// Keyset pagination over `(block_id, hash)`. The subquery counts how many of the query tags the
+// transaction carries, `?7` is the number of tags required to match.
+const SELECT_TXS_BY_TAGS: &str = "
+SELECT t.hash, t.block_id, t.sender, t.tags FROM confirmed_tags t
+WHERE (?1 IS NULL OR t.block_id >= ?1)
+  AND (?2 IS NULL OR t.block_id <= ?2)
+  AND (?3 IS NULL OR t.sender = ?3)
+  AND (?4 IS NULL OR t.block_id > ?4 OR (t.block_id = ?4 AND t.hash > ?5))
+  AND (
+    SELECT COUNT(DISTINCT q.key) FROM json_each(?6) q, json_each(t.tags) tag
+    WHERE json_extract(tag.value, '$[0]') = json_extract(q.value, '$[0]')
+      AND json_extract(tag.value, '$[1]') = json_extract(q.value, '$[1]')
+  ) >= ?7
+ORDER BY t.block_id, t.hash
+LIMIT ?8";
+
+/// Embedded LoadDb backed by a local SQLite file. Meant for self-hosted and test nodes which
+/// don't have access to PlanetScale.
+///
+/// rusqlite is blocking, the queries run on the blocking pool of the tokio runtime.
+#[derive(Debug)]
+pub struct SqliteDriver {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDriver {
+    pub fn open<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> eyre::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> eyre::Result<Self> {
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    async fn with_conn<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> eyre::Result<T> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|_| eyre!("LoadDb sqlite connection poisoned"))?;
+            f(&mut conn).map_err(|e| eyre!(e.to_string()))
+        })
+        .await
+        .map_err(|e| eyre!("LoadDb sqlite task failed: {e}"))?
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn record_version_for_test(&self, version: u32) {
+        self.with_conn(move |conn| record_migration(conn, version, "test")).await.unwrap();
+    }
+}
+
+fn schema_version(conn: &Connection) -> rusqlite::Result<Option<u32>> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        [MIGRATIONS_TABLE],
+        |row| row.get(0),
+    )?;
+
+    if !exists {
+        return Ok(None);
+    }
+
+    conn.query_row("SELECT MAX(version) FROM loaddb_migrations", [], |row| row.get(0))
+}
+
+fn record_migration(conn: &Connection, version: u32, description: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO loaddb_migrations (version, description, applied_at) VALUES (?1, ?2, ?3)",
+        params![version, description, now_millis().unwrap_or_default()],
+    )?;
+    Ok(())
+}
+
+fn now_millis() -> eyre::Result<i64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64)
+}
+
+fn raw_state_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RawState> {
+    Ok(RawState {
+        block_number: row.get::<_, i64>(0)? as i128,
+        arweave_id: row.get(1)?,
+        timestamp: row.get::<_, i64>(2)? as i128,
+        block_hash: row.get(3)?,
+    })
+}
+
+/// Copies the pending tags of `hashes` into `confirmed_tags`, see [`ensure_hex_hashes`].
+fn confirm_tags(
+    conn: &Connection,
+    hashes: &[String],
+    block_number: u64,
+    timestamp: i64,
+) -> rusqlite::Result<()> {
+    if hashes.is_empty() {
+        return Ok(());
+    }
+
+    let placeholders = (0..hashes.len()).map(|i| format!("?{}", i + 3)).collect::<Vec<_>>();
+    let sql = format!(
+        "INSERT INTO confirmed_tags (hash, tags, sender, block_id, created_at)
+         SELECT t.hash, t.tags, t.sender, ?1, ?2 FROM tags t
+         WHERE t.hash IN ({}) AND t.created_at <= ?2",
+        placeholders.join(", ")
+    );
+
+    let mut values = vec![Value::Integer(block_number as i64), Value::Integer(timestamp)];
+    values.extend(hashes.into_iter().map(|h| Value::Text(h.clone())));
+
+    conn.execute(&sql, params_from_iter(values))?;
+    Ok(())
+}
+
+fn insert_state(
+    conn: &Connection,
+    block_number: u64,
+    arweave_id: &str,
+    block_hash: &str,
+    timestamp: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO state (block_number, arweave_id, timestamp, block_hash)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![block_number as i64, arweave_id, timestamp, block_hash],
+    )?;
+    Ok(())
+}
+
+#[async_trait]
+impl LoadDbConnection for SqliteDriver {
+    async fn query_raw_state(&self, block_id: String) -> eyre::Result<Option<RawState>> {
+        let Some(lookup) = BlockLookup::parse(&block_id) else {
+            return Ok(None);
+        };
+        let (sql, value) = match lookup {
+            BlockLookup::Number(number) => {
+                let Ok(number) = i64::try_from(number) else {
+                    return Ok(None);
+                };
+                (
+                    "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE block_number = ?1",
+                    Value::Integer(number),
+                )
+            }
+            BlockLookup::ArweaveId(id) => (
+                "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE arweave_id = ?1",
+                Value::Text(id),
+            ),
+            BlockLookup::BlockHash(hash) => (
+                "SELECT block_number, arweave_id, timestamp, block_hash FROM state WHERE block_hash = ?1",
+                Value::Text(hash),
+            ),
+        };
+
+        self.with_conn(move |conn| conn.query_row(sql, [value], raw_state_from_row).optional())
+            .await
+    }
+
+    async fn query_transaction_by_tags(
+        &self,
+        tag: (String, String),
+    ) -> eyre::Result<Option<String>> {
+        if !is_valid_tag(&tag) {
+            return Ok(None);
+        }
+
+        self.with_conn(move |conn| {
+            conn.query_row(
+                "SELECT t.hash FROM confirmed_tags t, json_each(t.tags) tags_flat
+                 WHERE json_extract(tags_flat.value, '$[0]') = ?1
+                   AND json_extract(tags_flat.value, '$[1]') = ?2
+                 LIMIT 1",
+                params![tag.0, tag.1],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+        })
+        .await
+    }
+
+    async fn query_transactions(&self, query: &TagQuery) -> eyre::Result<TagQueryPage> {
+        let tags = query.tags_json()?;
+        let page_size = query.page_size();
+        let query = query.clone();
+
+        let rows = self
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare_cached(SELECT_TXS_BY_TAGS)?;
+                let rows = stmt.query_map(
+                    params![
+                        query.from_block.map(|n| n as i64),
+                        query.to_block.map(|n| n as i64),
+                        query.sender,
+                        query.cursor.as_ref().map(|c| c.block_number as i64),
+                        query.cursor.as_ref().map(|c| c.hash.as_str()),
+                        tags,
+                        query.required_matches(),
+                        page_size as i64 + 1,
+                    ],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, i64>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                            row.get::<_, String>(3)?,
+                        ))
+                    },
+                )?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await?;
+
+        let rows = rows
+            .into_iter()
+            .map(|(hash, block_number, sender, tags)| {
+                Ok(TaggedTransaction {
+                    hash,
+                    block_number: block_number as u64,
+                    sender,
+                    tags: serde_json::from_str(&tags)?,
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(TagQueryPage::from_rows(rows, page_size))
+    }
+
+    async fn save_hashes(&self, hashes: &[String], block_number: u64) -> eyre::Result<()> {
+        ensure_hex_hashes(hashes)?;
+        let timestamp = now_millis()?;
+        let hashes = hashes.to_vec();
+        self.with_conn(move |conn| confirm_tags(conn, &hashes, block_number, timestamp)).await
+    }
+
+    async fn save_block(
+        &self,
+        _block: &SealedBlockWithSenders,
+        block_number: u64,
+        arweave_id: String,
+        block_hash: String,
+    ) -> eyre::Result<()> {
+        let timestamp = now_millis()?;
+        self.with_conn(move |conn| {
+            insert_state(conn, block_number, &arweave_id, &block_hash, timestamp)
+        })
+        .await
+    }
+
+    async fn save_archived_blocks(&self, blocks: &[ArchivedBlock]) -> eyre::Result<()> {
+        ensure_hex_hashes(blocks.iter().flat_map(|block| &block.tx_hashes))?;
+        let timestamp = now_millis()?;
+        let blocks = blocks.to_vec();
+
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            for block in &blocks {
+                confirm_tags(&tx, &block.tx_hashes, block.block_number, timestamp)?;
+                insert_state(
+                    &tx,
+                    block.block_number,
+                    &block.arweave_id,
+                    &block.block_hash,
+                    timestamp,
+                )?;
+            }
+            tx.commit()
+        })
+        .await
+    }
+
+    async fn save_tx_tag(
+        &self,
+        tx_hash: String,
+        sender: Option<String>,
+        tags: Vec<(String, String)>,
+        created_at: u128,
+    ) -> eyre::Result<bool> {
+        if !is_hex_hash(&tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
+
+        if sender.as_deref().is_some_and(|sender| !is_address(sender)) {
+            return Err(eyre!("Invalid sender for transaction {}", tx_hash));
+        }
+
+        if !tags.iter().all(is_valid_tag) {
+            return Err(eyre!("Invalid tags for transaction {}", tx_hash));
+        }
+
+        let tags = serde_json::to_string(&tags)?;
+        let sender = sender.map(|s| s.to_lowercase());
+        let created_at = created_at as i64;
+
+        self.with_conn(move |conn| {
+            let values = params![tx_hash, tags, sender, created_at];
+            let inserted = conn.execute(
+                "INSERT OR IGNORE INTO tags (hash, tags, sender, created_at) VALUES (?1, ?2, ?3, ?4)",
+                values,
+            )? > 0;
+            if !inserted {
+                conn.execute(
+                    "UPDATE tags SET tags = ?2, sender = ?3, created_at = ?4
+                     WHERE hash = ?1 AND status = 'pending'",
+                    values,
+                )?;
+            }
+            Ok(inserted)
+        })
+        .await
+    }
+
+    async fn mark_tx_tag_mined(&self, tx_hash: &str) -> eyre::Result<()> {
+        if !is_hex_hash(tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
+
+        let tx_hash = tx_hash.to_string();
+        self.with_conn(move |conn| {
+            conn.execute("UPDATE tags SET status = 'mined' WHERE hash = ?1", params![tx_hash])
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    async fn revert_mined_tx_tags(
+        &self,
+        tx_hashes: &[String],
+        created_at: u128,
+    ) -> eyre::Result<Vec<String>> {
+        ensure_hex_hashes(tx_hashes)?;
+        if tx_hashes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let placeholders = (0..tx_hashes.len()).map(|i| format!("?{}", i + 2)).collect::<Vec<_>>();
+        let sql = format!(
+            "UPDATE tags SET status = 'pending', created_at = ?1
+             WHERE hash IN ({}) AND status = 'mined'
+             RETURNING hash",
+            placeholders.join(", ")
+        );
+
+        let mut values = vec![Value::Integer(created_at as i64)];
+        values.extend(tx_hashes.iter().map(|h| Value::Text(h.clone())));
+
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map(params_from_iter(values), |row| row.get::<_, String>(0))?;
+            rows.collect()
+        })
+        .await
+    }
+
+    async fn delete_tx_tag(&self, tx_hash: &str) -> eyre::Result<()> {
+        if !is_hex_hash(tx_hash) {
+            return Err(eyre!("Invalid transaction hash {}", tx_hash));
+        }
+
+        let tx_hash = tx_hash.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "DELETE FROM tags WHERE hash = ?1 AND status = 'pending'",
+                params![tx_hash],
+            )
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    async fn expire_tx_tags(&self, created_before: u128) -> eyre::Result<u64> {
+        let deleted = self
+            .with_conn(move |conn| {
+                conn.execute(
+                    "DELETE FROM tags WHERE status = 'pending' AND created_at < ?1",
+                    params![created_before as i64],
+                )
+            })
+            .await?;
+
+        Ok(deleted as u64)
+    }
+
+    async fn schema_version(&self) -> eyre::Result<Option<u32>> {
+        self.with_conn(|conn| schema_version(conn)).await
+    }
+
+    async fn migrate(&self) -> eyre::Result<u32> {
+        self.with_conn(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS loaddb_migrations (
+                    version INTEGER PRIMARY KEY,
+                    description TEXT NOT NULL,
+                    applied_at INTEGER NOT NULL
+                )",
+                [],
+            )?;
+
+            let current = schema_version(&tx)?;
+            for migration in pending_migrations(MIGRATIONS, current) {
+                for statement in migration.statements {
+                    tx.execute(statement, [])?;
+                }
+                record_migration(&tx, migration.version, migration.description)?;
+            }
+
+            let version = schema_version(&tx)?.unwrap_or_default();
+            tx.commit()?;
+            Ok(version)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod sqlite_driver_tests {
+    use crate::{
+        drivers::sqlite::SqliteDriver,
+        query::{TagMatch, TagQuery},
+        ArchivedBlock, LoadDbConnection,
+    };
+    use reth_primitives::SealedBlockWithSenders;
+
+    const TX_HASH: &str = "0xe0201f1e284fbe6fa0c90e811194a11a694a08d240f4691996b9182f2e767fee";
+    const BLOCK_HASH: &str = "0xb69e1a4a19c665b0573f74b2bf8e4824cb5b54176f4ad45b730f047e880cf5cc";
+    const ARWEAVE_ID: &str = "bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI";
+
+    async fn test_driver() -> SqliteDriver {
+        let db = SqliteDriver::open_in_memory().unwrap();
+        db.migrate().await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    pub async fn test_state_lookups() {
+        let db = test_driver().await;
+        db.save_block(
+            &SealedBlockWithSenders::default(),
+            141550,
+            ARWEAVE_ID.to_string(),
+            BLOCK_HASH.to_string(),
+        )
+        .await
+        .unwrap();
+
+        for id in ["141550", ARWEAVE_ID, BLOCK_HASH] {
+            let state = db.query_raw_state(id.to_string()).await.unwrap().unwrap();
+            assert_eq!(state.block_number, 141550);
+            assert_eq!(state.arweave_id, ARWEAVE_ID);
+            assert_eq!(state.block_hash, BLOCK_HASH);
+        }
+
+        assert!(db.query_raw_state("141551".to_string()).await.unwrap().is_none());
+        assert!(db.query_raw_state("' OR '1'='1".to_string()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_failed_lookups_are_errors() {
+        // not migrated, every table is missing
+        let db = SqliteDriver::open_in_memory().unwrap();
+        let tag = ("Content-Type".to_string(), "text/plain".to_string());
+
+        assert!(db.query_raw_state("141550".to_string()).await.is_err());
+        assert!(db.query_transaction_by_tags(tag).await.is_err());
+        // ids which can't refer to a block aren't looked up
+        assert!(db.query_raw_state("' OR '1'='1".to_string()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_tags_are_confirmed_with_block() {
+        let db = test_driver().await;
+        let tag = ("Content-Type".to_string(), "text/plain".to_string());
+
+        db.save_tx_tag(TX_HASH.to_string(), None, vec![tag.clone()], 0).await.unwrap();
+        assert!(db.query_transaction_by_tags(tag.clone()).await.unwrap().is_none());
+
+        db.save_hashes(&[TX_HASH.to_string()], 10).await.unwrap();
+        assert_eq!(db.query_transaction_by_tags(tag).await.unwrap(), Some(TX_HASH.to_string()));
+
+        let hostile = ("Content-Type".to_string(), "' OR '1'='1".to_string());
+        assert!(db.query_transaction_by_tags(hostile).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_rejects_invalid_writes() {
+        let db = test_driver().await;
+        assert!(db
+            .save_tx_tag("'; DROP TABLE tags; --".to_string(), None, vec![], 0)
+            .await
+            .is_err());
+        assert!(db
+            .save_tx_tag(TX_HASH.to_string(), None, vec![(String::new(), "v".to_string())], 0)
+            .await
+            .is_err());
+        assert!(db
+            .save_tx_tag(TX_HASH.to_string(), Some("' OR '1'='1".to_string()), vec![], 0)
+            .await
+            .is_err());
+        assert!(db.save_hashes(&[], 10).await.is_ok());
+        assert!(db.save_hashes(&[TX_HASH.to_string(), "0xzz".to_string()], 10).await.is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_reconcile_pending_tags() {
+        let db = test_driver().await;
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+        let mined = format!("0x{:064x}", 1);
+        let dropped = format!("0x{:064x}", 2);
+        let orphaned = format!("0x{:064x}", 3);
+
+        for (hash, created_at) in [(&mined, 10), (&dropped, 10), (&orphaned, 10), (TX_HASH, 50)] {
+            db.save_tx_tag(hash.to_string(), None, vec![tag.clone()], created_at).await.unwrap();
+        }
+
+        db.mark_tx_tag_mined(&mined).await.unwrap();
+        db.delete_tx_tag(&dropped).await.unwrap();
+        // Tags of a mined transaction are only dropped once confirmed.
+        db.delete_tx_tag(&mined).await.unwrap();
+        assert!(db.delete_tx_tag("' OR '1'='1").await.is_err());
+
+        assert_eq!(db.expire_tx_tags(20).await.unwrap(), 1);
+        assert_eq!(db.expire_tx_tags(20).await.unwrap(), 0);
+
+        db.save_hashes(&[mined.clone(), dropped, orphaned, TX_HASH.to_string()], 10).await.unwrap();
+        let query = TagQuery { tags: vec![tag], ..Default::default() }.validate().unwrap();
+        let confirmed = db
+            .query_transactions(&query)
+            .await
+            .unwrap()
+            .transactions
+            .into_iter()
+            .map(|tx| tx.hash)
+            .collect::<Vec<_>>();
+        assert_eq!(confirmed, vec![mined, TX_HASH.to_string()]);
+    }
+
+    #[tokio::test]
+    pub async fn test_revert_mined_tags() {
+        let db = test_driver().await;
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+        let pending = format!("0x{:064x}", 1);
+        let untagged = format!("0x{:064x}", 2);
+
+        for hash in [TX_HASH, &pending] {
+            db.save_tx_tag(hash.to_string(), None, vec![tag.clone()], 0).await.unwrap();
+        }
+        db.mark_tx_tag_mined(TX_HASH).await.unwrap();
+
+        // Only the mined tags are reverted, and they expire from the revert time.
+        let reorged = [TX_HASH.to_string(), pending, untagged];
+        assert_eq!(db.revert_mined_tx_tags(&reorged, 10).await.unwrap(), vec![TX_HASH]);
+        assert!(db.revert_mined_tx_tags(&reorged, 10).await.unwrap().is_empty());
+        assert!(db.revert_mined_tx_tags(&[], 10).await.unwrap().is_empty());
+        assert!(db.revert_mined_tx_tags(&["0xzz".to_string()], 10).await.is_err());
+        assert_eq!(db.expire_tx_tags(5).await.unwrap(), 1);
+        assert_eq!(db.expire_tx_tags(20).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_resubmitted_tags() {
+        let db = test_driver().await;
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+        let resubmitted = ("App-Name".to_string(), "resubmitted".to_string());
+        let pending = format!("0x{:064x}", 1);
+
+        assert!(db.save_tx_tag(TX_HASH.to_string(), None, vec![tag.clone()], 0).await.unwrap());
+        db.mark_tx_tag_mined(TX_HASH).await.unwrap();
+
+        // Resubmitting the mined transaction neither overwrites its tags nor makes them pending
+        // again, so they can't be discarded or expired.
+        assert!(!db
+            .save_tx_tag(TX_HASH.to_string(), None, vec![resubmitted.clone()], 10)
+            .await
+            .unwrap());
+        db.delete_tx_tag(TX_HASH).await.unwrap();
+        assert_eq!(db.expire_tx_tags(i64::MAX as u128).await.unwrap(), 0);
+
+        // Pending tags are updated by a resubmission, which didn't insert them.
+        assert!(db.save_tx_tag(pending.clone(), None, vec![tag.clone()], 0).await.unwrap());
+        assert!(!db
+            .save_tx_tag(pending.clone(), None, vec![resubmitted.clone()], 0)
+            .await
+            .unwrap());
+
+        db.save_hashes(&[TX_HASH.to_string(), pending.clone()], 10).await.unwrap();
+        let query = TagQuery { tags: vec![tag], ..Default::default() }.validate().unwrap();
+        let confirmed = db.query_transactions(&query).await.unwrap().transactions;
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(confirmed[0].hash, TX_HASH);
+        let query = TagQuery { tags: vec![resubmitted], ..Default::default() }.validate().unwrap();
+        let confirmed = db.query_transactions(&query).await.unwrap().transactions;
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(confirmed[0].hash, pending);
+    }
+
+    #[tokio::test]
+    pub async fn test_save_archived_blocks() {
+        let db = test_driver().await;
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+        db.save_tx_tag(TX_HASH.to_string(), None, vec![tag.clone()], 0).await.unwrap();
+
+        let blocks = vec![
+            ArchivedBlock {
+                block_number: 1,
+                arweave_id: ARWEAVE_ID.to_string(),
+                block_hash: BLOCK_HASH.to_string(),
+                tx_hashes: vec![TX_HASH.to_string()],
+            },
+            ArchivedBlock {
+                block_number: 2,
+                arweave_id: "a".repeat(43),
+                block_hash: TX_HASH.to_string(),
+                tx_hashes: vec![],
+            },
+        ];
+        db.save_archived_blocks(&blocks).await.unwrap();
+
+        assert_eq!(db.query_raw_state("2".to_string()).await.unwrap().unwrap().block_hash, TX_HASH);
+        assert_eq!(db.query_transaction_by_tags(tag).await.unwrap(), Some(TX_HASH.to_string()));
+    }
+
+    #[tokio::test]
+    pub async fn test_query_transactions() {
+        let db = test_driver().await;
+        let app = ("App-Name".to_string(), "wvm".to_string());
+        let kind = ("Kind".to_string(), "post".to_string());
+        let sender = "0xa2a0d977847805fe224b789d8c4d3d711ab251e7";
+
+        // Transaction `i` is confirmed in block `i`, odd ones also carry `kind`.
+        let hashes = (0..6u64).map(|i| format!("0x{:064x}", i)).collect::<Vec<_>>();
+        for (i, hash) in hashes.iter().enumerate() {
+            let tags = if i % 2 == 1 { vec![app.clone(), kind.clone()] } else { vec![app.clone()] };
+            let from = if i < 3 { Some(sender.to_string()) } else { None };
+            db.save_tx_tag(hash.clone(), from, tags, 0).await.unwrap();
+            db.save_hashes(&[hash.clone()], i as u64).await.unwrap();
+        }
+
+        let all = TagQuery { tags: vec![app.clone(), kind.clone()], ..Default::default() }
+            .validate()
+            .unwrap();
+        let page = db.query_transactions(&all).await.unwrap();
+        assert_eq!(
+            page.transactions.iter().map(|tx| tx.block_number).collect::<Vec<_>>(),
+            vec![1, 3, 5]
+        );
+        assert_eq!(page.transactions[0].tags, vec![app.clone(), kind.clone()]);
+        assert!(page.next_cursor.is_none());
+
+        let any = TagQuery { match_mode: TagMatch::Any, ..all.clone() };
+        assert_eq!(db.query_transactions(&any).await.unwrap().transactions.len(), 6);
+
+        let range = TagQuery { from_block: Some(2), to_block: Some(4), ..any.clone() };
+        assert_eq!(db.query_transactions(&range).await.unwrap().transactions.len(), 3);
+
+        let by_sender = TagQuery { sender: Some(sender.to_string()), ..any.clone() };
+        let page = db.query_transactions(&by_sender).await.unwrap();
+        assert_eq!(page.transactions.len(), 3);
+        assert_eq!(page.transactions[0].sender.as_deref(), Some(sender));
+
+        // Walk every page, each transaction must show up exactly once.
+        let mut query = TagQuery { limit: Some(4), ..any };
+        let mut seen = vec![];
+        loop {
+            let page = db.query_transactions(&query).await.unwrap();
+            seen.extend(page.transactions.into_iter().map(|tx| tx.hash));
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, hashes);
+    }
+}
@@ -1,29 +1,54 @@
+pub mod archive;
+pub mod batch;
+pub mod config;
 pub mod drivers;
+pub mod migrations;
+pub mod query;
+pub mod reconcile;
+pub mod sql;
 
 use async_trait::async_trait;
 use planetscale_driver::Database;
+use query::{TagQuery, TagQueryPage};
 use reth_primitives::SealedBlockWithSenders;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Database)]
 pub struct RawState {
     pub block_number: i128,
-    pub sealed_block_with_senders: String,
     pub arweave_id: String,
     pub timestamp: i128,
     pub block_hash: String,
 }
 
+#[derive(Serialize, Deserialize, Database)]
+pub struct TxHashRow {
+    pub hash: String,
+}
+
+/// Everything the Arweave actor stores once a block has been archived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedBlock {
+    pub block_number: u64,
+    pub arweave_id: String,
+    pub block_hash: String,
+    pub tx_hashes: Vec<String>,
+}
+
 #[async_trait]
 pub trait LoadDbConnection: Send + Sync + 'static {
     // reads
-    async fn query_raw_state(&self, block_id: String) -> Option<RawState>;
-
-    #[deprecated(
-        note = "Sealed_block_with_senders is deleted from database. Do not use. Ref is not deleted from struct just yet."
-    )]
-    async fn query_state(&self, block_id: String) -> Option<String>;
-    async fn query_transaction_by_tags(&self, tag: (String, String)) -> Option<String>;
+    /// Archive of a block, looked up by number, Arweave id or hash. `None` if the block isn't
+    /// archived or the id can't refer to one, errors are failures of the backend.
+    async fn query_raw_state(&self, block_id: String) -> eyre::Result<Option<RawState>>;
+    /// Hash of a confirmed transaction carrying the tag, `None` if there's none or the tag is
+    /// invalid.
+    async fn query_transaction_by_tags(
+        &self,
+        tag: (String, String),
+    ) -> eyre::Result<Option<String>>;
+    /// Confirmed transactions matching a validated [`TagQuery`], see [`TagQuery::validate`].
+    async fn query_transactions(&self, query: &TagQuery) -> eyre::Result<TagQueryPage>;
 
     //writes
     async fn save_hashes(&self, hashes: &[String], block_number: u64) -> eyre::Result<()>;
@@ -34,10 +59,41 @@ pub trait LoadDbConnection: Send + Sync + 'static {
         arweave_id: String,
         block_hash: String,
     ) -> eyre::Result<()>;
+    /// Saves the pending tags of a transaction, tags which aren't pending anymore are kept as they
+    /// are. Returns whether a new row was inserted, only then the tags may be dropped again by the
+    /// caller with [`Self::delete_tx_tag`].
     async fn save_tx_tag(
         &self,
         tx_hash: String,
+        sender: Option<String>,
         tags: Vec<(String, String)>,
         created_at: u128,
-    ) -> eyre::Result<()>;
+    ) -> eyre::Result<bool>;
+    /// Marks the pending tags of a transaction as mined, they're kept until its block is archived.
+    async fn mark_tx_tag_mined(&self, tx_hash: &str) -> eyre::Result<()>;
+    /// Puts the mined tags of transactions reorged out of the chain back to pending, `created_at`
+    /// restarts their expiry. Returns the hashes which had mined tags.
+    async fn revert_mined_tx_tags(
+        &self,
+        tx_hashes: &[String],
+        created_at: u128,
+    ) -> eyre::Result<Vec<String>>;
+    /// Drops the pending tags of a transaction which won't be mined.
+    async fn delete_tx_tag(&self, tx_hash: &str) -> eyre::Result<()>;
+    /// Drops the tags created before `created_before` whose transaction was never mined.
+    async fn expire_tx_tags(&self, created_before: u128) -> eyre::Result<u64>;
+    /// Confirms the tags and stores the state of several archived blocks at once.
+    async fn save_archived_blocks(&self, blocks: &[ArchivedBlock]) -> eyre::Result<()>;
+
+    // schema
+    /// Latest applied migration, `None` if the database was never migrated.
+    async fn schema_version(&self) -> eyre::Result<Option<u32>>;
+    /// Applies the pending migrations and returns the resulting schema version.
+    async fn migrate(&self) -> eyre::Result<u32>;
+}
+
+impl std::fmt::Debug for dyn LoadDbConnection + Send + Sync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LoadDbConnection")
+    }
 }
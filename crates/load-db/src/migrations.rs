@@ -0,0 +1,158 @@
+//! Versioned LoadDb schema.
+//!
+//! Every driver declares its migrations, numbered from 1 up to [`LOADDB_SCHEMA_VERSION`]. The
+//! applied versions are recorded in the `loaddb_migrations` table, so a node can tell whether the
+//! database it connects to matches the schema it was built for.
+
+use crate::LoadDbConnection;
+use eyre::eyre;
+use tracing::info;
+
+/// Schema version this build of the node expects.
+pub const LOADDB_SCHEMA_VERSION: u32 = 5;
+
+/// Table keeping track of the applied migrations.
+pub const MIGRATIONS_TABLE: &str = "loaddb_migrations";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    /// Statements applied in order. They're kept separate since not every backend accepts
+    /// multiple statements per call.
+    pub statements: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaStatus {
+    /// LoadDb has never been migrated.
+    Uninitialized,
+    /// Migrations up to `current` were applied, the node needs [`LOADDB_SCHEMA_VERSION`].
+    Outdated {
+        current: u32,
+    },
+    UpToDate,
+    /// The database was migrated by a newer node, this one can't safely use it.
+    Newer {
+        current: u32,
+    },
+}
+
+impl SchemaStatus {
+    pub fn from_version(current: Option<u32>) -> Self {
+        match current {
+            None | Some(0) => SchemaStatus::Uninitialized,
+            Some(current) if current < LOADDB_SCHEMA_VERSION => SchemaStatus::Outdated { current },
+            Some(LOADDB_SCHEMA_VERSION) => SchemaStatus::UpToDate,
+            Some(current) => SchemaStatus::Newer { current },
+        }
+    }
+}
+
+/// Migrations of `migrations` not applied yet, in order.
+pub fn pending_migrations(
+    migrations: &'static [Migration],
+    current: Option<u32>,
+) -> impl Iterator<Item = &'static Migration> {
+    let current = current.unwrap_or(0);
+    migrations.iter().filter(move |m| m.version > current)
+}
+
+/// Checks the LoadDb schema at startup. Pending migrations are applied when `auto_migrate` is
+/// set, otherwise the node refuses to start until `reth wvm loaddb migrate` has been run. A schema
+/// newer than [`LOADDB_SCHEMA_VERSION`] is always refused.
+pub async fn ensure_schema(db: &dyn LoadDbConnection, auto_migrate: bool) -> eyre::Result<u32> {
+    let current = db.schema_version().await?;
+
+    match SchemaStatus::from_version(current) {
+        SchemaStatus::UpToDate => Ok(LOADDB_SCHEMA_VERSION),
+        SchemaStatus::Newer { current } => Err(eyre!(
+            "LoadDb schema version {} is newer than the supported version {}, upgrade the node",
+            current,
+            LOADDB_SCHEMA_VERSION
+        )),
+        SchemaStatus::Uninitialized | SchemaStatus::Outdated { .. } if auto_migrate => {
+            let version = db.migrate().await?;
+            info!(target: "wvm::loaddb", version, "LoadDb schema migrated");
+            Ok(version)
+        }
+        SchemaStatus::Uninitialized | SchemaStatus::Outdated { .. } => Err(eyre!(
+            "LoadDb schema version {} is older than the required version {}, run `reth wvm loaddb migrate` or set LOADDB_AUTO_MIGRATE=true",
+            current.unwrap_or(0),
+            LOADDB_SCHEMA_VERSION
+        )),
+    }
+}
+
+/// Makes sure a driver's migrations are numbered `1..=LOADDB_SCHEMA_VERSION` without gaps.
+pub fn validate_migrations(migrations: &[Migration]) -> eyre::Result<()> {
+    for (index, migration) in migrations.iter().enumerate() {
+        if migration.version != index as u32 + 1 {
+            return Err(eyre!("Migration {} is out of order", migration.version));
+        }
+    }
+
+    match migrations.last() {
+        Some(last) if last.version == LOADDB_SCHEMA_VERSION => Ok(()),
+        _ => Err(eyre!("Migrations don't reach schema version {}", LOADDB_SCHEMA_VERSION)),
+    }
+}
+
+#[cfg(test)]
+mod migrations_tests {
+    use crate::{
+        drivers::{planetscale, postgres, sqlite},
+        migrations::{
+            ensure_schema, pending_migrations, validate_migrations, SchemaStatus,
+            LOADDB_SCHEMA_VERSION,
+        },
+        LoadDbConnection,
+    };
+
+    #[test]
+    pub fn test_drivers_reach_schema_version() {
+        validate_migrations(planetscale::MIGRATIONS).unwrap();
+        validate_migrations(postgres::MIGRATIONS).unwrap();
+        validate_migrations(sqlite::MIGRATIONS).unwrap();
+    }
+
+    #[test]
+    pub fn test_schema_status() {
+        assert_eq!(SchemaStatus::from_version(None), SchemaStatus::Uninitialized);
+        assert_eq!(SchemaStatus::from_version(Some(1)), SchemaStatus::Outdated { current: 1 });
+        assert_eq!(SchemaStatus::from_version(Some(LOADDB_SCHEMA_VERSION)), SchemaStatus::UpToDate);
+        assert_eq!(
+            SchemaStatus::from_version(Some(LOADDB_SCHEMA_VERSION + 1)),
+            SchemaStatus::Newer { current: LOADDB_SCHEMA_VERSION + 1 }
+        );
+    }
+
+    #[test]
+    pub fn test_pending_migrations() {
+        assert_eq!(pending_migrations(sqlite::MIGRATIONS, None).count(), sqlite::MIGRATIONS.len());
+        assert_eq!(pending_migrations(sqlite::MIGRATIONS, Some(1)).next().unwrap().version, 2);
+        assert_eq!(pending_migrations(sqlite::MIGRATIONS, Some(LOADDB_SCHEMA_VERSION)).count(), 0);
+    }
+
+    #[tokio::test]
+    pub async fn test_ensure_schema() {
+        let db = sqlite::SqliteDriver::open_in_memory().unwrap();
+
+        assert!(ensure_schema(&db, false).await.is_err());
+        assert_eq!(ensure_schema(&db, true).await.unwrap(), LOADDB_SCHEMA_VERSION);
+        assert_eq!(db.schema_version().await.unwrap(), Some(LOADDB_SCHEMA_VERSION));
+
+        // Migrating twice is a no-op.
+        assert_eq!(db.migrate().await.unwrap(), LOADDB_SCHEMA_VERSION);
+        assert_eq!(ensure_schema(&db, false).await.unwrap(), LOADDB_SCHEMA_VERSION);
+    }
+
+    #[tokio::test]
+    pub async fn test_refuses_newer_schema() {
+        let db = sqlite::SqliteDriver::open_in_memory().unwrap();
+        db.migrate().await.unwrap();
+        db.record_version_for_test(LOADDB_SCHEMA_VERSION + 1).await;
+
+        assert!(ensure_schema(&db, true).await.is_err());
+    }
+}
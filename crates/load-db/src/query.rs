@@ -0,0 +1,264 @@
+//! Tag queries over confirmed WVM transactions.
+//!
+//! Results are ordered by `(block_number, hash)` and paginated with a keyset cursor, so a page is
+//! always served from the indexes no matter how deep the caller goes.
+
+use crate::sql::{is_address, is_hex_hash, is_valid_tag};
+use eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Maximum number of tags in a single query.
+pub const MAX_QUERY_TAGS: usize = 10;
+/// Page size used when the caller doesn't provide one.
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+/// Maximum page size.
+pub const MAX_PAGE_SIZE: u32 = 1_000;
+
+/// How the tags of a [`TagQuery`] are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    /// A transaction must carry every tag.
+    #[default]
+    All,
+    /// A transaction must carry at least one of the tags.
+    Any,
+}
+
+/// Position of the last transaction of a page. Encoded as `<block_number>:<hash>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagCursor {
+    pub block_number: u64,
+    pub hash: String,
+}
+
+impl fmt::Display for TagCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.block_number, self.hash)
+    }
+}
+
+impl FromStr for TagCursor {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (block_number, hash) = s.split_once(':').ok_or_else(|| eyre!("Invalid cursor"))?;
+        let block_number = block_number.parse::<u64>().map_err(|_| eyre!("Invalid cursor"))?;
+
+        if !is_hex_hash(hash) {
+            return Err(eyre!("Invalid cursor"));
+        }
+
+        Ok(Self { block_number, hash: hash.to_lowercase() })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagQuery {
+    pub tags: Vec<(String, String)>,
+    pub match_mode: TagMatch,
+    /// Inclusive lower bound of the block range.
+    pub from_block: Option<u64>,
+    /// Inclusive upper bound of the block range.
+    pub to_block: Option<u64>,
+    /// Only transactions sent by this address.
+    pub sender: Option<String>,
+    /// Only transactions after this position.
+    pub cursor: Option<TagCursor>,
+    pub limit: Option<u32>,
+}
+
+impl TagQuery {
+    /// Checks the query and normalizes it for the drivers: duplicated tags are removed and the
+    /// sender is lowercased.
+    pub fn validate(mut self) -> eyre::Result<Self> {
+        if self.tags.is_empty() {
+            return Err(eyre!("At least one tag is required"));
+        }
+
+        let mut tags = Vec::with_capacity(self.tags.len());
+        for tag in self.tags {
+            if !is_valid_tag(&tag) {
+                return Err(eyre!("Invalid tag {}", tag.0));
+            }
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        if tags.len() > MAX_QUERY_TAGS {
+            return Err(eyre!("At most {} tags can be queried at once", MAX_QUERY_TAGS));
+        }
+        self.tags = tags;
+
+        if let (Some(from), Some(to)) = (self.from_block, self.to_block) {
+            if from > to {
+                return Err(eyre!("Invalid block range {}..={}", from, to));
+            }
+        }
+
+        if let Some(sender) = &self.sender {
+            if !is_address(sender) {
+                return Err(eyre!("Invalid sender {}", sender));
+            }
+            self.sender = Some(sender.to_lowercase());
+        }
+
+        if self.limit == Some(0) || self.limit.is_some_and(|limit| limit > MAX_PAGE_SIZE) {
+            return Err(eyre!("Limit must be between 1 and {}", MAX_PAGE_SIZE));
+        }
+
+        Ok(self)
+    }
+
+    pub fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    /// Number of query tags a transaction has to carry to match.
+    pub fn required_matches(&self) -> u32 {
+        match self.match_mode {
+            TagMatch::All => self.tags.len() as u32,
+            TagMatch::Any => 1,
+        }
+    }
+
+    /// Query tags as a JSON array of pairs, the format the tags are stored in.
+    pub fn tags_json(&self) -> eyre::Result<String> {
+        Ok(serde_json::to_string(&self.tags)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedTransaction {
+    pub hash: String,
+    pub block_number: u64,
+    pub sender: Option<String>,
+    pub tags: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagQueryPage {
+    pub transactions: Vec<TaggedTransaction>,
+    /// Set when more transactions match the query.
+    pub next_cursor: Option<TagCursor>,
+}
+
+impl TagQueryPage {
+    /// Builds a page out of rows fetched with `page_size + 1` as limit, the extra row only tells
+    /// whether there's a next page.
+    pub fn from_rows(mut rows: Vec<TaggedTransaction>, page_size: u32) -> Self {
+        let next_cursor = if rows.len() > page_size as usize {
+            rows.truncate(page_size as usize);
+            rows.last().map(|tx| TagCursor { block_number: tx.block_number, hash: tx.hash.clone() })
+        } else {
+            None
+        };
+
+        Self { transactions: rows, next_cursor }
+    }
+}
+
+/// Tag index kept by the node itself instead of LoadDb, see the `WVM_TAG_INDEX` ExEx.
+pub trait TagIndex: Send + Sync + 'static {
+    /// Records the tags of a transaction sent to this node until it's included in a block. Returns
+    /// whether the tags were added, they're not if the transaction already has pending tags.
+    fn save_pending_tags(&self, tx_hash: &str, tags: Vec<(String, String)>) -> eyre::Result<bool>;
+
+    /// Drops the pending tags of a transaction which won't be included.
+    fn discard_pending_tags(&self, tx_hash: &str) -> eyre::Result<()>;
+
+    /// Same as [`crate::LoadDbConnection::query_transactions`], the returned cursors are only
+    /// meaningful to the index that produced them.
+    fn query_transactions(&self, query: &TagQuery) -> eyre::Result<TagQueryPage>;
+}
+
+impl std::fmt::Debug for dyn TagIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TagIndex")
+    }
+}
+
+#[cfg(test)]
+mod query_tests {
+    use crate::query::{TagCursor, TagMatch, TagQuery, TagQueryPage, TaggedTransaction};
+
+    const TX_HASH: &str = "0xe0201f1e284fbe6fa0c90e811194a11a694a08d240f4691996b9182f2e767fee";
+
+    fn tag(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    pub fn test_cursor_roundtrip() {
+        let cursor = TagCursor { block_number: 42, hash: TX_HASH.to_string() };
+        assert_eq!(cursor.to_string().parse::<TagCursor>().unwrap(), cursor);
+
+        assert!("42".parse::<TagCursor>().is_err());
+        assert!("x:0x00".parse::<TagCursor>().is_err());
+        assert!("42:' OR '1'='1".parse::<TagCursor>().is_err());
+    }
+
+    #[test]
+    pub fn test_validate() {
+        let query = TagQuery {
+            tags: vec![tag("a", "1"), tag("b", "2"), tag("a", "1")],
+            sender: Some("0xAbCd000000000000000000000000000000000001".to_string()),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(query.tags, vec![tag("a", "1"), tag("b", "2")]);
+        assert_eq!(query.sender.as_deref(), Some("0xabcd000000000000000000000000000000000001"));
+        assert_eq!(query.required_matches(), 2);
+        assert_eq!(TagQuery { match_mode: TagMatch::Any, ..query }.required_matches(), 1);
+
+        assert!(TagQuery::default().validate().is_err());
+        assert!(TagQuery { tags: vec![tag("", "1")], ..Default::default() }.validate().is_err());
+        assert!(TagQuery {
+            tags: vec![tag("a", "1")],
+            from_block: Some(10),
+            to_block: Some(9),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(TagQuery { tags: vec![tag("a", "1")], limit: Some(0), ..Default::default() }
+            .validate()
+            .is_err());
+        assert!(TagQuery {
+            tags: vec![tag("a", "1")],
+            sender: Some("' OR '1'='1".to_string()),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_tags_json() {
+        let query = TagQuery { tags: vec![tag("a", "it's"), tag("b", "2")], ..Default::default() };
+        assert_eq!(query.tags_json().unwrap(), r#"[["a","it's"],["b","2"]]"#);
+    }
+
+    #[test]
+    pub fn test_page_from_rows() {
+        let rows = (0..3)
+            .map(|i| TaggedTransaction {
+                hash: TX_HASH.to_string(),
+                block_number: i,
+                sender: None,
+                tags: vec![],
+            })
+            .collect::<Vec<_>>();
+
+        let page = TagQueryPage::from_rows(rows.clone(), 2);
+        assert_eq!(page.transactions.len(), 2);
+        assert_eq!(page.next_cursor.unwrap().block_number, 1);
+
+        let page = TagQueryPage::from_rows(rows, 3);
+        assert_eq!(page.transactions.len(), 3);
+        assert!(page.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,100 @@
+//! Expiry of orphaned transaction tags.
+//!
+//! Tags are saved as `pending` before their transaction is submitted. The RPC follows the pool
+//! events of the transaction to mark them `mined` or drop them, tags whose transaction was never
+//! mined and missed those events, e.g. across a restart, are expired once they're older than
+//! [`TagExpiryConfig::ttl`]. Tags of mined transactions reorged out of the chain are pending again
+//! and expire the same way unless the transaction is mined again.
+//!
+//! The native tag index of the node applies the same config to its own pending tags.
+
+use crate::LoadDbConnection;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// Pending tags older than this are considered orphaned.
+pub const DEFAULT_TAG_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often orphaned tags are looked for.
+pub const DEFAULT_TAG_EXPIRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagExpiryConfig {
+    /// `LOADDB_TAG_TTL_SECS`, age after which pending tags are dropped.
+    pub ttl: Duration,
+    /// `LOADDB_TAG_EXPIRY_INTERVAL_SECS`, delay between two expiry runs.
+    pub interval: Duration,
+}
+
+impl Default for TagExpiryConfig {
+    fn default() -> Self {
+        Self { ttl: DEFAULT_TAG_TTL, interval: DEFAULT_TAG_EXPIRY_INTERVAL }
+    }
+}
+
+impl TagExpiryConfig {
+    pub fn from_env() -> Self {
+        let secs = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|secs| secs.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+        };
+
+        Self {
+            ttl: secs("LOADDB_TAG_TTL_SECS").unwrap_or(DEFAULT_TAG_TTL),
+            interval: secs("LOADDB_TAG_EXPIRY_INTERVAL_SECS")
+                .unwrap_or(DEFAULT_TAG_EXPIRY_INTERVAL),
+        }
+    }
+
+    /// Pending tags created before the returned timestamp, in milliseconds, are expired.
+    pub fn cutoff(&self, now: Duration) -> u128 {
+        now.saturating_sub(self.ttl).as_millis()
+    }
+}
+
+/// Expires orphaned pending tags every [`TagExpiryConfig::interval`], runs until the node exits.
+pub async fn expire_orphaned_tags(
+    db: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+    config: TagExpiryConfig,
+) {
+    let mut interval = tokio::time::interval(config.interval);
+
+    loop {
+        interval.tick().await;
+
+        let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(now) => now,
+            Err(e) => {
+                warn!(target: "wvm::loaddb", %e, "System time is before the unix epoch");
+                continue;
+            }
+        };
+
+        match db.expire_tx_tags(config.cutoff(now)).await {
+            Ok(0) => {}
+            Ok(expired) => info!(target: "wvm::loaddb", expired, "Expired orphaned tags"),
+            Err(e) => warn!(target: "wvm::loaddb", %e, "Failed to expire orphaned tags"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod reconcile_tests {
+    use crate::reconcile::TagExpiryConfig;
+    use std::time::Duration;
+
+    #[test]
+    pub fn test_cutoff() {
+        let config =
+            TagExpiryConfig { ttl: Duration::from_secs(60), interval: Duration::from_secs(1) };
+
+        assert_eq!(config.cutoff(Duration::from_secs(100)), 40_000);
+        assert_eq!(config.cutoff(Duration::from_secs(10)), 0);
+    }
+}
@@ -0,0 +1,320 @@
+//! Minimal safe query layer for the LoadDb drivers.
+//!
+//! Query templates are `&'static str` with `?` placeholders, so user input can never end up in
+//! the SQL text itself. Values are bound with [`SqlQuery::bind`] when the query is sent. The
+//! PlanetScale HTTP API has no server side parameters, strings are sent as hex literals which
+//! don't need any escaping and don't depend on the `sql_mode` (e.g. `NO_BACKSLASH_ESCAPES`).
+
+use eyre::eyre;
+use std::fmt::Write;
+
+/// Maximum number of characters of a tag name or value, matches the `VARCHAR(255)` tag columns.
+pub const MAX_TAG_LEN: usize = 255;
+
+/// A value bound to a `?` placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlParam {
+    Int(i128),
+    UInt(u128),
+    Str(String),
+    Null,
+    /// Comma separated values, for `IN (?)` clauses.
+    List(Vec<SqlParam>),
+}
+
+impl From<u64> for SqlParam {
+    fn from(value: u64) -> Self {
+        SqlParam::UInt(value as u128)
+    }
+}
+
+impl From<u128> for SqlParam {
+    fn from(value: u128) -> Self {
+        SqlParam::UInt(value)
+    }
+}
+
+impl From<i128> for SqlParam {
+    fn from(value: i128) -> Self {
+        SqlParam::Int(value)
+    }
+}
+
+impl From<&str> for SqlParam {
+    fn from(value: &str) -> Self {
+        SqlParam::Str(value.to_string())
+    }
+}
+
+impl From<String> for SqlParam {
+    fn from(value: String) -> Self {
+        SqlParam::Str(value)
+    }
+}
+
+impl From<&String> for SqlParam {
+    fn from(value: &String) -> Self {
+        SqlParam::Str(value.clone())
+    }
+}
+
+impl<T: Into<SqlParam>> From<Option<T>> for SqlParam {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SqlParam::Null, Into::into)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SqlQuery {
+    template: &'static str,
+    params: Vec<SqlParam>,
+}
+
+impl SqlQuery {
+    pub fn new(template: &'static str) -> Self {
+        Self { template, params: vec![] }
+    }
+
+    pub fn bind<T: Into<SqlParam>>(mut self, value: T) -> Self {
+        self.params.push(value.into());
+        self
+    }
+
+    /// Binds every value of `values` as a comma separated list, for `IN (?)` clauses.
+    pub fn bind_list<T: Into<SqlParam>>(self, values: impl IntoIterator<Item = T>) -> Self {
+        self.bind(SqlParam::List(values.into_iter().map(Into::into).collect()))
+    }
+
+    /// Renders the final statement. Fails if the number of bound values doesn't match the number
+    /// of placeholders.
+    pub fn render(&self) -> eyre::Result<String> {
+        let mut params = self.params.iter();
+        let mut out = String::with_capacity(self.template.len());
+
+        for c in self.template.chars() {
+            if c != '?' {
+                out.push(c);
+                continue;
+            }
+
+            let param =
+                params.next().ok_or_else(|| eyre!("Missing value for placeholder in query"))?;
+
+            out.push_str(&render_param(param));
+        }
+
+        if params.next().is_some() {
+            return Err(eyre!("Too many values bound to query"));
+        }
+
+        Ok(out)
+    }
+}
+
+fn render_param(param: &SqlParam) -> String {
+    match param {
+        SqlParam::Int(v) => v.to_string(),
+        SqlParam::UInt(v) => v.to_string(),
+        SqlParam::Str(v) => hex_literal(v),
+        SqlParam::Null => "NULL".to_string(),
+        SqlParam::List(values) => values.iter().map(render_param).collect::<Vec<_>>().join(", "),
+    }
+}
+
+/// Renders a string as a `utf8mb4` hex literal, e.g. `_utf8mb4 X'6869'` for `hi`.
+pub fn hex_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() * 2 + 12);
+    out.push_str("_utf8mb4 X'");
+    for byte in value.as_bytes() {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out.push('\'');
+    out
+}
+
+/// How a block is looked up in the `state` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockLookup {
+    Number(u64),
+    ArweaveId(String),
+    BlockHash(String),
+}
+
+impl BlockLookup {
+    /// Parses a block number, a `0x` prefixed block hash or an Arweave transaction id. Anything
+    /// else is rejected before it reaches the database.
+    pub fn parse(block_id: &str) -> Option<Self> {
+        let block_id = block_id.trim();
+
+        if let Ok(number) = block_id.parse::<u64>() {
+            return Some(BlockLookup::Number(number));
+        }
+
+        if is_hex_hash(block_id) {
+            return Some(BlockLookup::BlockHash(block_id.to_lowercase()));
+        }
+
+        if is_arweave_id(block_id) {
+            return Some(BlockLookup::ArweaveId(block_id.to_string()));
+        }
+
+        None
+    }
+}
+
+/// `0x` followed by 64 hex characters.
+pub fn is_hex_hash(value: &str) -> bool {
+    value.len() == 66 &&
+        value.starts_with("0x") &&
+        value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// `0x` followed by 40 hex characters.
+pub fn is_address(value: &str) -> bool {
+    value.len() == 42 &&
+        value.starts_with("0x") &&
+        value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Arweave ids are 32 bytes encoded as 43 base64url characters.
+pub fn is_arweave_id(value: &str) -> bool {
+    value.len() == 43 && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Tags are stored in `VARCHAR(255)` columns, longer or empty values can never match.
+pub fn is_valid_tag(tag: &(String, String)) -> bool {
+    !tag.0.is_empty() &&
+        tag.0.chars().count() <= MAX_TAG_LEN &&
+        tag.1.chars().count() <= MAX_TAG_LEN
+}
+
+/// Fails on the first of `hashes` that isn't a transaction hash, instead of dropping it.
+pub fn ensure_hex_hashes<'a>(hashes: impl IntoIterator<Item = &'a String>) -> eyre::Result<()> {
+    match hashes.into_iter().find(|hash| !is_hex_hash(hash)) {
+        Some(hash) => Err(eyre!("Invalid transaction hash {}", hash)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod sql_tests {
+    use crate::sql::{
+        ensure_hex_hashes, hex_literal, is_address, is_valid_tag, BlockLookup, SqlQuery,
+        MAX_TAG_LEN,
+    };
+
+    #[test]
+    pub fn test_render_binds_in_order() {
+        let query = SqlQuery::new("SELECT a FROM t WHERE b = ? AND c = ? LIMIT 1")
+            .bind(10u64)
+            .bind("hello")
+            .render()
+            .unwrap();
+        assert_eq!(query, "SELECT a FROM t WHERE b = 10 AND c = _utf8mb4 X'68656c6c6f' LIMIT 1");
+    }
+
+    #[test]
+    pub fn test_render_placeholder_mismatch() {
+        assert!(SqlQuery::new("SELECT ? , ?").bind(1u64).render().is_err());
+        assert!(SqlQuery::new("SELECT ?").bind(1u64).bind(2u64).render().is_err());
+    }
+
+    #[test]
+    pub fn test_hostile_strings() {
+        let hostile = [
+            "' OR '1'='1",
+            "'; DROP TABLE state; --",
+            "\\' OR 1=1 -- ",
+            "abc\0' OR 1=1",
+            "\" OR \"\"=\"",
+            "?",
+        ];
+
+        for input in hostile {
+            let query =
+                SqlQuery::new("SELECT hash FROM tags WHERE hash = ?").bind(input).render().unwrap();
+            let literal =
+                query.strip_prefix("SELECT hash FROM tags WHERE hash = _utf8mb4 X'").unwrap();
+            let literal = literal.strip_suffix('\'').unwrap();
+
+            // Only hex digits inside the literal, whatever the escaping rules of the server.
+            assert!(literal.chars().all(|c| c.is_ascii_hexdigit()), "{query}");
+            assert_eq!(literal.len(), input.len() * 2);
+        }
+    }
+
+    #[test]
+    pub fn test_bound_placeholder_is_not_expanded() {
+        let query = SqlQuery::new("SELECT ?, ?").bind("?").bind(1u64).render().unwrap();
+        assert_eq!(query, "SELECT _utf8mb4 X'3f', 1");
+    }
+
+    #[test]
+    pub fn test_bind_list() {
+        let hashes = vec!["a".to_string(), "b'c".to_string()];
+        let query = SqlQuery::new("SELECT * FROM t WHERE h IN (?) AND n = ?")
+            .bind_list(&hashes)
+            .bind(5u64)
+            .render()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM t WHERE h IN (_utf8mb4 X'61', _utf8mb4 X'622763') AND n = 5"
+        );
+    }
+
+    #[test]
+    pub fn test_bind_option() {
+        let query =
+            SqlQuery::new("SELECT ?, ?").bind(None::<u64>).bind(Some("a")).render().unwrap();
+        assert_eq!(query, "SELECT NULL, _utf8mb4 X'61'");
+    }
+
+    #[test]
+    pub fn test_hex_literal() {
+        assert_eq!(hex_literal(""), "_utf8mb4 X''");
+        assert_eq!(hex_literal("it's"), "_utf8mb4 X'69742773'");
+        assert_eq!(hex_literal("a\\b"), "_utf8mb4 X'615c62'");
+        assert_eq!(hex_literal("é"), "_utf8mb4 X'c3a9'");
+    }
+
+    #[test]
+    pub fn test_block_lookup() {
+        assert_eq!(BlockLookup::parse("1127975"), Some(BlockLookup::Number(1127975)));
+        assert_eq!(
+            BlockLookup::parse("bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI"),
+            Some(BlockLookup::ArweaveId("bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI".to_string()))
+        );
+        let hash = "0xe0201f1e284fbe6fa0c90e811194a11a694a08d240f4691996b9182f2e767fee";
+        assert_eq!(BlockLookup::parse(hash), Some(BlockLookup::BlockHash(hash.to_string())));
+
+        assert_eq!(BlockLookup::parse("1 OR 1=1"), None);
+        assert_eq!(BlockLookup::parse("' OR '1'='1"), None);
+        assert_eq!(BlockLookup::parse("-1"), None);
+        assert_eq!(BlockLookup::parse(""), None);
+    }
+
+    #[test]
+    pub fn test_valid_tag() {
+        assert!(is_valid_tag(&("Content-Type".to_string(), "text/plain".to_string())));
+        assert!(!is_valid_tag(&(String::new(), "value".to_string())));
+        assert!(!is_valid_tag(&("a".repeat(MAX_TAG_LEN + 1), "value".to_string())));
+        // multi-byte characters count once
+        assert!(is_valid_tag(&("name".to_string(), "é".repeat(MAX_TAG_LEN))));
+        assert!(!is_valid_tag(&("name".to_string(), "é".repeat(MAX_TAG_LEN + 1))));
+    }
+
+    #[test]
+    pub fn test_ensure_hex_hashes() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        assert!(ensure_hex_hashes(&[hash.clone()]).is_ok());
+        assert!(ensure_hex_hashes(&[hash, "0xzz".to_string()]).is_err());
+    }
+
+    #[test]
+    pub fn test_is_address() {
+        assert!(is_address("0xa2A0D977847805fE224B789D8C4d3D711ab251e7"));
+        assert!(!is_address("0xa2A0D977847805fE224B789D8C4d3D711ab251e"));
+        assert!(!is_address("0x' OR '1'='1"));
+    }
+}
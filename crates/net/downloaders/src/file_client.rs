@@ -12,7 +12,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_primitives::{BlockBody, Header, SealedHeader};
+use reth_primitives::{Block, BlockBody, Header, SealedHeader};
 use thiserror::Error;
 use tokio::{fs::File, io::AsyncReadExt};
 use tokio_stream::StreamExt;
@@ -92,6 +92,22 @@ impl FileClient {
         Ok(Self::from_reader(&reader[..], file_len).await?.file_client)
     }
 
+    /// Create a new file client from blocks decoded from another source than a chain file.
+    pub fn from_blocks(blocks: impl IntoIterator<Item = Block>) -> Self {
+        let mut headers = HashMap::default();
+        let mut hash_to_number = HashMap::default();
+        let mut bodies = HashMap::default();
+
+        for block in blocks {
+            let block_hash = block.header.hash_slow();
+            headers.insert(block.header.number, block.header.clone());
+            hash_to_number.insert(block_hash, block.header.number);
+            bodies.insert(block_hash, block.into());
+        }
+
+        Self { headers, hash_to_number, bodies }
+    }
+
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<B256> {
         self.headers.get(&self.max_block()?).map(|h| h.hash_slow())
@@ -517,7 +533,7 @@ mod tests {
     use crate::{
         bodies::{
             bodies::BodiesDownloaderBuilder,
-            test_utils::{insert_headers, zip_blocks},
+            test_utils::{create_raw_bodies, insert_headers, zip_blocks},
         },
         headers::{reverse_headers::ReverseHeadersDownloaderBuilder, test_utils::child_header},
         test_utils::{generate_bodies, generate_bodies_file},
@@ -559,6 +575,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn streams_bodies_from_blocks() {
+        let factory = create_test_provider_factory();
+        let (headers, mut bodies) = generate_bodies(0..=19);
+
+        insert_headers(factory.db_ref().db(), &headers);
+
+        let client = Arc::new(FileClient::from_blocks(create_raw_bodies(
+            headers.iter().cloned(),
+            &mut bodies.clone(),
+        )));
+        assert_eq!(client.tip(), Some(headers.last().unwrap().hash()));
+        assert!(client.has_canonical_blocks());
+
+        let mut downloader = BodiesDownloaderBuilder::default().build(
+            client.clone(),
+            Arc::new(TestConsensus::default()),
+            factory,
+        );
+        downloader.set_download_range(0..=19).expect("failed to set download range");
+
+        assert_matches!(
+            downloader.next().await,
+            Some(Ok(res)) => assert_eq!(res, zip_blocks(headers.iter(), &mut bodies))
+        );
+    }
+
     #[tokio::test]
     async fn download_headers_at_fork_head() {
         reth_tracing::init_test_tracing();
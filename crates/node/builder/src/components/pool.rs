@@ -48,6 +48,8 @@ pub struct PoolBuilderConfigOverrides {
     pub blob_limit: Option<SubPoolLimit>,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: Option<usize>,
+    /// Max combined size (in bytes) of the transactions of a single account
+    pub max_account_bytes: Option<usize>,
     /// Minimum base fee required by the protocol.
     pub minimal_protocol_basefee: Option<u64>,
     /// Addresses that will be considered as local. Above exemptions apply.
@@ -65,6 +67,7 @@ impl PoolBuilderConfigOverrides {
             queued_limit,
             blob_limit,
             max_account_slots,
+            max_account_bytes,
             minimal_protocol_basefee,
             local_addresses,
             additional_validation_tasks: _,
@@ -85,6 +88,9 @@ impl PoolBuilderConfigOverrides {
         if let Some(max_account_slots) = max_account_slots {
             config.max_account_slots = max_account_slots;
         }
+        if let Some(max_account_bytes) = max_account_bytes {
+            config.max_account_bytes = max_account_bytes;
+        }
         if let Some(minimal_protocol_basefee) = minimal_protocol_basefee {
             config.minimal_protocol_basefee = minimal_protocol_basefee;
         }
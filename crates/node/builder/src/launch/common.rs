@@ -1088,6 +1088,9 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    wvm_tags_full: false,
+                    wvm_tags_distance: None,
+                    wvm_tags_before: None,
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
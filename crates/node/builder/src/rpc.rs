@@ -5,10 +5,12 @@ use std::{
     future::Future,
     marker::PhantomData,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 use alloy_rpc_types::engine::ClientVersionV1;
 use futures::TryFutureExt;
+use load_db::{archive::ArchiveGateway, query::TagIndex, LoadDbConnection};
 use reth_node_api::{
     AddOnsContext, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes, NodeTypesWithEngine,
 };
@@ -339,6 +341,12 @@ pub struct RpcAddOns<Node: FullNodeComponents, EthApi: EthApiTypes, EV> {
     eth_api_builder: Box<dyn FnOnce(&EthApiBuilderCtx<Node>) -> EthApi + Send + Sync>,
     /// Engine validator
     engine_validator_builder: EV,
+    /// WVM: LoadDb connection serving the `wvm` namespace
+    load_db: Option<Arc<dyn LoadDbConnection + Send + Sync>>,
+    /// WVM: Arweave gateway describing the archives of the `wvm` namespace
+    archive_gateway: Option<Arc<dyn ArchiveGateway>>,
+    /// WVM: tag index kept by the node, serving the tag methods of the `wvm` namespace
+    tag_index: Option<Arc<dyn TagIndex>>,
     _pd: PhantomData<(Node, EthApi)>,
 }
 
@@ -350,6 +358,9 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes, EV: Debug> Debug
             .field("hooks", &self.hooks)
             .field("eth_api_builder", &"...")
             .field("engine_validator_builder", &self.engine_validator_builder)
+            .field("load_db", &self.load_db)
+            .field("archive_gateway", &self.archive_gateway)
+            .field("tag_index", &self.tag_index)
             .finish()
     }
 }
@@ -364,10 +375,31 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes, EV> RpcAddOns<Node, EthApi,
             hooks: RpcHooks::default(),
             eth_api_builder: Box::new(eth_api_builder),
             engine_validator_builder,
+            load_db: None,
+            archive_gateway: None,
+            tag_index: None,
             _pd: PhantomData,
         }
     }
 
+    /// WVM: sets the LoadDb connection, the `wvm` namespace is only served with one.
+    pub fn with_load_db(mut self, load_db: Arc<dyn LoadDbConnection + Send + Sync>) -> Self {
+        self.load_db = Some(load_db);
+        self
+    }
+
+    /// WVM: sets the Arweave gateway describing the archives of `wvm_getArweaveStorageProof`.
+    pub fn with_archive_gateway(mut self, archive_gateway: Arc<dyn ArchiveGateway>) -> Self {
+        self.archive_gateway = Some(archive_gateway);
+        self
+    }
+
+    /// WVM: sets the tag index kept by the node, tags are queried from LoadDb without one.
+    pub fn with_tag_index(mut self, tag_index: Arc<dyn TagIndex>) -> Self {
+        self.tag_index = Some(tag_index);
+        self
+    }
+
     /// Sets the hook that is run once the rpc server is started.
     pub fn on_rpc_started<F>(mut self, hook: F) -> Self
     where
@@ -409,7 +441,15 @@ where
     type Handle = RpcHandle<N, EthApi>;
 
     async fn launch_add_ons(self, ctx: AddOnsContext<'_, N>) -> eyre::Result<Self::Handle> {
-        let Self { eth_api_builder, engine_validator_builder, hooks, _pd: _ } = self;
+        let Self {
+            eth_api_builder,
+            engine_validator_builder,
+            hooks,
+            load_db,
+            archive_gateway,
+            tag_index,
+            _pd: _,
+        } = self;
 
         let engine_validator = engine_validator_builder.build(&ctx).await?;
         let AddOnsContext { node, config, beacon_engine_handle, jwt_secret } = ctx;
@@ -438,7 +478,7 @@ where
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
-        let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
+        let mut rpc_builder = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
             .with_network(node.network().clone())
@@ -446,8 +486,18 @@ where
             .with_executor(node.task_executor().clone())
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
-            .with_consensus(node.consensus().clone())
-            .build_with_auth_server(module_config, engine_api, eth_api_builder);
+            .with_consensus(node.consensus().clone());
+        if let Some(load_db) = load_db {
+            rpc_builder = rpc_builder.with_load_db(load_db);
+        }
+        if let Some(archive_gateway) = archive_gateway {
+            rpc_builder = rpc_builder.with_archive_gateway(archive_gateway);
+        }
+        if let Some(tag_index) = tag_index {
+            rpc_builder = rpc_builder.with_tag_index(tag_index);
+        }
+        let (mut modules, mut auth_module, registry) =
+            rpc_builder.build_with_auth_server(module_config, engine_api, eth_api_builder);
 
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
@@ -82,6 +82,19 @@ pub struct PruningArgs {
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // WVM Tags
+    /// Prunes all WVM transaction tags.
+    #[arg(long = "prune.wvmtags.full", conflicts_with_all = &["wvm_tags_distance", "wvm_tags_before"])]
+    pub wvm_tags_full: bool,
+    /// Prune WVM transaction tags before the `head-N` block number. In other words, keep last
+    /// N + 1 blocks.
+    #[arg(long = "prune.wvmtags.distance", value_name = "BLOCKS", conflicts_with_all = &["wvm_tags_full", "wvm_tags_before"])]
+    pub wvm_tags_distance: Option<u64>,
+    /// Prune WVM transaction tags before the specified block number. The specified block number is
+    /// not pruned.
+    #[arg(long = "prune.wvmtags.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["wvm_tags_full", "wvm_tags_distance"])]
+    pub wvm_tags_before: Option<BlockNumber>,
+
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
@@ -111,6 +124,7 @@ impl PruningArgs {
                         .or(Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    wvm_tags: None,
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract()
@@ -141,6 +155,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.wvm_tags_prune_mode() {
+            config.segments.wvm_tags = Some(mode);
+        }
 
         Some(config)
     }
@@ -203,6 +220,18 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn wvm_tags_prune_mode(&self) -> Option<PruneMode> {
+        if self.wvm_tags_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.wvm_tags_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.wvm_tags_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 pub(crate) fn parse_receipts_log_filter(
@@ -8,13 +8,12 @@ use clap::Args;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
-    validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
-    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
-use std::sync::atomic::Ordering::SeqCst;
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -44,6 +43,10 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max combined size in bytes of the transactions of a single account
+    #[arg(long = "txpool.max-account-bytes", alias = "txpool.max_account_bytes", default_value_t = TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER)]
+    pub max_account_bytes: usize,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
@@ -54,16 +57,18 @@ pub struct TxPoolArgs {
     pub minimal_protocol_basefee: u64,
 
     /// The default enforced gas limit for transactions entering the pool
-    #[arg(long = "txpool.gas-limit", default_value_t = *ETHEREUM_BLOCK_GAS_LIMIT)]
+    #[arg(long = "txpool.gas-limit", default_value_t = ETHEREUM_BLOCK_GAS_LIMIT)]
     pub gas_limit: u64,
 
     /// Price bump percentage to replace an already existing blob transaction
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
-    /// Max size in bytes of a single transaction allowed to enter the pool
-    #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = internal_default_max_tx_bytes())]
-    pub max_tx_input_bytes: usize,
+    // WVM: unset by default, so the limit of the chain spec applies
+    /// Max size in bytes of a single transaction allowed to enter the pool, overriding the limit
+    /// of the chain spec
+    #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes")]
+    pub max_tx_input_bytes: Option<usize>,
 
     /// The maximum number of blobs to keep in the in memory blob cache.
     #[arg(long = "txpool.max-cached-entries", alias = "txpool.max_cached_entries", default_value_t = DEFAULT_MAX_CACHED_BLOBS)]
@@ -91,10 +96,6 @@ pub struct TxPoolArgs {
     pub new_tx_listener_buffer_size: usize,
 }
 
-fn internal_default_max_tx_bytes() -> usize {
-    *DEFAULT_MAX_TX_INPUT_BYTES
-}
-
 impl Default for TxPoolArgs {
     fn default() -> Self {
         Self {
@@ -105,11 +106,12 @@ impl Default for TxPoolArgs {
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_account_bytes: TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
-            minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE.load(SeqCst),
-            gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+            minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
-            max_tx_input_bytes: *DEFAULT_MAX_TX_INPUT_BYTES,
+            max_tx_input_bytes: None,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
             locals: Default::default(),
@@ -147,6 +149,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.queued_max_size * 1024 * 1024,
             },
             max_account_slots: self.max_account_slots,
+            max_account_bytes: self.max_account_bytes,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
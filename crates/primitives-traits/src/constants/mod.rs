@@ -2,17 +2,6 @@
 
 use alloy_primitives::{b256, B256, U256};
 use core::time::Duration;
-use fees::{
-    util::raw_calculate_lowest_possible_gas_price,
-    wvm_fee::{WvmFee, WvmFeeManager},
-};
-use std::{
-    cell::LazyCell,
-    sync::{
-        atomic::{AtomicU64, Ordering::SeqCst},
-        Arc, LazyLock,
-    },
-};
 
 /// Gas units, for example [`GIGAGAS`].
 pub mod gas_units;
@@ -43,57 +32,22 @@ pub const EPOCH_DURATION: Duration = Duration::from_secs(12 * EPOCH_SLOTS);
 pub const BEACON_NONCE: u64 = 0u64;
 
 /// The default Ethereum block gas limit.
-// TODO: This should be a chain spec parameter.
-/// See <https://github.com/paradigmxyz/reth/issues/3233>.
-/// WVM: we set 300kk gas limit
-pub const ETHEREUM_BLOCK_GAS_LIMIT: LazyCell<u64> = LazyCell::new(|| 500_000_000); // WVM: 500_000_000 gas limit
+///
+/// WVM: 500_000_000 gas, the gas limit of a chain is scheduled in its chain spec.
+pub const ETHEREUM_BLOCK_GAS_LIMIT: u64 = 500_000_000;
+
+/// WVM: the default budget (in bytes) for the combined encoded size of the transactions the
+/// payload builder packs into a block when the chain spec schedules no maximum block size: 16 MiB.
+pub const DEFAULT_BLOCK_SIZE_BUDGET: usize = 16 * 1024 * 1024;
 
 /// The minimum tx fee below which the txpool will reject the transaction.
 ///
-/// Configured to `7` WEI which is the lowest possible value of base fee under mainnet EIP-1559
-/// parameters. `BASE_FEE_MAX_CHANGE_DENOMINATOR` <https://eips.ethereum.org/EIPS/eip-1559>
-/// is `8`, or 12.5%. Once the base fee has dropped to `7` WEI it cannot decrease further because
-/// 12.5% of 7 is less than 1.
-///
-/// Note that min base fee under different 1559 parameterizations may differ, but there's no
-/// significant harm in leaving this setting as is.
-// pub const MIN_PROTOCOL_BASE_FEE: u64 = 7;
-
-// WVM: min base fee 7 => 500k
-pub static MIN_PROTOCOL_BASE_FEE: LazyLock<AtomicU64> =
-    LazyLock::new(|| AtomicU64::new(500_000u64));
-
-/// The WVM fee manager singleton that handles fee calculations and updates across the system.
-/// This manager maintains the dynamic fee state and provides fee calculation services.
-pub static WVM_FEE_MANAGER: LazyLock<Arc<WvmFeeManager>> = LazyLock::new(|| {
-    let fee = WvmFee::new(Some(Box::new(move |price| {
-        let original_price = price as f64 / 1_000_000_000f64;
-        let lowest_possible_gas_price_in_gwei =
-            raw_calculate_lowest_possible_gas_price(original_price, *ETHEREUM_BLOCK_GAS_LIMIT);
-        let mut to_wei = lowest_possible_gas_price_in_gwei * 1e9;
-        // WVM: minimum fee check
-        if to_wei < 500_000f64 {
-            to_wei = 500_000f64;
-        }
-        MIN_PROTOCOL_BASE_FEE.store(to_wei as u64, SeqCst);
-        Ok(())
-    })));
-
-    fee.init();
-
-    let manager = WvmFeeManager::new(Arc::new(fee));
-    manager.init();
-
-    Arc::new(manager)
-});
-
-/// Returns the current minimum protocol base fee
-pub fn get_latest_min_protocol_base_fee() -> u64 {
-    MIN_PROTOCOL_BASE_FEE.load(SeqCst)
-}
+/// WVM: configured to `500_000` WEI. This is a local policy of the txpool, the minimum base fee
+/// enforced by consensus is the base fee floor scheduled in the chain spec.
+pub const MIN_PROTOCOL_BASE_FEE: u64 = 500_000;
 
 /// Same as [`MIN_PROTOCOL_BASE_FEE`] but as a U256.
-pub const MIN_PROTOCOL_BASE_FEE_U256: U256 = U256::from_limbs([640_000_000u64, 0u64, 0u64, 0u64]);
+pub const MIN_PROTOCOL_BASE_FEE_U256: U256 = U256::from_limbs([500_000u64, 0u64, 0u64, 0u64]);
 
 /// Initial base fee as defined in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
 pub const EIP1559_INITIAL_BASE_FEE: u64 = 1_000_000_000;
@@ -125,23 +79,8 @@ pub const BEACON_CONSENSUS_REORG_UNWIND_DEPTH: u64 = 3;
 mod tests {
     use super::*;
 
-    use crate::constants::{get_latest_min_protocol_base_fee, WVM_FEE_MANAGER};
-    use std::time::Duration;
-
-    #[tokio::test]
-    pub async fn test_wvm_fee_manager() {
-        let init = &*WVM_FEE_MANAGER;
-        tokio::time::sleep(Duration::from_secs(10)).await;
-        let latest_gas = get_latest_min_protocol_base_fee();
-        assert!(&latest_gas > &630000000);
-        println!("{}", latest_gas);
-        assert!(&latest_gas < &650000000);
-    }
-
-    #[tokio::test]
-    async fn min_protocol_sanity() {
-        let init = &*WVM_FEE_MANAGER;
-        tokio::time::sleep(Duration::from_secs(10)).await;
-        assert_eq!(MIN_PROTOCOL_BASE_FEE_U256.to::<u64>(), get_latest_min_protocol_base_fee());
+    #[test]
+    fn min_protocol_sanity() {
+        assert_eq!(MIN_PROTOCOL_BASE_FEE_U256.to::<u64>(), MIN_PROTOCOL_BASE_FEE);
     }
 }
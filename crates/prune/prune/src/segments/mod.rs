@@ -18,7 +18,7 @@ use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
     AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    TransactionLookup, WvmTags,
 };
 
 /// A segment represents a pruning of some portion of the data.
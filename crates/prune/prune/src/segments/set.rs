@@ -1,6 +1,6 @@
 use crate::segments::{
     AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    UserReceipts, WvmTags,
 };
 use reth_db::transaction::DbTxMut;
 use reth_provider::{
@@ -59,6 +59,7 @@ where
             receipts,
             account_history,
             storage_history,
+            wvm_tags,
             receipts_log_filter,
         } = prune_modes;
 
@@ -84,6 +85,8 @@ where
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // WVM transaction tags
+            .segment_opt(wvm_tags.map(WvmTags::new))
     }
 }
 
@@ -5,6 +5,7 @@ mod receipts_by_logs;
 mod sender_recovery;
 mod storage_history;
 mod transaction_lookup;
+mod wvm_tags;
 
 pub use account_history::AccountHistory;
 pub use receipts::Receipts;
@@ -12,3 +13,4 @@ pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
 pub use storage_history::StorageHistory;
 pub use transaction_lookup::TransactionLookup;
+pub use wvm_tags::WvmTags;
@@ -0,0 +1,170 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment, SegmentOutput},
+    PrunerError,
+};
+use reth_db::{tables, transaction::DbTxMut};
+use reth_db_api::models::{wvm_tag_key, WvmTagIndexKey, WvmTxKey};
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneLimiter, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct WvmTags {
+    mode: PruneMode,
+}
+
+impl WvmTags {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for WvmTags
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::WvmTags
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No WVM tags to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+
+        let mut index_keys = Vec::new();
+        let mut last_pruned_block = None;
+        let (pruned, done) =
+            provider.tx_ref().prune_table_with_range::<tables::WvmTxTags>(
+                WvmTxKey::new(*range.start(), 0)..=WvmTxKey::new(range_end, u32::MAX),
+                &mut limiter,
+                |_| false,
+                |(key, entry)| {
+                    index_keys.extend(
+                        entry.tags.0.iter().map(|(name, value)| {
+                            WvmTagIndexKey::new(wvm_tag_key(name, value), key)
+                        }),
+                    );
+                    last_pruned_block = Some(key.block_number);
+                },
+            )?;
+
+        // Index entries always go together with their transaction, so they're pruned without a
+        // limit and only accounted for afterwards.
+        index_keys.sort_unstable();
+        let (pruned_index, _) =
+            provider.tx_ref().prune_table_with_iterator::<tables::WvmTagIndex>(
+                index_keys,
+                &mut PruneLimiter::default(),
+                |_| {},
+            )?;
+        limiter.increment_deleted_entries_count_by(pruned_index);
+
+        trace!(target: "pruner", %pruned, %pruned_index, %done, "Pruned WVM tags");
+
+        let last_pruned_block = if done {
+            Some(range_end)
+        } else {
+            // If there's more tags to prune, set the checkpoint block number to previous, so we
+            // could finish pruning the tags of this block on the next run.
+            last_pruned_block.and_then(|block| block.checked_sub(1))
+        };
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned + pruned_index,
+            checkpoint: last_pruned_block.map(|block_number| SegmentOutputCheckpoint {
+                block_number: Some(block_number),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, WvmTags};
+    use alloy_primitives::{Address, TxHash};
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_db_api::models::{
+        wvm_tag_key, WvmTagEntry, WvmTagIndexKey, WvmTags as StoredWvmTags, WvmTxKey,
+    };
+    use reth_provider::{DBProvider, DatabaseProviderFactory};
+    use reth_prune_types::{PruneLimiter, PruneMode};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        db.commit(|tx| {
+            for block_number in 1..=10u64 {
+                for tx_index in 0..2u32 {
+                    let key = WvmTxKey::new(block_number, tx_index);
+                    let tx_hash =
+                        TxHash::with_last_byte((block_number * 2 + tx_index as u64) as u8);
+                    let tags = vec![
+                        ("App-Name".to_string(), "wvm".to_string()),
+                        ("Block".to_string(), block_number.to_string()),
+                    ];
+                    for (name, value) in &tags {
+                        tx.put::<tables::WvmTagIndex>(
+                            WvmTagIndexKey::new(wvm_tag_key(name, value), key),
+                            tx_hash,
+                        )?;
+                    }
+                    tx.put::<tables::WvmTxTags>(
+                        key,
+                        WvmTagEntry { tx_hash, sender: Address::ZERO, tags: StoredWvmTags(tags) },
+                    )?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 6;
+        let segment = WvmTags::new(PruneMode::Before(to_block + 1));
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert!(result.progress.is_finished());
+        assert_eq!(result.pruned, 6 * 2 * 3);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+
+        let remaining = db.table::<tables::WvmTxTags>().unwrap();
+        assert_eq!(remaining.len(), 4 * 2);
+        assert!(remaining.iter().all(|(key, _)| key.block_number > to_block));
+        assert!(db
+            .table::<tables::WvmTagIndex>()
+            .unwrap()
+            .iter()
+            .all(|(key, _)| key.tx.block_number > to_block));
+        assert_eq!(db.table::<tables::WvmTagIndex>().unwrap().len(), 4 * 2 * 2);
+    }
+}
@@ -37,6 +37,9 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// WVM transaction tags pruning configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wvm_tags: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -59,6 +62,8 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            // WVM: the tag index isn't chain data, it's only pruned when configured explicitly.
+            wvm_tags: None,
             receipts_log_filter: Default::default(),
         }
     }
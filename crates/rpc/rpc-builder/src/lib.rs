@@ -177,6 +177,7 @@ use jsonrpsee::{
     },
     Methods, RpcModule,
 };
+use load_db::{archive::ArchiveGateway, query::TagIndex, LoadDbConnection};
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::Consensus;
 use reth_engine_primitives::EngineTypes;
@@ -189,12 +190,13 @@ use reth_provider::{
 };
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
+    TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api, WvmApi,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
-    helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
+    helpers::{Call, EthApiSpec, EthFees, EthTransactions, LoadPendingBlock, TraceExt},
     EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcReceipt, RpcTransaction,
+    WvmApiServer, WvmLegacyEthApiServer,
 };
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
@@ -313,6 +315,13 @@ pub struct RpcModuleBuilder<
     block_executor: BlockExecutor,
     /// The consensus implementation.
     consensus: Consensus,
+    /// WVM: LoadDb connection of the `wvm` namespace, see [`RpcModuleBuilder::with_load_db`].
+    load_db: Option<Arc<dyn LoadDbConnection + Send + Sync>>,
+    /// WVM: Arweave gateway of the `wvm` namespace, see
+    /// [`RpcModuleBuilder::with_archive_gateway`].
+    archive_gateway: Option<Arc<dyn ArchiveGateway>>,
+    /// WVM: native tag index of the `wvm` namespace, see [`RpcModuleBuilder::with_tag_index`].
+    tag_index: Option<Arc<dyn TagIndex>>,
 }
 
 // === impl RpcBuilder ===
@@ -332,7 +341,40 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         block_executor: BlockExecutor,
         consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config, block_executor, consensus }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db: None,
+            archive_gateway: None,
+            tag_index: None,
+        }
+    }
+
+    /// WVM: configures the LoadDb connection serving the `wvm` namespace and its `eth` aliases,
+    /// neither is registered without one.
+    pub fn with_load_db(mut self, load_db: Arc<dyn LoadDbConnection + Send + Sync>) -> Self {
+        self.load_db = Some(load_db);
+        self
+    }
+
+    /// WVM: configures the Arweave gateway describing the archives served by
+    /// `wvm_getArweaveStorageProof`, which are only located with LoadDb without one.
+    pub fn with_archive_gateway(mut self, archive_gateway: Arc<dyn ArchiveGateway>) -> Self {
+        self.archive_gateway = Some(archive_gateway);
+        self
+    }
+
+    /// WVM: configures the tag index kept by the node, which serves the tag methods of the `wvm`
+    /// namespace instead of LoadDb.
+    pub fn with_tag_index(mut self, tag_index: Arc<dyn TagIndex>) -> Self {
+        self.tag_index = Some(tag_index);
+        self
     }
 
     /// Configure the provider instance.
@@ -343,8 +385,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -354,6 +407,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -366,7 +422,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         P: TransactionPool + 'static,
     {
         let Self {
-            provider, network, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -377,6 +443,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -398,7 +467,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, executor, events, network, evm_config, block_executor, consensus, ..
+            provider,
+            executor,
+            events,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -409,6 +488,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             block_executor,
             pool: NoopTransactionPool::default(),
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -421,7 +503,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         N: NetworkInfo + Peers + 'static,
     {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -432,6 +524,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -453,7 +548,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         Consensus,
     > {
         let Self {
-            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -464,6 +569,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -475,8 +583,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -486,6 +605,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -505,8 +627,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         BlockExecutor,
         Consensus,
     > {
-        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
-            self;
+        let Self {
+            pool,
+            network,
+            provider,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -516,6 +649,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -528,7 +664,17 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         E: CanonStateSubscriptions + 'static,
     {
         let Self {
-            provider, pool, executor, network, evm_config, block_executor, consensus, ..
+            provider,
+            pool,
+            executor,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
         } = self;
         RpcModuleBuilder {
             provider,
@@ -539,6 +685,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -550,8 +699,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, block_executor, consensus, .. } =
-            self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            network,
+            events,
+            block_executor,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -561,6 +721,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -572,7 +735,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
     where
         BE: BlockExecutorProvider,
     {
-        let Self { provider, network, pool, executor, events, evm_config, consensus, .. } = self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -582,6 +757,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 
@@ -590,8 +768,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
         self,
         consensus: C,
     ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, C> {
-        let Self { provider, network, pool, executor, events, evm_config, block_executor, .. } =
-            self;
+        let Self {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            load_db,
+            archive_gateway,
+            tag_index,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -601,6 +790,9 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         }
     }
 }
@@ -648,6 +840,9 @@ where
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
@@ -664,6 +859,9 @@ where
             eth,
             block_executor,
         );
+        registry.load_db = load_db;
+        registry.archive_gateway = archive_gateway;
+        registry.tag_index = tag_index;
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -723,8 +921,11 @@ where
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         } = self;
-        RpcRegistryInner::new(
+        let mut registry = RpcRegistryInner::new(
             provider,
             pool,
             network,
@@ -735,7 +936,11 @@ where
             evm_config,
             eth,
             block_executor,
-        )
+        );
+        registry.load_db = load_db;
+        registry.archive_gateway = archive_gateway;
+        registry.tag_index = tag_index;
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -759,6 +964,9 @@ where
             evm_config,
             block_executor,
             consensus,
+            load_db,
+            archive_gateway,
+            tag_index,
         } = self;
 
         if !module_config.is_empty() {
@@ -776,6 +984,9 @@ where
                 eth,
                 block_executor,
             );
+            registry.load_db = load_db;
+            registry.archive_gateway = archive_gateway;
+            registry.tag_index = tag_index;
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -897,6 +1108,13 @@ pub struct RpcRegistryInner<
     blocking_pool_guard: BlockingTaskGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// WVM: LoadDb connection of the `wvm` namespace, see [`RpcModuleBuilder::with_load_db`].
+    load_db: Option<Arc<dyn LoadDbConnection + Send + Sync>>,
+    /// WVM: Arweave gateway of the `wvm` namespace, see
+    /// [`RpcModuleBuilder::with_archive_gateway`].
+    archive_gateway: Option<Arc<dyn ArchiveGateway>>,
+    /// WVM: native tag index of the `wvm` namespace, see [`RpcModuleBuilder::with_tag_index`].
+    tag_index: Option<Arc<dyn TagIndex>>,
 }
 
 // === impl RpcRegistryInner ===
@@ -962,6 +1180,9 @@ where
             blocking_pool_guard,
             events,
             block_executor,
+            load_db: None,
+            archive_gateway: None,
+            tag_index: None,
         }
     }
 }
@@ -1158,6 +1379,23 @@ where
         self
     }
 
+    /// Register WVM namespace, if a LoadDb connection was configured
+    ///
+    /// See also [`Self::eth_api`]
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime.
+    pub fn register_wvm(&mut self) -> &mut Self
+    where
+        EthApi: EthFees + EthTransactions,
+    {
+        if let Some(wvm_api) = self.wvm_api() {
+            self.modules.insert(RethRpcModule::Wvm, WvmApiServer::into_rpc(wvm_api).into());
+        }
+        self
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
@@ -1167,6 +1405,22 @@ where
         let eth_api = self.eth_api().clone();
         OtterscanApi::new(eth_api)
     }
+
+    /// Instantiates `WvmApi`, backed by the node's LoadDb connection. `None` if no connection
+    /// was configured, see [`RpcModuleBuilder::with_load_db`]
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn wvm_api(&self) -> Option<WvmApi<EthApi>> {
+        let load_db = self.load_db.clone()?;
+        Some(WvmApi::new(
+            self.eth_api().clone(),
+            load_db,
+            self.archive_gateway.clone(),
+            self.tag_index.clone(),
+        ))
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
@@ -1378,6 +1632,17 @@ where
                                     .into_rpc(),
                                 )
                                 .expect("No conflicts");
+                            // WVM: deprecated `eth_` aliases of the `wvm_` methods
+                            if let Some(load_db) = self.load_db.clone() {
+                                module
+                                    .merge(WvmLegacyEthApiServer::into_rpc(WvmApi::new(
+                                        eth_api.clone(),
+                                        load_db,
+                                        self.archive_gateway.clone(),
+                                        self.tag_index.clone(),
+                                    )))
+                                    .expect("No conflicts");
+                            }
 
                             module.into()
                         }
@@ -1412,6 +1677,17 @@ where
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Wvm => match self.load_db.clone() {
+                            Some(load_db) => WvmApiServer::into_rpc(WvmApi::new(
+                                eth_api.clone(),
+                                load_db,
+                                self.archive_gateway.clone(),
+                                self.tag_index.clone(),
+                            ))
+                            .into(),
+                            // WVM: not served without a LoadDb connection
+                            None => Methods::new(),
+                        },
                         RethRpcModule::Flashbots => ValidationApi::new(
                             self.provider.clone(),
                             Arc::new(self.consensus.clone()),
@@ -2362,6 +2638,7 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "wvm" => RethRpcModule::Wvm,
             );
     }
 
@@ -0,0 +1,73 @@
+//! WVM namespace tests
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+    core::{client::ClientT, params::ArrayParams},
+    http_client::HttpClient,
+    rpc_params,
+    types::error::ErrorCode,
+};
+use load_db::{drivers::sqlite::SqliteDriver, LoadDbConnection};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig};
+use reth_rpc_server_types::RethRpcModule;
+use serde_json::{json, Value};
+
+use crate::utils::{test_address, test_rpc_builder};
+
+async fn launch_http_with_load_db(
+    load_db: Option<Arc<dyn LoadDbConnection + Send + Sync>>,
+) -> RpcServerHandle {
+    let mut builder = test_rpc_builder();
+    if let Some(load_db) = load_db {
+        builder = builder.with_load_db(load_db);
+    }
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Eth, RethRpcModule::Wvm]),
+        Box::new(EthApi::with_spawner),
+    );
+    RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&server)
+        .await
+        .unwrap()
+}
+
+fn tags_query() -> ArrayParams {
+    rpc_params![json!({ "tags": [["app", "wvm"]] })]
+}
+
+async fn is_method_not_found(client: &HttpClient, method: &str) -> bool {
+    match client.request::<Value, _>(method, tags_query()).await {
+        Err(jsonrpsee::core::client::Error::Call(err)) => {
+            err.code() == ErrorCode::MethodNotFound.code()
+        }
+        _ => false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wvm_methods_and_eth_aliases_served_with_load_db() {
+    let load_db = SqliteDriver::open_in_memory().unwrap();
+    load_db.migrate().await.unwrap();
+    let handle = launch_http_with_load_db(Some(Arc::new(load_db))).await;
+    let client = handle.http_client().unwrap();
+
+    for method in ["wvm_getTransactionsByTags", "eth_getWvmTransactionsByTags"] {
+        let page = client.request::<Value, _>(method, tags_query()).await.unwrap();
+        assert_eq!(page["transactions"], json!([]), "{method}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wvm_methods_and_eth_aliases_not_served_without_load_db() {
+    let handle = launch_http_with_load_db(None).await;
+    let client = handle.http_client().unwrap();
+
+    for method in ["wvm_getTransactionsByTags", "eth_getWvmTransactionsByTags"] {
+        assert!(is_method_not_found(&client, method).await, "{method}");
+    }
+    // the rest of the eth namespace is still served
+    client.request::<Value, _>("eth_chainId", rpc_params![]).await.unwrap();
+}
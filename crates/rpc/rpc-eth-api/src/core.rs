@@ -17,7 +17,6 @@ use alloy_rpc_types::{
 };
 use alloy_rpc_types_eth::transaction::TransactionRequest;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_rpc_eth_types::wvm::{GetWvmTransactionByTagRequest, WvmTransactionRequest};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
@@ -331,23 +330,6 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject> {
     #[method(name = "sendTransaction")]
     async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<B256>;
 
-    /// Sends WVM transaction; will block waiting for signer to return the
-    /// transaction hash.
-    #[method(name = "sendWvmTransaction")]
-    async fn send_wvm_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256>;
-
-    /// Sends WVM transaction; will block waiting for signer to return the
-    /// transaction hash.
-    #[method(name = "getWvmTransactionByTag")]
-    async fn get_wvm_transaction_by_tag(
-        &self,
-        request: GetWvmTransactionByTagRequest,
-    ) -> RpcResult<Option<Bytes>>;
-
-    /// Gets the arweave transaction id that prooves the permanency of a given block
-    #[method(name = "getArweaveStorageProof")]
-    async fn get_arweave_storage_proof(&self, block_height: String) -> RpcResult<String>;
-
     /// Sends signed transaction, returning its hash.
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
@@ -783,28 +765,6 @@ where
         Ok(EthTransactions::send_transaction(self, request).await?)
     }
 
-    /// Handler for: `eth_sendWvmTransaction`
-    async fn send_wvm_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256> {
-        trace!(target: "rpc::eth", ?request, "Serving eth_sendWvmTransaction");
-        Ok(EthTransactions::send_wvm_transaction(self, request).await?)
-    }
-
-    /// Handler for: `eth_getWvmTransactionByTag`
-    /// Returns the information about a wvm transaction requested by wvm transaction tag.
-    async fn get_wvm_transaction_by_tag(
-        &self,
-        request: GetWvmTransactionByTagRequest,
-    ) -> RpcResult<Option<Bytes>> {
-        trace!(target: "rpc::eth", ?request, "Serving eth_getWvmTransactionByTag");
-        Ok(EthTransactions::get_wvm_transaction_by_tag(self, request).await?)
-    }
-
-    /// Handler for: `eth_getArweaveStorageProof`
-    async fn get_arweave_storage_proof(&self, block_height: String) -> RpcResult<String> {
-        trace!(target: "rpc::eth", ?block_height, "Serving eth_getArweaveStorageProof");
-        Ok(EthTransactions::get_arweave_storage_proof(self, block_height).await?)
-    }
-
     /// Handler for: `eth_sendRawTransaction`
     async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256> {
         trace!(target: "rpc::eth", ?tx, "Serving eth_sendRawTransaction");
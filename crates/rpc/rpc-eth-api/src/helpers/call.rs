@@ -136,7 +136,9 @@ pub trait EthCall: Call + LoadPendingBlock {
                                 .next_block_base_fee(base_fee_params)
                                 .unwrap_or_default()
                         };
-                        block_env.basefee = U256::from(base_fee);
+                        let base_fee_floor =
+                            chain_spec.base_fee_floor_at_block(block_env.number.to());
+                        block_env.basefee = U256::from(base_fee.max(base_fee_floor));
                     } else {
                         block_env.basefee = U256::ZERO;
                     }
@@ -764,15 +766,20 @@ pub trait Call: LoadState<Evm: ConfigureEvm<Header = Header>> + SpawnBlocking {
                     let no_code_callee = code.map(|code| code.is_empty()).unwrap_or(true);
                     if no_code_callee {
                         // If the tx is a simple transfer (call to an account with no code) we can
-                        // shortcircuit. But simply returning
-                        // `MIN_TRANSACTION_GAS` is dangerous because there might be additional
-                        // field combos that bump the price up, so we try executing the function
-                        // with the minimum gas limit to make sure.
+                        // shortcircuit. But simply returning the minimum transaction gas of the
+                        // chain spec is dangerous because there might be additional field combos
+                        // that bump the price up, so we try executing the function with the
+                        // minimum gas limit to make sure.
+                        let min_transaction_gas = self
+                            .provider()
+                            .chain_spec()
+                            .min_transaction_gas_at_block(env.block.number.saturating_to())
+                            .unwrap_or(MIN_TRANSACTION_GAS);
                         let mut env = env.clone();
-                        env.tx.gas_limit = MIN_TRANSACTION_GAS;
+                        env.tx.gas_limit = min_transaction_gas;
                         if let Ok((res, _)) = self.transact(&mut db, env) {
                             if res.result.is_success() {
-                                return Ok(U256::from(MIN_TRANSACTION_GAS))
+                                return Ok(U256::from(min_transaction_gas))
                             }
                         }
                     }
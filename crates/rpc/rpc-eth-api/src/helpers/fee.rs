@@ -6,8 +6,9 @@ use futures::Future;
 use reth_chainspec::EthChainSpec;
 use reth_provider::{BlockIdReader, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_types::{
-    fee_history::calculate_reward_percentiles_for_block, EthApiError, FeeHistoryCache,
-    FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
+    fee_history::calculate_reward_percentiles_for_block,
+    wvm::{WvmFeeFloorChange, WvmFeeInfo},
+    EthApiError, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
 };
 use tracing::debug;
 
@@ -121,6 +122,10 @@ pub trait EthFees: LoadFee {
 
             let mut rewards: Vec<Vec<u128>> = Vec::new();
 
+            // WVM: the base fee of the next block can't drop below its floor
+            let base_fee_floor =
+                self.provider().chain_spec().base_fee_floor_at_block(end_block_plus);
+
             // Check if the requested range is within the cache bounds
             let fee_entries = self.fee_history_cache().get_history(start_block, end_block).await;
 
@@ -147,8 +152,9 @@ pub trait EthFees: LoadFee {
 
                 // Also need to include the `base_fee_per_gas` and `base_fee_per_blob_gas` for the
                 // next block
-                base_fee_per_gas
-                    .push(last_entry.next_block_base_fee(self.provider().chain_spec()) as u128);
+                let next_block_base_fee =
+                    last_entry.next_block_base_fee(self.provider().chain_spec());
+                base_fee_per_gas.push(next_block_base_fee.max(base_fee_floor) as u128);
 
                 base_fee_per_blob_gas.push(last_entry.next_block_blob_fee().unwrap_or_default());
             } else {
@@ -204,7 +210,8 @@ pub trait EthFees: LoadFee {
                             last_header.gas_used ,
                             last_header.gas_limit,
                             last_header.base_fee_per_gas.unwrap_or_default() ,
-                        ) as u128,
+                        )
+                        .max(base_fee_floor) as u128,
                 );
 
                 // Same goes for the `base_fee_per_blob_gas`:
@@ -223,6 +230,56 @@ pub trait EthFees: LoadFee {
         }
     }
 
+    /// Returns the base fee floor of the next block, when it took effect and its next scheduled
+    /// change.
+    fn wvm_fee_info(&self) -> impl Future<Output = Result<WvmFeeInfo, Self::Error>> + Send {
+        async move {
+            let latest = self
+                .provider()
+                .block_number_for_id(BlockNumberOrTag::Latest.into())
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+            let latest = self
+                .provider()
+                .header_by_number(latest)
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(latest.into()))?;
+            let chain_spec = self.provider().chain_spec();
+
+            let block_number = latest.number + 1;
+            let base_fee_floor = chain_spec.base_fee_floor_at_block(block_number);
+            let base_fee = latest
+                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(latest.timestamp))
+                .unwrap_or_default()
+                .max(base_fee_floor);
+
+            let schedule = chain_spec.base_fee_floor_schedule();
+            let floor_updated_at_block =
+                schedule.and_then(|schedule| schedule.scheduled_at(block_number)).map(|s| s.block);
+            let floor_updated_at = match floor_updated_at_block {
+                Some(block) => self
+                    .provider()
+                    .header_by_number(block)
+                    .map_err(Self::Error::from_eth_err)?
+                    .map(|header| header.timestamp),
+                None => None,
+            };
+            let next_change =
+                schedule.and_then(|schedule| schedule.next_after(block_number)).map(|next| {
+                    WvmFeeFloorChange { block_number: next.block, base_fee_floor: next.value }
+                });
+
+            Ok(WvmFeeInfo {
+                block_number,
+                base_fee_floor,
+                base_fee,
+                floor_updated_at_block,
+                floor_updated_at,
+                next_change,
+            })
+        }
+    }
+
     /// Approximates reward at a given percentile for a specific block
     /// Based on the configured resolution
     fn approximate_percentile(&self, entry: &FeeHistoryEntry, requested_percentile: f64) -> u128 {
@@ -324,8 +381,11 @@ pub trait LoadFee: LoadBlock {
         let suggested_tip = self.suggested_priority_fee();
         async move {
             let (header, suggested_tip) = futures::try_join!(header, suggested_tip)?;
-            let base_fee = header.and_then(|h| h.base_fee_per_gas).unwrap_or_default();
-            Ok(suggested_tip + U256::from(base_fee))
+            let base_fee = header.as_ref().and_then(|h| h.base_fee_per_gas).unwrap_or_default();
+            // WVM: transactions can't be included below the base fee floor of the next block
+            let base_fee_floor = header
+                .map_or(0, |h| self.provider().chain_spec().base_fee_floor_at_block(h.number + 1));
+            Ok(suggested_tip + U256::from(base_fee.max(base_fee_floor)))
         }
     }
 
@@ -85,9 +85,13 @@ pub trait LoadPendingBlock:
             // base fee of the child block
             let chain_spec = self.provider().chain_spec();
 
-            latest_header.base_fee_per_gas = latest_header.next_block_base_fee(
-                chain_spec.base_fee_params_at_timestamp(latest_header.timestamp),
-            );
+            latest_header.base_fee_per_gas = latest_header
+                .next_block_base_fee(
+                    chain_spec.base_fee_params_at_timestamp(latest_header.timestamp),
+                )
+                .map(|base_fee| {
+                    base_fee.max(chain_spec.base_fee_floor_at_block(latest_header.number))
+                });
 
             // update excess blob gas consumed above target
             latest_header.excess_blob_gas = latest_header.next_block_excess_blob_gas();
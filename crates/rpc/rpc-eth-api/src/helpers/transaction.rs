@@ -12,28 +12,20 @@ use reth_primitives::{Receipt, SealedBlockWithSenders, TransactionMeta, Transact
 use reth_provider::{BlockNumReader, BlockReaderIdExt, ReceiptProvider, TransactionsProvider};
 use reth_rpc_eth_types::{
     utils::{binary_search, recover_raw_transaction},
-    wvm::{GetWvmTransactionByTagRequest, WvmTransactionRequest},
     EthApiError, SignError, TransactionSource,
 };
 use reth_rpc_types_compat::transaction::{from_recovered, from_recovered_with_block_context};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
-use serde::Serialize;
-use std::{
-    str::FromStr,
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
-};
+use std::sync::Arc;
 
 use crate::{
     FromEthApiError, FullEthApiTypes, IntoEthApiError, RpcNodeCore, RpcNodeCoreExt, RpcReceipt,
     RpcTransaction,
 };
-use wvm_static::PRECOMPILE_LOADDB_CLIENT;
 
 use super::{
     Call, EthApiSpec, EthSigner, LoadBlock, LoadPendingBlock, LoadReceipt, LoadState, SpawnBlocking,
 };
-use load_db::LoadDbConnection;
 
 /// Transaction related functions for the [`EthApiServer`](crate::EthApiServer) trait in
 /// the `eth_` namespace.
@@ -349,102 +341,6 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         }
     }
 
-    /// WVM Exclusive
-    /// Sends a transaction to the blockchain (raw)
-    /// And saves the transaction with tags in load db storage.
-    /// Tags will be then be used for easier indexing of the chain transaction itself
-    fn send_wvm_transaction(
-        &self,
-        request: WvmTransactionRequest,
-    ) -> impl Future<Output = Result<B256, Self::Error>> + Send
-    where
-        Self: EthApiSpec + LoadBlock + LoadPendingBlock + Call,
-    {
-        // WVM
-        let load_db_client = (&*PRECOMPILE_LOADDB_CLIENT).clone();
-
-        async move {
-            let tags = request.tags;
-
-            let hash = self.send_raw_transaction(request.tx).await?;
-            let created_at = {
-                let now = SystemTime::now();
-                let since_epoch = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
-                since_epoch.as_millis()
-            };
-
-            load_db_client
-                .save_tx_tag(hash.to_string(), tags.unwrap_or(vec![]), created_at)
-                .await
-                .map_err(|_| EthApiError::InternalEthError)?;
-
-            Ok(hash)
-        }
-    }
-
-    /// WVM Exclusive
-    /// Obtains the Arweave Hash of the transaction containing the WEVM Block
-    fn get_arweave_storage_proof(
-        &self,
-        block_height: String,
-    ) -> impl Future<Output = Result<String, EthApiError>> + Send
-    where
-        Self: EthApiSpec + LoadBlock + LoadPendingBlock + Call,
-    {
-        let load_db_client = (&*PRECOMPILE_LOADDB_CLIENT).clone();
-
-        async move {
-            let result_set = load_db_client.query_raw_state(block_height).await;
-
-            match result_set {
-                Some(result_set) => Ok(result_set.arweave_id),
-                None => Err(EthApiError::TransactionNotFound), // Result set is None
-            }
-        }
-    }
-
-    /// WVM Exclusive
-    /// Obtains transactions using tag
-    fn get_wvm_transaction_by_tag(
-        &self,
-        req: GetWvmTransactionByTagRequest,
-    ) -> impl Future<Output = Result<Option<Bytes>, Self::Error>> + Send
-    where
-        Self: EthApiSpec + LoadBlock + LoadPendingBlock + Call,
-    {
-        let load_db_client = (&*PRECOMPILE_LOADDB_CLIENT).clone();
-        async move {
-            let tag = match req.tag {
-                Some(tag) => tag,
-                None => {
-                    return Err(EthApiError::InvalidParams("empty tag in request".to_string())
-                        .into_eth_err())
-                }
-            };
-
-            tracing::debug!(first = tag.0, second = tag.1, "incoming tag");
-
-            let hash_str = load_db_client
-                .query_transaction_by_tags(tag)
-                .await
-                .ok_or_else(|| EthApiError::TransactionNotFound.into_eth_err())?;
-
-            let hash = B256::from_str(&hash_str).map_err(|_| {
-                EthApiError::InvalidParams("invalid hash format".to_string()).into_eth_err()
-            })?;
-
-            tracing::debug!(target = "wvm", "found hash of tx by tag! {tx_hash}", tx_hash = hash);
-
-            self.spawn_blocking_io(move |ref this| {
-                this.provider()
-                    .transaction_by_hash(hash)
-                    .map_err(Self::Error::from_eth_err)
-                    .map(|maybe_tx| maybe_tx.map(|tx| tx.encoded_2718().into()))
-            })
-            .await
-        }
-    }
-
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     fn send_transaction(
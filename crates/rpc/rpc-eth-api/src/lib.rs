@@ -19,6 +19,7 @@ pub mod helpers;
 pub mod node;
 pub mod pubsub;
 pub mod types;
+pub mod wvm;
 
 pub use reth_rpc_types_compat::TransactionCompat;
 
@@ -29,6 +30,7 @@ pub use helpers::error::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthAp
 pub use node::{RpcNodeCore, RpcNodeCoreExt};
 pub use pubsub::EthPubSubApiServer;
 pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcReceipt, RpcTransaction};
+pub use wvm::{WvmApiServer, WvmLegacyEthApiServer};
 
 #[cfg(feature = "client")]
 pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
@@ -36,3 +38,5 @@ pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
 pub use filter::EthFilterApiClient;
+#[cfg(feature = "client")]
+pub use wvm::{WvmApiClient, WvmLegacyEthApiClient};
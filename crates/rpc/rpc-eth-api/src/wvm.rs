@@ -0,0 +1,76 @@
+//! `wvm_` RPC API for WVM specific chain data.
+
+use alloy_primitives::{Bytes, B256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::wvm::{
+    ArweaveStorageProofOptions, GetWvmTransactionByTagRequest, GetWvmTransactionsByTagsRequest,
+    WvmArweaveStorageProof, WvmFeeInfo, WvmTransactionRequest, WvmTransactionsByTagsResponse,
+};
+
+/// WVM rpc interface.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "wvm"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "wvm"))]
+pub trait WvmApi {
+    /// Returns the base fee floor of the next block, when it took effect and its next scheduled
+    /// change.
+    #[method(name = "feeInfo")]
+    async fn fee_info(&self) -> RpcResult<WvmFeeInfo>;
+
+    /// Submits a raw transaction to the pool and indexes it with the given tags, returning its
+    /// hash.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256>;
+
+    /// Returns the encoded transaction indexed with the given tag.
+    #[method(name = "getTransactionByTag")]
+    async fn get_transaction_by_tag(
+        &self,
+        request: GetWvmTransactionByTagRequest,
+    ) -> RpcResult<Option<Bytes>>;
+
+    /// Returns a page of confirmed transactions matching all or any of the given tags.
+    #[method(name = "getTransactionsByTags")]
+    async fn get_transactions_by_tags(
+        &self,
+        request: GetWvmTransactionsByTagsRequest,
+    ) -> RpcResult<WvmTransactionsByTagsResponse>;
+
+    /// Returns the archive of the given block on Arweave and its confirmation, optionally checking
+    /// it decodes to the canonical block.
+    #[method(name = "getArweaveStorageProof")]
+    async fn get_arweave_storage_proof(
+        &self,
+        block_height: String,
+        options: Option<ArweaveStorageProofOptions>,
+    ) -> RpcResult<WvmArweaveStorageProof>;
+}
+
+/// Legacy `eth_` aliases of the [`WvmApi`] methods.
+///
+/// Deprecated, served by the `eth` module until clients moved to the `wvm` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait WvmLegacyEthApi {
+    /// Deprecated alias of `wvm_sendTransaction`.
+    #[method(name = "sendWvmTransaction")]
+    async fn send_wvm_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256>;
+
+    /// Deprecated alias of `wvm_getTransactionByTag`.
+    #[method(name = "getWvmTransactionByTag")]
+    async fn get_wvm_transaction_by_tag(
+        &self,
+        request: GetWvmTransactionByTagRequest,
+    ) -> RpcResult<Option<Bytes>>;
+
+    /// Deprecated alias of `wvm_getTransactionsByTags`.
+    #[method(name = "getWvmTransactionsByTags")]
+    async fn get_wvm_transactions_by_tags(
+        &self,
+        request: GetWvmTransactionsByTagsRequest,
+    ) -> RpcResult<WvmTransactionsByTagsResponse>;
+
+    /// Deprecated alias of `wvm_getArweaveStorageProof`, only returning the Arweave id of the
+    /// archive.
+    #[method(name = "getArweaveStorageProof")]
+    async fn get_arweave_storage_proof(&self, block_height: String) -> RpcResult<String>;
+}
@@ -643,6 +643,9 @@ pub enum RpcPoolError {
     /// When oversized data is encountered
     #[error("oversized data")]
     OversizedData,
+    /// When the transaction can never fit into a block
+    #[error("transaction size {0} exceeds the maximum block size {1}")]
+    ExceedsMaxBlockSize(usize, usize),
     /// When the max initcode size is exceeded
     #[error("max initcode size exceeded")]
     ExceedsMaxInitCodeSize,
@@ -706,6 +709,9 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
                 Self::Invalid(RpcInvalidTransactionError::GasTooLow)
             }
             InvalidPoolTransactionError::OversizedData(_, _) => Self::OversizedData,
+            InvalidPoolTransactionError::ExceedsMaxBlockSize(size, max_size) => {
+                Self::ExceedsMaxBlockSize(size, max_size)
+            }
             InvalidPoolTransactionError::Underpriced => Self::Underpriced,
             InvalidPoolTransactionError::Other(err) => Self::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => Self::Eip4844(err),
@@ -19,6 +19,7 @@ pub mod pending_block;
 pub mod receipt;
 pub mod revm_utils;
 pub mod simulate;
+pub mod tag_reconciler;
 pub mod transaction;
 pub mod utils;
 pub mod wvm;
@@ -0,0 +1,267 @@
+//! Keeps the pending tags of WVM transactions in line with their pool events.
+
+use alloy_primitives::TxHash;
+use futures::{Stream, StreamExt};
+use load_db::{query::TagIndex, LoadDbConnection};
+use reth_chain_state::CanonStateNotification;
+use reth_transaction_pool::TransactionEvent;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// Where the tags of a transaction wait until it's included.
+#[derive(Clone)]
+pub enum PendingTagStore {
+    /// `tags` table of LoadDb.
+    LoadDb(Arc<dyn LoadDbConnection + Send + Sync + 'static>),
+    /// Native tag index of the node, see `WVM_TAG_INDEX`.
+    Native(Arc<dyn TagIndex>),
+}
+
+impl fmt::Debug for PendingTagStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LoadDb(_) => f.write_str("PendingTagStore::LoadDb"),
+            Self::Native(_) => f.write_str("PendingTagStore::Native"),
+        }
+    }
+}
+
+impl PendingTagStore {
+    /// Saves the tags of a transaction before it's submitted to the pool. Returns whether they were
+    /// added by this call, only those may be discarded again if the submission fails.
+    pub async fn save(
+        &self,
+        tx_hash: TxHash,
+        sender: Option<String>,
+        tags: Vec<(String, String)>,
+    ) -> eyre::Result<bool> {
+        match self {
+            Self::LoadDb(db) => {
+                let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+                db.save_tx_tag(tx_hash.to_string(), sender, tags, created_at).await
+            }
+            // Queued to the tag index ExEx, which owns the writes to the node database.
+            Self::Native(index) => index.save_pending_tags(&tx_hash.to_string(), tags),
+        }
+    }
+
+    /// Records that the transaction was mined, its tags are confirmed once its block is archived.
+    pub async fn mined(&self, tx_hash: TxHash) -> eyre::Result<()> {
+        match self {
+            Self::LoadDb(db) => db.mark_tx_tag_mined(&tx_hash.to_string()).await,
+            // The tag index ExEx picks the tags up from the committed chain.
+            Self::Native(_) => Ok(()),
+        }
+    }
+
+    /// Records that the mined transactions were reorged out of the chain, their tags are pending
+    /// again. Returns the transactions which carried tags.
+    pub async fn reverted(&self, tx_hashes: &[TxHash]) -> eyre::Result<Vec<TxHash>> {
+        match self {
+            Self::LoadDb(db) => {
+                let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+                let tx_hashes = tx_hashes.iter().map(ToString::to_string).collect::<Vec<_>>();
+                let reverted = db.revert_mined_tx_tags(&tx_hashes, created_at).await?;
+                Ok(reverted.iter().filter_map(|tx_hash| tx_hash.parse().ok()).collect())
+            }
+            // The tag index ExEx reverts the tags of reorged blocks itself.
+            Self::Native(_) => Ok(vec![]),
+        }
+    }
+
+    /// Drops the tags of a transaction which won't be mined.
+    pub async fn discard(&self, tx_hash: TxHash) -> eyre::Result<()> {
+        match self {
+            Self::LoadDb(db) => db.delete_tx_tag(&tx_hash.to_string()).await,
+            Self::Native(index) => index.discard_pending_tags(&tx_hash.to_string()),
+        }
+    }
+}
+
+/// Follows the pool events of `tx_hash` until it's mined or dropped and updates its pending tags
+/// accordingly.
+///
+/// If the stream ends without a final event, e.g. on shutdown, the tags are left pending and
+/// eventually expired, see [`load_db::reconcile`].
+pub async fn reconcile_pending_tags<S>(store: PendingTagStore, tx_hash: TxHash, mut events: S)
+where
+    S: Stream<Item = TransactionEvent> + Unpin,
+{
+    while let Some(event) = events.next().await {
+        let result = match &event {
+            TransactionEvent::Mined(block_hash) => {
+                debug!(target: "rpc::eth", %tx_hash, %block_hash, "Tagged transaction mined");
+                store.mined(tx_hash).await
+            }
+            TransactionEvent::Replaced(_) |
+            TransactionEvent::Discarded |
+            TransactionEvent::Invalid => {
+                debug!(target: "rpc::eth", %tx_hash, ?event, "Tagged transaction dropped");
+                store.discard(tx_hash).await
+            }
+            TransactionEvent::Pending |
+            TransactionEvent::Queued |
+            TransactionEvent::Propagated(_) => continue,
+        };
+
+        if let Err(err) = result {
+            warn!(target: "rpc::eth", %tx_hash, %err, "Failed to reconcile pending tags");
+        }
+
+        // An invalid transaction is removed from the pool, no further event follows.
+        if event.is_final() || matches!(event, TransactionEvent::Invalid) {
+            break
+        }
+    }
+}
+
+/// Follows the canonical chain to keep the tags of reorged transactions up to date, runs until the
+/// node exits.
+///
+/// The pool events of a transaction end once it's mined, a reorg doesn't reach
+/// [`reconcile_pending_tags`] anymore. Mined tags of reorged transactions are put back to pending
+/// here and marked mined again if the transaction is included in a later block within `ttl`,
+/// otherwise they expire with the other pending tags.
+pub async fn reconcile_reorged_tags<S>(store: PendingTagStore, mut notifications: S, ttl: Duration)
+where
+    S: Stream<Item = CanonStateNotification> + Unpin,
+{
+    let mut reverted = HashMap::<TxHash, Instant>::new();
+
+    while let Some(notification) = notifications.next().await {
+        let committed = notification.committed();
+        let included = committed
+            .blocks_iter()
+            .flat_map(|block| block.body.transactions.iter().map(|tx| tx.hash()))
+            .collect::<Vec<_>>();
+
+        if let Some(old) = notification.reverted() {
+            let reorged = old
+                .blocks_iter()
+                .flat_map(|block| block.body.transactions.iter().map(|tx| tx.hash()))
+                .filter(|tx_hash| !included.contains(tx_hash))
+                .collect::<Vec<_>>();
+
+            // Most transactions carry no tags, only the tagged ones are tracked.
+            match store.reverted(&reorged).await {
+                Ok(tagged) => {
+                    for tx_hash in tagged {
+                        debug!(target: "rpc::eth", %tx_hash, "Tagged transaction reorged");
+                        reverted.insert(tx_hash, Instant::now());
+                    }
+                }
+                Err(err) => {
+                    warn!(target: "rpc::eth", %err, reorged = reorged.len(), "Failed to revert mined tags")
+                }
+            }
+        }
+
+        reverted.retain(|_, reverted_at| reverted_at.elapsed() < ttl);
+        for tx_hash in included {
+            if reverted.remove(&tx_hash).is_none() {
+                continue;
+            }
+
+            debug!(target: "rpc::eth", %tx_hash, "Reorged transaction mined again");
+            if let Err(err) = store.mined(tx_hash).await {
+                warn!(target: "rpc::eth", %tx_hash, %err, "Failed to reconcile pending tags");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, Signature, B256};
+    use load_db::{drivers::sqlite::SqliteDriver, query::TagQuery};
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use reth_primitives::{SealedBlockWithSenders, Transaction, TransactionSigned};
+
+    fn chain_with(transactions: &[TransactionSigned]) -> Arc<Chain> {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.transactions = transactions.to_vec();
+        block.senders = vec![Address::ZERO; transactions.len()];
+        Arc::new(Chain::from_block(block, ExecutionOutcome::default(), None))
+    }
+
+    #[tokio::test]
+    async fn reconcile_with_pool_events() {
+        let db = Arc::new(SqliteDriver::open_in_memory().unwrap());
+        db.migrate().await.unwrap();
+        let store = PendingTagStore::LoadDb(db.clone());
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+
+        let mined = TxHash::with_last_byte(1);
+        let replaced = TxHash::with_last_byte(2);
+        for tx_hash in [mined, replaced] {
+            store.save(tx_hash, None, vec![tag.clone()]).await.unwrap();
+        }
+
+        let events = futures::stream::iter([
+            TransactionEvent::Pending,
+            TransactionEvent::Mined(B256::ZERO),
+            TransactionEvent::Discarded,
+        ]);
+        reconcile_pending_tags(store.clone(), mined, events).await;
+
+        let events = futures::stream::iter([
+            TransactionEvent::Queued,
+            TransactionEvent::Replaced(TxHash::with_last_byte(3)),
+        ]);
+        reconcile_pending_tags(store, replaced, events).await;
+
+        // Only the mined transaction is left to confirm, and it doesn't expire.
+        assert_eq!(db.expire_tx_tags(i64::MAX as u128).await.unwrap(), 0);
+        db.save_hashes(&[mined.to_string(), replaced.to_string()], 1).await.unwrap();
+        let query = TagQuery { tags: vec![tag], ..Default::default() }.validate().unwrap();
+        let confirmed = db.query_transactions(&query).await.unwrap().transactions;
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(confirmed[0].hash, mined.to_string());
+    }
+
+    #[tokio::test]
+    async fn reconcile_with_reorgs() {
+        let db = Arc::new(SqliteDriver::open_in_memory().unwrap());
+        db.migrate().await.unwrap();
+        let store = PendingTagStore::LoadDb(db.clone());
+        let tag = ("App-Name".to_string(), "wvm".to_string());
+
+        let transactions = (0..3)
+            .map(|nonce| {
+                TransactionSigned::from_transaction_and_signature(
+                    Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+                    Signature::test_signature(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let [remined, kept, dropped] = [0, 1, 2].map(|i| transactions[i].hash());
+        for tx_hash in [remined, kept, dropped] {
+            store.save(tx_hash, None, vec![tag.clone()]).await.unwrap();
+            store.mined(tx_hash).await.unwrap();
+        }
+
+        let notifications = futures::stream::iter([
+            CanonStateNotification::Reorg {
+                old: chain_with(&transactions),
+                new: chain_with(&transactions[1..2]),
+            },
+            CanonStateNotification::Commit { new: chain_with(&transactions[..1]) },
+        ]);
+        reconcile_reorged_tags(store, notifications, Duration::from_secs(60)).await;
+
+        // Only the transaction which left the chain is pending again.
+        assert_eq!(db.expire_tx_tags(i64::MAX as u128).await.unwrap(), 1);
+        db.save_hashes(&[remined.to_string(), kept.to_string(), dropped.to_string()], 1)
+            .await
+            .unwrap();
+        let query = TagQuery { tags: vec![tag], ..Default::default() }.validate().unwrap();
+        assert_eq!(db.query_transactions(&query).await.unwrap().transactions.len(), 2);
+    }
+}
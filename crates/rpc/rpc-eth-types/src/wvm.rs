@@ -1,7 +1,14 @@
-use alloy_primitives::Bytes;
+use crate::{error::ToRpcError, EthApiError};
+use alloy_primitives::{Address, Bytes, B256};
 use jsonrpsee_core::Serialize;
+use jsonrpsee_types::ErrorObject;
+use load_db::query::TagMatch;
+use reth_rpc_server_types::result::rpc_err;
 use serde::Deserialize;
 
+/// Arweave blocks mined on top of an archive before it's considered final.
+pub const ARWEAVE_FINALITY_DEPTH: u64 = 15;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WvmTransactionRequest {
@@ -16,3 +23,205 @@ pub struct GetWvmTransactionByTagRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tag: Option<(String, String)>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWvmTransactionsByTagsRequest {
+    pub tags: Vec<(String, String)>,
+    /// `all` (default) or `any`.
+    #[serde(default, rename = "match")]
+    pub match_mode: TagMatch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    /// `nextCursor` of the previous page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WvmTaggedTransaction {
+    pub hash: B256,
+    pub block_number: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    pub tags: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WvmTransactionsByTagsResponse {
+    pub transactions: Vec<WvmTaggedTransaction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Base fee floor of the chain, returned by `wvm_feeInfo`.
+///
+/// Floors are scheduled in the chain spec, so unlike the price-fed floor they replace there is no
+/// source price: the floor only changes at the blocks of its schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WvmFeeInfo {
+    /// Block the fees apply to, the one after the latest block.
+    pub block_number: u64,
+    /// Minimum base fee of the block.
+    pub base_fee_floor: u64,
+    /// Base fee of the block, never below the floor.
+    pub base_fee: u64,
+    /// Block the floor took effect at, `None` while the chain has no floor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_updated_at_block: Option<u64>,
+    /// Timestamp of the block the floor took effect at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_updated_at: Option<u64>,
+    /// Next scheduled change of the floor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_change: Option<WvmFeeFloorChange>,
+}
+
+/// Scheduled change of the base fee floor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WvmFeeFloorChange {
+    /// First block of the new floor.
+    pub block_number: u64,
+    /// New minimum base fee.
+    pub base_fee_floor: u64,
+}
+
+/// Options of `wvm_getArweaveStorageProof`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArweaveStorageProofOptions {
+    /// Downloads the archive and checks it decodes to the canonical block.
+    #[serde(default)]
+    pub verify: bool,
+}
+
+/// Archive of a block on Arweave, returned by `wvm_getArweaveStorageProof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WvmArweaveStorageProof {
+    /// Id of the data item holding the block.
+    pub arweave_id: String,
+    /// Bundle the data item was posted in, `None` until the gateway indexes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    pub tags: Vec<(String, String)>,
+    /// Attestation uploaded along with the data item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_id: Option<String>,
+    pub block_number: u64,
+    /// Hash of the block as archived.
+    pub block_hash: B256,
+    pub status: ArweaveConfirmation,
+    /// Whether the archive decodes to the canonical block, only set when verification was
+    /// requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+}
+
+/// Confirmation of an archive on Arweave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ArweaveConfirmation {
+    /// Not mined in an Arweave block yet.
+    Pending,
+    /// Mined at Arweave height `height`, less than [`ARWEAVE_FINALITY_DEPTH`] blocks deep.
+    Posted { height: u64 },
+    /// Mined at Arweave height `height`, at least [`ARWEAVE_FINALITY_DEPTH`] blocks deep.
+    Finalized { height: u64 },
+}
+
+impl ArweaveConfirmation {
+    /// Confirmation of an archive mined at Arweave height `height`, given the current height of
+    /// the network.
+    pub const fn mined(height: u64, network_height: u64) -> Self {
+        if network_height.saturating_sub(height) + 1 >= ARWEAVE_FINALITY_DEPTH {
+            Self::Finalized { height }
+        } else {
+            Self::Posted { height }
+        }
+    }
+}
+
+/// Block not archived error code of `wvm_getArweaveStorageProof`.
+pub const NOT_ARCHIVED_CODE: i32 = -32001;
+/// Archive not verifiable error code of `wvm_getArweaveStorageProof`.
+pub const ARCHIVE_UNVERIFIED_CODE: i32 = -32002;
+
+/// Errors of `wvm_getArweaveStorageProof`.
+#[derive(Debug, thiserror::Error)]
+pub enum WvmStorageProofError {
+    /// LoadDb has no archive of the block.
+    #[error("block {0} is not archived")]
+    NotArchived(u64),
+    /// Verification was requested from a node without an Arweave gateway.
+    #[error("arweave gateway unavailable: {0}")]
+    GatewayUnavailable(String),
+    /// The archive could not be downloaded or decoded to check it.
+    #[error("archive could not be verified: {0}")]
+    VerificationFailed(String),
+}
+
+impl WvmStorageProofError {
+    const fn error_code(&self) -> i32 {
+        match self {
+            Self::NotArchived(_) => NOT_ARCHIVED_CODE,
+            Self::GatewayUnavailable(_) | Self::VerificationFailed(_) => ARCHIVE_UNVERIFIED_CODE,
+        }
+    }
+}
+
+impl ToRpcError for WvmStorageProofError {
+    fn to_rpc_error(&self) -> ErrorObject<'static> {
+        rpc_err(self.error_code(), self.to_string(), None)
+    }
+}
+
+impl From<WvmStorageProofError> for EthApiError {
+    fn from(err: WvmStorageProofError) -> Self {
+        Self::other(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arweave_confirmation() {
+        assert_eq!(ArweaveConfirmation::mined(90, 100), ArweaveConfirmation::Posted { height: 90 });
+        assert_eq!(
+            ArweaveConfirmation::mined(86, 100),
+            ArweaveConfirmation::Finalized { height: 86 }
+        );
+        // The gateway may index the item before it reports the new network height.
+        assert_eq!(
+            ArweaveConfirmation::mined(101, 100),
+            ArweaveConfirmation::Posted { height: 101 }
+        );
+    }
+
+    #[test]
+    fn serde_arweave_confirmation() {
+        let s = r#"{"state":"finalized","height":1500000}"#;
+        let confirmation: ArweaveConfirmation = serde_json::from_str(s).unwrap();
+        assert_eq!(confirmation, ArweaveConfirmation::Finalized { height: 1_500_000 });
+        assert_eq!(serde_json::to_string(&confirmation).unwrap(), s);
+        assert_eq!(
+            serde_json::to_string(&ArweaveConfirmation::Pending).unwrap(),
+            r#"{"state":"pending"}"#
+        );
+
+        let options: ArweaveStorageProofOptions = serde_json::from_str("{}").unwrap();
+        assert!(!options.verify);
+    }
+}
@@ -260,6 +260,8 @@ pub enum RethRpcModule {
     Ots,
     /// `flashbots_` module
     Flashbots,
+    /// `wvm_` module
+    Wvm,
 }
 
 // === impl RethRpcModule ===
@@ -309,6 +311,7 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "flashbots" => Self::Flashbots,
+            "wvm" => Self::Wvm,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
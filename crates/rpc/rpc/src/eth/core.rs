@@ -401,10 +401,12 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
 #[cfg(test)]
 mod tests {
     use alloy_eips::BlockNumberOrTag;
-    use alloy_primitives::{B256, U64};
+    use alloy_primitives::{B256, U256, U64};
     use alloy_rpc_types::FeeHistory;
     use jsonrpsee_types::error::INVALID_PARAMS_CODE;
-    use reth_chainspec::{BaseFeeParams, ChainSpec, EthChainSpec};
+    use reth_chainspec::{
+        BaseFeeParams, BlockSchedule, ChainSpec, ChainSpecBuilder, EthChainSpec, WvmChainParams,
+    };
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{Block, BlockBody, Header, TransactionSigned};
@@ -412,8 +414,9 @@ mod tests {
         test_utils::{MockEthProvider, NoopProvider},
         BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
     };
-    use reth_rpc_eth_api::EthApiServer;
+    use reth_rpc_eth_api::{helpers::EthFees, EthApiServer};
     use reth_rpc_eth_types::{
+        wvm::{WvmFeeFloorChange, WvmFeeInfo},
         EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{
@@ -422,6 +425,7 @@ mod tests {
     use reth_tasks::pool::BlockingTaskPool;
     use reth_testing_utils::{generators, generators::Rng};
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use std::sync::Arc;
 
     use crate::EthApi;
 
@@ -703,4 +707,56 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    /// Fee suggestions never go below the base fee floor of the next block
+    #[tokio::test]
+    async fn test_base_fee_floor() {
+        let block_count = 10;
+        let newest_block = 1337;
+        // above the random base fees of the mock blocks
+        let base_fee_floor = 1 << 40;
+
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                base_fee_floor: BlockSchedule::from([
+                    (newest_block + 1, base_fee_floor),
+                    (newest_block + 100, base_fee_floor * 2),
+                ]),
+                ..Default::default()
+            },
+            ..ChainSpecBuilder::mainnet().build()
+        };
+        let mock_provider =
+            MockEthProvider { chain_spec: Arc::new(chain_spec), ..Default::default() };
+        let (eth_api, base_fees_per_gas, _) =
+            prepare_eth_api(newest_block, None, block_count, mock_provider);
+
+        let fee_history =
+            eth_api.fee_history(U64::from(block_count), newest_block.into(), None).await.unwrap();
+        // only the base fee of the next block is floored
+        assert_eq!(
+            &fee_history.base_fee_per_gas[..block_count as usize],
+            &base_fees_per_gas[..block_count as usize]
+        );
+        assert_eq!(fee_history.base_fee_per_gas.last(), Some(&(base_fee_floor as u128)));
+
+        let gas_price = EthFees::gas_price(&eth_api).await.unwrap();
+        assert!(gas_price >= U256::from(base_fee_floor));
+
+        let fee_info = EthFees::wvm_fee_info(&eth_api).await.unwrap();
+        assert_eq!(
+            fee_info,
+            WvmFeeInfo {
+                block_number: newest_block + 1,
+                base_fee_floor,
+                base_fee: base_fee_floor,
+                floor_updated_at_block: Some(newest_block + 1),
+                floor_updated_at: None,
+                next_change: Some(WvmFeeFloorChange {
+                    block_number: newest_block + 100,
+                    base_fee_floor: base_fee_floor * 2,
+                }),
+            }
+        );
+    }
 }
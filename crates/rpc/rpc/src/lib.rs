@@ -44,6 +44,7 @@ mod trace;
 mod txpool;
 mod validation;
 mod web3;
+mod wvm;
 
 pub use admin::AdminApi;
 pub use debug::DebugApi;
@@ -57,3 +58,4 @@ pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
 pub use validation::{ValidationApi, ValidationApiConfig};
 pub use web3::Web3Api;
+pub use wvm::WvmApi;
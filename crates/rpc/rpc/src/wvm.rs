@@ -0,0 +1,801 @@
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, Bytes, B256};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use load_db::{
+    archive::{ArchiveGateway, ArchiveItem, DecodedArchive},
+    query::{TagCursor, TagIndex, TagQuery},
+    sql::is_valid_tag,
+    LoadDbConnection,
+};
+use parking_lot::Mutex;
+use reth_provider::{BlockHashReader, TransactionsProvider};
+use reth_rpc_eth_api::{
+    helpers::{EthFees, EthTransactions, SpawnBlocking},
+    FromEthApiError, IntoEthApiError, RpcNodeCore, WvmApiServer, WvmLegacyEthApiServer,
+};
+use reth_rpc_eth_types::{
+    tag_reconciler::{reconcile_pending_tags, PendingTagStore},
+    utils::recover_raw_transaction,
+    wvm::{
+        ArweaveConfirmation, ArweaveStorageProofOptions, GetWvmTransactionByTagRequest,
+        GetWvmTransactionsByTagsRequest, WvmArweaveStorageProof, WvmFeeInfo, WvmStorageProofError,
+        WvmTaggedTransaction, WvmTransactionRequest, WvmTransactionsByTagsResponse,
+    },
+    EthApiError,
+};
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{
+    error::{PoolError, PoolErrorKind},
+    TransactionPool,
+};
+use schnellru::{ByLength, LruMap};
+use std::{str::FromStr, sync::Arc};
+use tracing::{debug, trace, warn};
+
+/// Archives decoded by `wvm_getArweaveStorageProof` kept by [`WvmApi`], downloading and decoding
+/// one again is expensive.
+const DECODED_ARCHIVES_CACHE_SIZE: u32 = 1024;
+
+/// `wvm` API implementation.
+///
+/// This type provides the functionality for handling `wvm` related requests.
+pub struct WvmApi<Eth> {
+    /// Handle to the `eth` API, serving the chain data.
+    eth_api: Eth,
+    /// LoadDb, storing the tags of transactions and the archives of blocks.
+    load_db: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+    /// Arweave gateway describing the archives of blocks, storage proofs are only located with
+    /// LoadDb without one.
+    archive_gateway: Option<Arc<dyn ArchiveGateway>>,
+    /// Tag index kept by the node, tags are saved and queried in LoadDb without one.
+    tag_index: Option<Arc<dyn TagIndex>>,
+    /// Archives decoded to verify storage proofs, keyed by Arweave id.
+    decoded_archives: Arc<Mutex<LruMap<String, DecodedArchive>>>,
+}
+
+impl<Eth> WvmApi<Eth> {
+    /// Creates a new instance of `WvmApi`.
+    pub fn new(
+        eth_api: Eth,
+        load_db: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+        archive_gateway: Option<Arc<dyn ArchiveGateway>>,
+        tag_index: Option<Arc<dyn TagIndex>>,
+    ) -> Self {
+        Self {
+            eth_api,
+            load_db,
+            archive_gateway,
+            tag_index,
+            decoded_archives: Arc::new(Mutex::new(LruMap::new(ByLength::new(
+                DECODED_ARCHIVES_CACHE_SIZE,
+            )))),
+        }
+    }
+}
+
+impl<Eth> WvmApi<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Submits the transaction like `eth_sendRawTransaction` and saves its tags in LoadDb, or in
+    /// the node database when the native tag index is enabled.
+    ///
+    /// Tags are saved as pending before the transaction is submitted, and dropped again if the
+    /// pool rejects it. Once accepted, the pool events of the transaction keep them up to date, see
+    /// [`reconcile_pending_tags`].
+    async fn submit_tagged_transaction(
+        &self,
+        request: WvmTransactionRequest,
+    ) -> Result<B256, Eth::Error> {
+        let store = match self.tag_index.clone() {
+            Some(tag_index) => PendingTagStore::Native(tag_index),
+            None => PendingTagStore::LoadDb(self.load_db.clone()),
+        };
+
+        let tags = request.tags.unwrap_or_default();
+        if !tags.iter().all(is_valid_tag) {
+            return Err(EthApiError::InvalidParams("invalid tags".to_string()).into_eth_err())
+        }
+
+        let recovered = recover_raw_transaction(request.tx.clone())?;
+        let hash = *recovered.hash();
+        let sender = recovered.signer();
+
+        // Saving the tags again would reset those of the transaction already in the pool.
+        if self.eth_api.pool().contains(&hash) {
+            return Err(Eth::Error::from_eth_err(PoolError::new(
+                hash,
+                PoolErrorKind::AlreadyImported,
+            )))
+        }
+
+        // Pending tags have to be saved before the transaction can be included
+        let inserted = store.save(hash, Some(sender.to_string()), tags).await.map_err(|e| {
+            warn!(target: "rpc::wvm", %e, %hash, "Failed to save pending tags");
+            EthApiError::InternalEthError.into_eth_err()
+        })?;
+
+        if let Err(err) = self.eth_api.send_raw_transaction(request.tx).await {
+            // Tags saved by an earlier or concurrent submission of the transaction, e.g. one
+            // that's already mined, are left alone.
+            if inserted {
+                if let Err(e) = store.discard(hash).await {
+                    warn!(target: "rpc::wvm", %e, %hash, "Failed to discard pending tags");
+                }
+            }
+            return Err(err)
+        }
+
+        // Without events, e.g. if the transaction already left the pool, the tags are left pending
+        // and expire unless the transaction is mined.
+        let Some(events) = self.eth_api.pool().transaction_event_listener(hash) else {
+            debug!(target: "rpc::wvm", %hash, "No pool events for tagged transaction");
+            return Ok(hash)
+        };
+        self.eth_api.io_task_spawner().spawn(Box::pin(reconcile_pending_tags(store, hash, events)));
+
+        Ok(hash)
+    }
+
+    /// Returns the encoded transaction indexed with the tag of the request.
+    async fn transaction_by_tag(
+        &self,
+        req: GetWvmTransactionByTagRequest,
+    ) -> Result<Option<Bytes>, Eth::Error> {
+        let tag = match req.tag {
+            Some(tag) => tag,
+            None => {
+                return Err(
+                    EthApiError::InvalidParams("empty tag in request".to_string()).into_eth_err()
+                )
+            }
+        };
+
+        debug!(target: "rpc::wvm", first = tag.0, second = tag.1, "incoming tag");
+
+        let hash_str = match self.tag_index.clone() {
+            Some(tag_index) => {
+                let query = TagQuery { tags: vec![tag], limit: Some(1), ..Default::default() }
+                    .validate()
+                    .map_err(|e| EthApiError::InvalidParams(e.to_string()).into_eth_err())?;
+
+                self.eth_api
+                    .spawn_blocking_io(move |_| {
+                        tag_index.query_transactions(&query).map_err(|e| {
+                            warn!(target: "rpc::wvm", %e, "Failed to query transaction by tag");
+                            EthApiError::InternalEthError.into_eth_err()
+                        })
+                    })
+                    .await?
+                    .transactions
+                    .into_iter()
+                    .next()
+                    .map(|tx| tx.hash)
+            }
+            None => self.load_db.query_transaction_by_tags(tag).await.map_err(|e| {
+                warn!(target: "rpc::wvm", %e, "Failed to query transaction by tag");
+                EthApiError::InternalEthError.into_eth_err()
+            })?,
+        }
+        .ok_or_else(|| EthApiError::TransactionNotFound.into_eth_err())?;
+
+        let hash = B256::from_str(&hash_str).map_err(|_| {
+            EthApiError::InvalidParams("invalid hash format".to_string()).into_eth_err()
+        })?;
+
+        debug!(target: "rpc::wvm", tx_hash = %hash, "found hash of tx by tag");
+
+        self.eth_api
+            .spawn_blocking_io(move |this| {
+                this.provider()
+                    .transaction_by_hash(hash)
+                    .map_err(Eth::Error::from_eth_err)
+                    .map(|maybe_tx| maybe_tx.map(|tx| tx.encoded_2718().into()))
+            })
+            .await
+    }
+
+    /// Returns a page of confirmed transactions matching several tags, optionally restricted to a
+    /// block range and a sender.
+    async fn transactions_by_tags(
+        &self,
+        req: GetWvmTransactionsByTagsRequest,
+    ) -> Result<WvmTransactionsByTagsResponse, Eth::Error> {
+        let cursor = req
+            .cursor
+            .map(|cursor| cursor.parse::<TagCursor>())
+            .transpose()
+            .map_err(|e| EthApiError::InvalidParams(e.to_string()).into_eth_err())?;
+
+        let query = TagQuery {
+            tags: req.tags,
+            match_mode: req.match_mode,
+            from_block: req.from_block,
+            to_block: req.to_block,
+            sender: req.sender.map(|sender| sender.to_string()),
+            cursor,
+            limit: req.limit,
+        }
+        .validate()
+        .map_err(|e| EthApiError::InvalidParams(e.to_string()).into_eth_err())?;
+
+        let page = match self.tag_index.clone() {
+            Some(tag_index) => {
+                self.eth_api
+                    .spawn_blocking_io(move |_| Ok(tag_index.query_transactions(&query)))
+                    .await?
+            }
+            None => self.load_db.query_transactions(&query).await,
+        }
+        .map_err(|e| {
+            warn!(target: "rpc::wvm", %e, "Failed to query transactions by tags");
+            EthApiError::InternalEthError.into_eth_err()
+        })?;
+
+        let transactions = page
+            .transactions
+            .into_iter()
+            .map(|tx| {
+                Some(WvmTaggedTransaction {
+                    hash: B256::from_str(&tx.hash).ok()?,
+                    block_number: tx.block_number,
+                    sender: tx.sender.as_deref().map(Address::from_str).transpose().ok()?,
+                    tags: tx.tags,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| EthApiError::InternalEthError.into_eth_err())?;
+
+        Ok(WvmTransactionsByTagsResponse {
+            transactions,
+            next_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
+        })
+    }
+
+    /// Returns the Arweave id of the archive of the block.
+    async fn arweave_id(&self, block_height: String) -> Result<String, EthApiError> {
+        self.load_db
+            .query_raw_state(block_height)
+            .await
+            .map_err(|e| {
+                warn!(target: "rpc::wvm", %e, "Failed to look up archived block");
+                EthApiError::InternalEthError
+            })?
+            .map(|state| state.arweave_id)
+            .ok_or(EthApiError::TransactionNotFound)
+    }
+
+    /// Returns the archive of the block on Arweave, located with LoadDb and described by the
+    /// Arweave gateway.
+    ///
+    /// Failed gateway lookups leave the archive pending, only with `verify` is the gateway
+    /// required: the archive is then downloaded and checked to decode to the canonical block.
+    async fn arweave_storage_proof(
+        &self,
+        block_height: String,
+        options: ArweaveStorageProofOptions,
+    ) -> Result<WvmArweaveStorageProof, Eth::Error> {
+        let block_number = block_height.parse::<u64>().map_err(|_| {
+            EthApiError::InvalidParams("invalid block number".to_string()).into_eth_err()
+        })?;
+
+        let state = self
+            .load_db
+            .query_raw_state(block_number.to_string())
+            .await
+            .map_err(|e| {
+                warn!(target: "rpc::wvm", %e, block_number, "Failed to look up archived block");
+                EthApiError::InternalEthError.into_eth_err()
+            })?
+            .ok_or_else(|| {
+                Eth::Error::from_eth_err(WvmStorageProofError::NotArchived(block_number))
+            })?;
+        let block_hash = B256::from_str(&state.block_hash)
+            .map_err(|_| EthApiError::InternalEthError.into_eth_err())?;
+
+        let gateway = self.archive_gateway.clone();
+        if options.verify && gateway.is_none() {
+            return Err(Eth::Error::from_eth_err(WvmStorageProofError::GatewayUnavailable(
+                "no gateway configured".to_string(),
+            )))
+        }
+        let decoded_archives = self.decoded_archives.clone();
+        let arweave_id = state.arweave_id;
+
+        self.eth_api
+            .spawn_blocking_io(move |this| {
+                let (item, attestation_id, status) = match &gateway {
+                    Some(gateway) => describe_archive(gateway.as_ref(), &arweave_id),
+                    None => (None, None, ArweaveConfirmation::Pending),
+                };
+                let item = item.unwrap_or_else(|| ArchiveItem {
+                    arweave_id: arweave_id.clone(),
+                    ..Default::default()
+                });
+
+                let verified = match gateway.filter(|_| options.verify) {
+                    Some(gateway) => {
+                        let cached = decoded_archives.lock().get(&arweave_id).cloned();
+                        let decoded = match cached {
+                            Some(decoded) => decoded,
+                            None => {
+                                let decoded = gateway.decode(&item).map_err(|e| {
+                                    Eth::Error::from_eth_err(
+                                        WvmStorageProofError::VerificationFailed(e.to_string()),
+                                    )
+                                })?;
+                                decoded_archives.lock().insert(arweave_id.clone(), decoded.clone());
+                                decoded
+                            }
+                        };
+                        let canonical_hash = this
+                            .provider()
+                            .block_hash(block_number)
+                            .map_err(Eth::Error::from_eth_err)?;
+
+                        Some(
+                            decoded.block_number == block_number &&
+                                canonical_hash.is_some_and(|hash| {
+                                    B256::from_str(&decoded.block_hash).is_ok_and(|h| h == hash)
+                                }),
+                        )
+                    }
+                    None => None,
+                };
+
+                Ok(WvmArweaveStorageProof {
+                    arweave_id: item.arweave_id,
+                    bundle_id: item.bundle_id,
+                    tags: item.tags,
+                    attestation_id,
+                    block_number,
+                    block_hash,
+                    status,
+                    verified,
+                })
+            })
+            .await
+    }
+}
+
+/// Item, attestation and confirmation of the archive `arweave_id`, as far as the gateway could
+/// describe it.
+///
+/// Failed lookups are logged rather than failing the storage proof: an unknown item is left
+/// pending, and a mined one posted when the height of the network is unknown.
+fn describe_archive(
+    gateway: &dyn ArchiveGateway,
+    arweave_id: &str,
+) -> (Option<ArchiveItem>, Option<String>, ArweaveConfirmation) {
+    let item = gateway.item(arweave_id).unwrap_or_else(|e| {
+        warn!(target: "rpc::wvm", %e, %arweave_id, "Failed to look up archive item");
+        None
+    });
+    let attestation_id = gateway.attestation_id(arweave_id).unwrap_or_else(|e| {
+        warn!(target: "rpc::wvm", %e, %arweave_id, "Failed to look up archive attestation");
+        None
+    });
+    let status = match item.as_ref().and_then(|item| item.block_height) {
+        Some(height) => match gateway.network_height() {
+            Ok(network_height) => ArweaveConfirmation::mined(height, network_height),
+            Err(e) => {
+                warn!(target: "rpc::wvm", %e, %arweave_id, "Failed to look up Arweave height");
+                ArweaveConfirmation::Posted { height }
+            }
+        },
+        None => ArweaveConfirmation::Pending,
+    };
+
+    (item, attestation_id, status)
+}
+
+#[async_trait]
+impl<Eth> WvmApiServer for WvmApi<Eth>
+where
+    Eth: EthFees + EthTransactions + 'static,
+{
+    /// Handler for `wvm_feeInfo`
+    async fn fee_info(&self) -> RpcResult<WvmFeeInfo> {
+        trace!(target: "rpc::wvm", "Serving wvm_feeInfo");
+        EthFees::wvm_fee_info(&self.eth_api).await.map_err(Into::into)
+    }
+
+    /// Handler for `wvm_sendTransaction`
+    async fn send_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256> {
+        trace!(target: "rpc::wvm", ?request, "Serving wvm_sendTransaction");
+        self.submit_tagged_transaction(request).await.map_err(Into::into)
+    }
+
+    /// Handler for `wvm_getTransactionByTag`
+    async fn get_transaction_by_tag(
+        &self,
+        request: GetWvmTransactionByTagRequest,
+    ) -> RpcResult<Option<Bytes>> {
+        trace!(target: "rpc::wvm", ?request, "Serving wvm_getTransactionByTag");
+        self.transaction_by_tag(request).await.map_err(Into::into)
+    }
+
+    /// Handler for `wvm_getTransactionsByTags`
+    async fn get_transactions_by_tags(
+        &self,
+        request: GetWvmTransactionsByTagsRequest,
+    ) -> RpcResult<WvmTransactionsByTagsResponse> {
+        trace!(target: "rpc::wvm", ?request, "Serving wvm_getTransactionsByTags");
+        self.transactions_by_tags(request).await.map_err(Into::into)
+    }
+
+    /// Handler for `wvm_getArweaveStorageProof`
+    async fn get_arweave_storage_proof(
+        &self,
+        block_height: String,
+        options: Option<ArweaveStorageProofOptions>,
+    ) -> RpcResult<WvmArweaveStorageProof> {
+        trace!(target: "rpc::wvm", ?block_height, ?options, "Serving wvm_getArweaveStorageProof");
+        self.arweave_storage_proof(block_height, options.unwrap_or_default())
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl<Eth> WvmLegacyEthApiServer for WvmApi<Eth>
+where
+    Eth: EthFees + EthTransactions + 'static,
+{
+    /// Handler for `eth_sendWvmTransaction`
+    async fn send_wvm_transaction(&self, request: WvmTransactionRequest) -> RpcResult<B256> {
+        trace!(target: "rpc::wvm", ?request, "Serving deprecated eth_sendWvmTransaction");
+        WvmApiServer::send_transaction(self, request).await
+    }
+
+    /// Handler for `eth_getWvmTransactionByTag`
+    async fn get_wvm_transaction_by_tag(
+        &self,
+        request: GetWvmTransactionByTagRequest,
+    ) -> RpcResult<Option<Bytes>> {
+        trace!(target: "rpc::wvm", ?request, "Serving deprecated eth_getWvmTransactionByTag");
+        WvmApiServer::get_transaction_by_tag(self, request).await
+    }
+
+    /// Handler for `eth_getWvmTransactionsByTags`
+    async fn get_wvm_transactions_by_tags(
+        &self,
+        request: GetWvmTransactionsByTagsRequest,
+    ) -> RpcResult<WvmTransactionsByTagsResponse> {
+        trace!(target: "rpc::wvm", ?request, "Serving deprecated eth_getWvmTransactionsByTags");
+        WvmApiServer::get_transactions_by_tags(self, request).await
+    }
+
+    /// Handler for `eth_getArweaveStorageProof`
+    async fn get_arweave_storage_proof(&self, block_height: String) -> RpcResult<String> {
+        trace!(target: "rpc::wvm", ?block_height, "Serving deprecated eth_getArweaveStorageProof");
+        self.arweave_id(block_height).await.map_err(Into::into)
+    }
+}
+
+impl<Eth> std::fmt::Debug for WvmApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WvmApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use jsonrpsee::types::error::INTERNAL_ERROR_CODE;
+    use load_db::{
+        drivers::sqlite::SqliteDriver,
+        query::{TagQueryPage, TaggedTransaction},
+        ArchivedBlock,
+    };
+    use reth_chainspec::EthChainSpec;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Block, Header};
+    use reth_provider::{test_utils::MockEthProvider, ChainSpecProvider};
+    use reth_rpc_eth_types::{
+        wvm::{ARCHIVE_UNVERIFIED_CODE, NOT_ARCHIVED_CODE},
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const ARWEAVE_ID: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8";
+    const NETWORK_HEIGHT: u64 = 1_500_000;
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+    #[derive(Debug, Default)]
+    struct MockGateway {
+        /// Indexed item, `None` while it's pending.
+        item: Option<ArchiveItem>,
+        /// Block the archive decodes to, `None` if it can't be decoded.
+        decoded: Option<DecodedArchive>,
+        /// Fails every lookup.
+        unavailable: bool,
+        decodes: AtomicUsize,
+    }
+
+    impl MockGateway {
+        fn mined(height: u64) -> Self {
+            Self {
+                item: Some(ArchiveItem {
+                    arweave_id: ARWEAVE_ID.to_string(),
+                    bundle_id: Some("bundle".to_string()),
+                    tags: vec![("Block-Number".to_string(), "1".to_string())],
+                    block_height: Some(height),
+                }),
+                ..Default::default()
+            }
+        }
+
+        fn decoding_to(block_number: u64, block_hash: B256) -> Self {
+            Self {
+                decoded: Some(DecodedArchive { block_number, block_hash: block_hash.to_string() }),
+                ..Default::default()
+            }
+        }
+
+        fn lookup(&self) -> eyre::Result<()> {
+            if self.unavailable {
+                eyre::bail!("gateway unavailable")
+            }
+            Ok(())
+        }
+    }
+
+    impl ArchiveGateway for MockGateway {
+        fn item(&self, _arweave_id: &str) -> eyre::Result<Option<ArchiveItem>> {
+            self.lookup()?;
+            Ok(self.item.clone())
+        }
+
+        fn attestation_id(&self, _arweave_id: &str) -> eyre::Result<Option<String>> {
+            self.lookup()?;
+            Ok(self.item.as_ref().map(|_| "attestation".to_string()))
+        }
+
+        fn network_height(&self) -> eyre::Result<u64> {
+            self.lookup()?;
+            Ok(NETWORK_HEIGHT)
+        }
+
+        fn decode(&self, _item: &ArchiveItem) -> eyre::Result<DecodedArchive> {
+            self.decodes.fetch_add(1, Ordering::Relaxed);
+            self.decoded.clone().ok_or_else(|| eyre::eyre!("archive could not be decoded"))
+        }
+    }
+
+    /// Tag index holding a single transaction, tagged `app=wvm`.
+    #[derive(Debug)]
+    struct MockTagIndex;
+
+    impl TagIndex for MockTagIndex {
+        fn save_pending_tags(
+            &self,
+            _tx_hash: &str,
+            _tags: Vec<(String, String)>,
+        ) -> eyre::Result<bool> {
+            Ok(true)
+        }
+
+        fn discard_pending_tags(&self, _tx_hash: &str) -> eyre::Result<()> {
+            Ok(())
+        }
+
+        fn query_transactions(&self, _query: &TagQuery) -> eyre::Result<TagQueryPage> {
+            Ok(TagQueryPage {
+                transactions: vec![TaggedTransaction {
+                    hash: B256::with_last_byte(1).to_string(),
+                    block_number: 1,
+                    sender: None,
+                    tags: vec![("app".to_string(), "wvm".to_string())],
+                }],
+                next_cursor: None,
+            })
+        }
+    }
+
+    /// Block 1 of the chain, archived as [`ARWEAVE_ID`].
+    fn block() -> (B256, Block) {
+        let block =
+            Block { header: Header { number: 1, ..Default::default() }, ..Default::default() };
+        (block.header.hash_slow(), block)
+    }
+
+    async fn wvm_api(gateway: Option<Arc<MockGateway>>) -> WvmApi<TestEthApi> {
+        let provider = MockEthProvider::default();
+        let (hash, block) = block();
+        provider.add_block(hash, block);
+
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+            provider.chain_spec().max_gas_limit(),
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+
+        let load_db = SqliteDriver::open_in_memory().unwrap();
+        load_db.migrate().await.unwrap();
+        load_db
+            .save_archived_blocks(&[ArchivedBlock {
+                block_number: 1,
+                arweave_id: ARWEAVE_ID.to_string(),
+                block_hash: hash.to_string(),
+                tx_hashes: vec![],
+            }])
+            .await
+            .unwrap();
+
+        WvmApi::new(
+            eth_api,
+            Arc::new(load_db),
+            gateway.map(|gateway| gateway as Arc<dyn ArchiveGateway>),
+            None,
+        )
+    }
+
+    async fn storage_proof(
+        api: &WvmApi<TestEthApi>,
+        block_height: &str,
+        verify: bool,
+    ) -> RpcResult<WvmArweaveStorageProof> {
+        WvmApiServer::get_arweave_storage_proof(
+            api,
+            block_height.to_string(),
+            Some(ArweaveStorageProofOptions { verify }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn storage_proof_confirmation() {
+        let (hash, _) = block();
+
+        let api = wvm_api(Some(Arc::new(MockGateway::default()))).await;
+        let proof = storage_proof(&api, "1", false).await.unwrap();
+        assert_eq!(
+            proof,
+            WvmArweaveStorageProof {
+                arweave_id: ARWEAVE_ID.to_string(),
+                bundle_id: None,
+                tags: vec![],
+                attestation_id: None,
+                block_number: 1,
+                block_hash: hash,
+                status: ArweaveConfirmation::Pending,
+                verified: None,
+            }
+        );
+
+        let api = wvm_api(Some(Arc::new(MockGateway::mined(NETWORK_HEIGHT - 5)))).await;
+        let proof = storage_proof(&api, "1", false).await.unwrap();
+        assert_eq!(proof.status, ArweaveConfirmation::Posted { height: NETWORK_HEIGHT - 5 });
+        assert_eq!(proof.bundle_id.as_deref(), Some("bundle"));
+        assert_eq!(proof.tags, vec![("Block-Number".to_string(), "1".to_string())]);
+        assert_eq!(proof.attestation_id.as_deref(), Some("attestation"));
+
+        let api = wvm_api(Some(Arc::new(MockGateway::mined(NETWORK_HEIGHT - 100)))).await;
+        let proof = storage_proof(&api, "1", false).await.unwrap();
+        assert_eq!(proof.status, ArweaveConfirmation::Finalized { height: NETWORK_HEIGHT - 100 });
+    }
+
+    #[tokio::test]
+    async fn storage_proof_not_archived() {
+        let api = wvm_api(Some(Arc::new(MockGateway::default()))).await;
+        let err = storage_proof(&api, "2", false).await.unwrap_err();
+        assert_eq!(err.code(), NOT_ARCHIVED_CODE);
+    }
+
+    #[tokio::test]
+    async fn storage_proof_without_gateway() {
+        let unavailable = MockGateway { unavailable: true, ..MockGateway::mined(NETWORK_HEIGHT) };
+        for gateway in [None, Some(Arc::new(unavailable))] {
+            let api = wvm_api(gateway).await;
+            let proof = storage_proof(&api, "1", false).await.unwrap();
+            assert_eq!(proof.arweave_id, ARWEAVE_ID);
+            assert_eq!(proof.status, ArweaveConfirmation::Pending);
+            assert_eq!(proof.attestation_id, None);
+        }
+
+        let api = wvm_api(None).await;
+        let err = storage_proof(&api, "1", true).await.unwrap_err();
+        assert_eq!(err.code(), ARCHIVE_UNVERIFIED_CODE);
+    }
+
+    #[tokio::test]
+    async fn storage_proof_verified() {
+        let (hash, _) = block();
+
+        let gateway = Arc::new(MockGateway::decoding_to(1, hash));
+        let api = wvm_api(Some(gateway.clone())).await;
+        for _ in 0..2 {
+            let proof = storage_proof(&api, "1", true).await.unwrap();
+            assert_eq!(proof.verified, Some(true));
+        }
+        // the decoded archive is cached
+        assert_eq!(gateway.decodes.load(Ordering::Relaxed), 1);
+
+        let api =
+            wvm_api(Some(Arc::new(MockGateway::decoding_to(1, B256::with_last_byte(1))))).await;
+        let proof = storage_proof(&api, "1", true).await.unwrap();
+        assert_eq!(proof.verified, Some(false));
+
+        let api = wvm_api(Some(Arc::new(MockGateway::decoding_to(2, hash)))).await;
+        let proof = storage_proof(&api, "1", true).await.unwrap();
+        assert_eq!(proof.verified, Some(false));
+
+        let api = wvm_api(Some(Arc::new(MockGateway::default()))).await;
+        let err = storage_proof(&api, "1", true).await.unwrap_err();
+        assert_eq!(err.code(), ARCHIVE_UNVERIFIED_CODE);
+    }
+
+    #[tokio::test]
+    async fn load_db_failures_are_internal_errors() {
+        // LoadDb without its tables fails every query
+        let mut api = wvm_api(None).await;
+        api.load_db = Arc::new(SqliteDriver::open_in_memory().unwrap());
+
+        let err = storage_proof(&api, "1", false).await.unwrap_err();
+        assert_eq!(err.code(), INTERNAL_ERROR_CODE);
+
+        let err = WvmLegacyEthApiServer::get_arweave_storage_proof(&api, "1".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), INTERNAL_ERROR_CODE);
+
+        let request = GetWvmTransactionByTagRequest {
+            tag: Some(("Content-Type".to_string(), "text/plain".to_string())),
+        };
+        let err = WvmApiServer::get_transaction_by_tag(&api, request).await.unwrap_err();
+        assert_eq!(err.code(), INTERNAL_ERROR_CODE);
+    }
+
+    #[tokio::test]
+    async fn deprecated_storage_proof() {
+        let api = wvm_api(Some(Arc::new(MockGateway::mined(NETWORK_HEIGHT)))).await;
+        let arweave_id =
+            WvmLegacyEthApiServer::get_arweave_storage_proof(&api, "1".to_string()).await.unwrap();
+        assert_eq!(arweave_id, ARWEAVE_ID);
+    }
+
+    #[tokio::test]
+    async fn tags_served_by_injected_index() {
+        let request = GetWvmTransactionsByTagsRequest {
+            tags: vec![("app".to_string(), "wvm".to_string())],
+            match_mode: Default::default(),
+            from_block: None,
+            to_block: None,
+            sender: None,
+            cursor: None,
+            limit: None,
+        };
+
+        // nothing is tagged in LoadDb
+        let api = wvm_api(None).await;
+        let page = WvmApiServer::get_transactions_by_tags(&api, request.clone()).await.unwrap();
+        assert!(page.transactions.is_empty());
+
+        let mut api = wvm_api(None).await;
+        api.tag_index = Some(Arc::new(MockTagIndex));
+        let page = WvmApiServer::get_transactions_by_tags(&api, request).await.unwrap();
+        assert_eq!(page.transactions.len(), 1);
+        assert_eq!(page.transactions[0].hash, B256::with_last_byte(1));
+    }
+}
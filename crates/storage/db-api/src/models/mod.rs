@@ -20,6 +20,7 @@ pub mod blocks;
 pub mod integer_list;
 pub mod sharded_key;
 pub mod storage_sharded_key;
+pub mod wvm;
 
 pub use accounts::*;
 pub use blocks::*;
@@ -27,6 +28,7 @@ pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
+pub use wvm::{wvm_tag_key, WvmPendingTagEntry, WvmTagEntry, WvmTagIndexKey, WvmTags, WvmTxKey};
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
 macro_rules! impl_uints {
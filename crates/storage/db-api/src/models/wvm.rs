@@ -0,0 +1,283 @@
+//! Models of the WVM transaction tag index.
+use crate::{
+    table::{Compress, Decode, Decompress, Encode},
+    DatabaseError,
+};
+use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, B256};
+use serde::{Deserialize, Serialize};
+
+/// The size of [`WvmTxKey`] encode bytes: 8-byte block number and 4-byte transaction index.
+const WVM_TX_KEY_BYTES_SIZE: usize = 8 + 4;
+
+/// The size of [`WvmTagIndexKey`] encode bytes: 32-byte tag key followed by a [`WvmTxKey`].
+const WVM_TAG_INDEX_KEY_BYTES_SIZE: usize = 32 + WVM_TX_KEY_BYTES_SIZE;
+
+/// Returns the key of a `(name, value)` tag in the tag index.
+///
+/// The name length is part of the preimage, so that `("ab", "c")` and `("a", "bc")` don't collide.
+pub fn wvm_tag_key(name: &str, value: &str) -> B256 {
+    let mut preimage = Vec::with_capacity(4 + name.len() + value.len());
+    preimage.extend_from_slice(&(name.len() as u32).to_be_bytes());
+    preimage.extend_from_slice(name.as_bytes());
+    preimage.extend_from_slice(value.as_bytes());
+    keccak256(preimage)
+}
+
+/// Tags sent along a WVM transaction, as `(name, value)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmTags(pub Vec<(String, String)>);
+
+impl WvmTags {
+    fn encode_to<B: bytes::BufMut>(&self, buf: &mut B) {
+        buf.put_u32(self.0.len() as u32);
+        for (name, value) in &self.0 {
+            buf.put_u32(name.len() as u32);
+            buf.put_slice(name.as_bytes());
+            buf.put_u32(value.len() as u32);
+            buf.put_slice(value.as_bytes());
+        }
+    }
+
+    fn decode_from(buf: &mut &[u8]) -> Result<Self, DatabaseError> {
+        let len = read_u32(buf)? as usize;
+        // Every tag takes at least 8 bytes, don't trust the length for the allocation.
+        let mut tags = Vec::with_capacity(len.min(buf.len() / 8));
+        for _ in 0..len {
+            tags.push((read_string(buf)?, read_string(buf)?));
+        }
+        Ok(Self(tags))
+    }
+}
+
+fn read_u32(buf: &mut &[u8]) -> Result<u32, DatabaseError> {
+    let (bytes, rest) = buf.split_first_chunk::<4>().ok_or(DatabaseError::Decode)?;
+    *buf = rest;
+    Ok(u32::from_be_bytes(*bytes))
+}
+
+fn read_string(buf: &mut &[u8]) -> Result<String, DatabaseError> {
+    let len = read_u32(buf)? as usize;
+    if buf.len() < len {
+        return Err(DatabaseError::Decode)
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    String::from_utf8(bytes.to_vec()).map_err(|_| DatabaseError::Decode)
+}
+
+impl Compress for WvmTags {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        self.encode_to(buf);
+    }
+}
+
+impl Decompress for WvmTags {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        let mut buf = value;
+        let tags = Self::decode_from(&mut buf)?;
+        if !buf.is_empty() {
+            return Err(DatabaseError::Decode)
+        }
+        Ok(tags)
+    }
+}
+
+/// Tags of a transaction waiting to be included.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmPendingTagEntry {
+    /// When the tags were received, in milliseconds since the unix epoch. Entries whose
+    /// transaction is never included are expired after a while.
+    pub created_at: u64,
+    /// Tags sent along the transaction.
+    pub tags: WvmTags,
+}
+
+impl Compress for WvmPendingTagEntry {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        buf.put_u64(self.created_at);
+        self.tags.encode_to(buf);
+    }
+}
+
+impl Decompress for WvmPendingTagEntry {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        let (created_at, tags) = value.split_first_chunk::<8>().ok_or(DatabaseError::Decode)?;
+
+        Ok(Self { created_at: u64::from_be_bytes(*created_at), tags: WvmTags::decompress(tags)? })
+    }
+}
+
+/// Tags of a transaction included in the canonical chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmTagEntry {
+    /// Hash of the transaction.
+    pub tx_hash: TxHash,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Tags sent along the transaction.
+    pub tags: WvmTags,
+}
+
+impl Compress for WvmTagEntry {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        buf.put_slice(self.tx_hash.as_slice());
+        buf.put_slice(self.sender.as_slice());
+        self.tags.encode_to(buf);
+    }
+}
+
+impl Decompress for WvmTagEntry {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() < 32 + 20 {
+            return Err(DatabaseError::Decode)
+        }
+        let tx_hash = TxHash::from_slice(&value[..32]);
+        let sender = Address::from_slice(&value[32..52]);
+        let tags = WvmTags::decompress(&value[52..])?;
+
+        Ok(Self { tx_hash, sender, tags })
+    }
+}
+
+/// Position of a transaction in the canonical chain: block number and index in the block.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct WvmTxKey {
+    /// Block the transaction is included in.
+    pub block_number: BlockNumber,
+    /// Index of the transaction in the block.
+    pub tx_index: u32,
+}
+
+impl WvmTxKey {
+    /// Creates a new `WvmTxKey`.
+    pub const fn new(block_number: BlockNumber, tx_index: u32) -> Self {
+        Self { block_number, tx_index }
+    }
+}
+
+impl Encode for WvmTxKey {
+    type Encoded = [u8; WVM_TX_KEY_BYTES_SIZE];
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; WVM_TX_KEY_BYTES_SIZE];
+        buf[..8].copy_from_slice(&self.block_number.to_be_bytes());
+        buf[8..].copy_from_slice(&self.tx_index.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for WvmTxKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != WVM_TX_KEY_BYTES_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+        let block_number =
+            u64::from_be_bytes(value[..8].try_into().map_err(|_| DatabaseError::Decode)?);
+        let tx_index =
+            u32::from_be_bytes(value[8..].try_into().map_err(|_| DatabaseError::Decode)?);
+
+        Ok(Self { block_number, tx_index })
+    }
+}
+
+/// Key of the tag index: the tag key, see [`wvm_tag_key`], followed by the position of a
+/// transaction carrying the tag. Walking a tag key yields its transactions in chain order.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct WvmTagIndexKey {
+    /// Key of the tag.
+    pub tag: B256,
+    /// Position of the transaction.
+    pub tx: WvmTxKey,
+}
+
+impl WvmTagIndexKey {
+    /// Creates a new `WvmTagIndexKey`.
+    pub const fn new(tag: B256, tx: WvmTxKey) -> Self {
+        Self { tag, tx }
+    }
+}
+
+impl Encode for WvmTagIndexKey {
+    type Encoded = [u8; WVM_TAG_INDEX_KEY_BYTES_SIZE];
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; WVM_TAG_INDEX_KEY_BYTES_SIZE];
+        buf[..32].copy_from_slice(self.tag.as_slice());
+        buf[32..].copy_from_slice(&self.tx.encode());
+        buf
+    }
+}
+
+impl Decode for WvmTagIndexKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != WVM_TAG_INDEX_KEY_BYTES_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+
+        Ok(Self { tag: B256::decode(&value[..32])?, tx: WvmTxKey::decode(&value[32..])? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wvm_tags_roundtrip() {
+        let tags = WvmTags(vec![
+            ("Content-Type".to_string(), "text/plain".to_string()),
+            ("App-Name".to_string(), String::new()),
+        ]);
+        let entry = WvmTagEntry {
+            tx_hash: TxHash::with_last_byte(1),
+            sender: Address::with_last_byte(2),
+            tags: tags.clone(),
+        };
+
+        let pending = WvmPendingTagEntry { created_at: 1_700_000_000_000, tags: tags.clone() };
+
+        assert_eq!(WvmTags::decompress(&tags.clone().compress()).unwrap(), tags);
+        assert_eq!(WvmTagEntry::decompress(&entry.clone().compress()).unwrap(), entry);
+        assert_eq!(WvmPendingTagEntry::decompress(&pending.clone().compress()).unwrap(), pending);
+        assert!(WvmPendingTagEntry::decompress(&[0; 7]).is_err());
+
+        let compressed = tags.compress();
+        assert!(WvmTags::decompress(&compressed[..compressed.len() - 1]).is_err());
+        assert!(WvmTags::decompress(&[0, 0, 0, 9]).is_err());
+    }
+
+    #[test]
+    fn test_wvm_keys_order() {
+        let tag = wvm_tag_key("App-Name", "wvm");
+        let keys = [
+            WvmTagIndexKey::new(tag, WvmTxKey::new(1, 2)),
+            WvmTagIndexKey::new(tag, WvmTxKey::new(2, 0)),
+            WvmTagIndexKey::new(tag, WvmTxKey::new(256, 1)),
+        ];
+
+        for pair in keys.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(pair[0].encode() < pair[1].encode());
+        }
+
+        for key in keys {
+            assert_eq!(WvmTagIndexKey::decode(&key.encode()).unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn test_wvm_tag_key() {
+        assert_ne!(wvm_tag_key("ab", "c"), wvm_tag_key("a", "bc"));
+        assert_eq!(wvm_tag_key("a", "b"), wvm_tag_key("a", "b"));
+    }
+}
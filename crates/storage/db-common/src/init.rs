@@ -6,7 +6,11 @@ use reth_chainspec::EthChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
-use reth_db_api::{transaction::DbTxMut, DatabaseError};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
 use reth_etl::Collector;
 use reth_primitives::{Account, Bytecode, GotExpected, Receipts, StaticFileSegment, StorageEntry};
 use reth_provider::{
@@ -22,7 +26,10 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
 use reth_trie_db::DatabaseStateRoot;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io::BufRead};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{BufRead, Write},
+};
 use tracing::{debug, error, info, trace};
 
 /// Default soft limit for number of bytes to read from state dump file, before inserting into
@@ -561,6 +568,61 @@ where
     }
 }
 
+/// Writes the plain state of the database as a state dump readable by
+/// [`init_from_state_dump`], with `state_root` as first line.
+///
+/// Returns the number of exported accounts.
+pub fn export_state_dump<TX: DbTx>(
+    tx: &mut TX,
+    state_root: B256,
+    mut writer: impl Write,
+) -> eyre::Result<usize> {
+    // The whole plain state is read from one transaction, which outlives the read timeout
+    tx.disable_long_read_transaction_safety();
+
+    serde_json::to_writer(&mut writer, &StateRoot { root: state_root })?;
+    writer.write_all(b"\n")?;
+
+    let mut accounts = tx.cursor_read::<tables::PlainAccountState>()?;
+    let mut storages = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut bytecodes = tx.cursor_read::<tables::Bytecodes>()?;
+    let mut total_exported_accounts = 0;
+
+    for entry in accounts.walk(None)? {
+        let (address, account) = entry?;
+
+        let code = match account.bytecode_hash {
+            Some(hash) => bytecodes.seek_exact(hash)?.map(|(_, code)| code.original_bytes()),
+            None => None,
+        };
+        let storage = storages
+            .walk_dup(Some(address), None)?
+            .map(|entry| entry.map(|(_, entry)| (entry.key, B256::from(entry.value))))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        let genesis_account = GenesisAccount {
+            nonce: Some(account.nonce),
+            balance: account.balance,
+            code,
+            storage: (!storage.is_empty()).then_some(storage),
+            private_key: None,
+        };
+        serde_json::to_writer(
+            &mut writer,
+            &GenesisAccountWithAddress { genesis_account, address },
+        )?;
+        writer.write_all(b"\n")?;
+
+        total_exported_accounts += 1;
+        if total_exported_accounts % AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP == 0 {
+            info!(target: "reth::cli", total_exported_accounts, "Exporting accounts");
+        }
+    }
+
+    writer.flush()?;
+    Ok(total_exported_accounts)
+}
+
 /// Type to deserialize state root from state dump file.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct StateRoot {
@@ -714,4 +776,51 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    fn export_genesis_state_dump() {
+        let address_with_code = Address::with_last_byte(1);
+        let address_with_storage = Address::with_last_byte(2);
+        let alloc = BTreeMap::from([
+            (
+                address_with_code,
+                GenesisAccount {
+                    nonce: Some(1),
+                    balance: U256::from(1),
+                    code: Some(alloy_primitives::Bytes::from_static(&[0x60, 0x00, 0x60, 0x00])),
+                    ..Default::default()
+                },
+            ),
+            (
+                address_with_storage,
+                GenesisAccount {
+                    nonce: Some(0),
+                    storage: Some(BTreeMap::from([(B256::with_last_byte(1), B256::random())])),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let chain_spec = Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc: alloc.clone(), ..Default::default() },
+            ..Default::default()
+        });
+
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(&factory).unwrap();
+        let mut provider = factory.provider().unwrap();
+        let state_root = provider.header_by_number(0).unwrap().unwrap().state_root;
+
+        let mut dump = Vec::new();
+        assert_eq!(export_state_dump(provider.tx_mut(), state_root, &mut dump).unwrap(), 2);
+
+        let mut reader = dump.as_slice();
+        assert_eq!(parse_state_root(&mut reader).unwrap(), state_root);
+        let exported = reader
+            .lines()
+            .map(|line| serde_json::from_str::<GenesisAccountWithAddress>(&line.unwrap()).unwrap())
+            .map(|account| (account.address, account.genesis_account))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(exported, alloc);
+    }
 }
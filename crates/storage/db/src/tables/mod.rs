@@ -26,7 +26,7 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, ShardedKey, StoredBlockBodyIndices,
-        StoredBlockWithdrawals,
+        StoredBlockWithdrawals, WvmPendingTagEntry, WvmTagEntry, WvmTagIndexKey, WvmTxKey,
     },
     table::{Decode, DupSort, Encode, Table},
 };
@@ -404,6 +404,15 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the tags of WVM transactions sent to this node and not included yet.
+    table WvmPendingTags<Key = TxHash, Value = WvmPendingTagEntry>;
+
+    /// Stores the tags of included WVM transactions by their position in the chain.
+    table WvmTxTags<Key = WvmTxKey, Value = WvmTagEntry>;
+
+    /// Stores the transactions carrying a tag, ordered by their position in the chain.
+    table WvmTagIndex<Key = WvmTagIndexKey, Value = TxHash>;
 }
 
 /// Keys for the `ChainState` table.
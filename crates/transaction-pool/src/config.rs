@@ -1,15 +1,20 @@
 use crate::{
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    validate::DEFAULT_MAX_TX_INPUT_BYTES,
     PoolSize, TransactionOrigin,
 };
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
 use alloy_primitives::Address;
 use reth_primitives::constants::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
-use std::{collections::HashSet, ops::Mul, sync::atomic::Ordering::SeqCst};
+use std::{collections::HashSet, ops::Mul};
 
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// The default maximum combined encoded size (in bytes) of the transactions of one sender.
+/// WVM: room for 4 transactions of [`DEFAULT_MAX_TX_INPUT_BYTES`]
+pub const TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER: usize = 4 * DEFAULT_MAX_TX_INPUT_BYTES;
+
 /// The default maximum allowed number of transactions in the given subpool.
 /// WVM: 1kk
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 1_000_000;
@@ -42,6 +47,8 @@ pub struct PoolConfig {
     pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max combined size (in bytes) of the transactions of a single account
+    pub max_account_bytes: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// Minimum base fee required by the protocol.
@@ -76,9 +83,10 @@ impl Default for PoolConfig {
             queued_limit: Default::default(),
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_account_bytes: TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER,
             price_bumps: Default::default(),
-            minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE.load(SeqCst),
-            gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+            minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             local_transactions_config: Default::default(),
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
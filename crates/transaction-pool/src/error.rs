@@ -203,6 +203,10 @@ pub enum InvalidPoolTransactionError {
     /// making the transaction invalid, rather a DOS protection.
     #[error("input data too large")]
     OversizedData(usize, usize),
+    /// Thrown if the encoded transaction is larger than the maximum block size, so it can never
+    /// be included in a block.
+    #[error("transaction's size {0} exceeds the maximum block size {1}")]
+    ExceedsMaxBlockSize(usize, usize),
     /// Thrown if the transaction's fee is below the minimum fee
     #[error("transaction underpriced")]
     Underpriced,
@@ -278,6 +282,7 @@ impl InvalidPoolTransactionError {
             Self::ExceedsGasLimit(_, _) => true,
             Self::ExceedsMaxInitCodeSize(_, _) => true,
             Self::OversizedData(_, _) => true,
+            Self::ExceedsMaxBlockSize(_, _) => true,
             Self::Underpriced => {
                 // local setting
                 false
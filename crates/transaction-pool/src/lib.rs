@@ -167,11 +167,11 @@ pub use crate::{
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
-        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{CalldataAwareOrdering, CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
         TransactionEvent, TransactionEvents,
@@ -207,6 +207,13 @@ pub type EthTransactionPool<Client, S> = Pool<
     S,
 >;
 
+/// WVM: Type alias for the ethereum transaction pool ordered by [`CalldataAwareOrdering`]
+pub type WvmTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<EthTransactionValidator<Client, EthPooledTransaction>>,
+    CalldataAwareOrdering<EthPooledTransaction>,
+    S,
+>;
+
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
 pub struct Pool<V, T: TransactionOrdering, S> {
@@ -320,6 +327,24 @@ where
     }
 }
 
+impl<Client, S> WvmTransactionPool<Client, S>
+where
+    Client: StateProviderFactory + Clone + 'static,
+    S: BlobStore,
+{
+    /// WVM: Returns a new [`Pool`] that uses the default [`TransactionValidationTaskExecutor`] when
+    /// validating [`EthPooledTransaction`]s and ords via [`CalldataAwareOrdering`]
+    pub fn wvm_pool(
+        validator: TransactionValidationTaskExecutor<
+            EthTransactionValidator<Client, EthPooledTransaction>,
+        >,
+        blob_store: S,
+        config: PoolConfig,
+    ) -> Self {
+        Self::new(validator, CalldataAwareOrdering::default(), blob_store, config)
+    }
+}
+
 /// implements the `TransactionPool` interface for various transaction pool API consumers.
 impl<V, T, S> TransactionPool for Pool<V, T, S>
 where
@@ -116,7 +116,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             last_seen_block_number: latest.number,
             pending_basefee: latest
                 .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(latest.timestamp + 12))
-                .unwrap_or_default(),
+                .unwrap_or_default()
+                .max(chain_spec.base_fee_floor_at_block(latest.number + 1)),
             pending_blob_fee: latest.next_block_blob_fee(),
         };
         pool.set_block_info(info);
@@ -273,7 +274,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     .next_block_base_fee(
                         chain_spec.base_fee_params_at_timestamp(new_tip.timestamp + 12),
                     )
-                    .unwrap_or_default();
+                    .unwrap_or_default()
+                    .max(chain_spec.base_fee_floor_at_block(new_tip.number + 1));
                 let pending_block_blob_fee = new_tip.next_block_blob_fee();
 
                 // we know all changed account in the new chain
@@ -379,7 +381,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     .next_block_base_fee(
                         chain_spec.base_fee_params_at_timestamp(tip.timestamp + 12),
                     )
-                    .unwrap_or_default();
+                    .unwrap_or_default()
+                    .max(chain_spec.base_fee_floor_at_block(tip.number + 1));
                 let pending_block_blob_fee = tip.next_block_blob_fee();
 
                 let first_block = blocks.first();
@@ -79,3 +79,73 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// WVM: ordering that accounts for the calldata footprint of a transaction.
+///
+/// The priority is the effective tip the transaction pays the coinbase (tip per gas times its gas
+/// limit) per byte of its encoded size, so block space is given to the transactions paying the
+/// most for the bytes they take.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CalldataAwareOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for CalldataAwareOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let size = transaction.encoded_length().max(1);
+        transaction
+            .effective_tip_per_gas(base_fee)
+            .map(|tip| U256::from(tip) * U256::from(transaction.gas_limit()) / U256::from(size))
+            .into()
+    }
+}
+
+impl<T> Default for CalldataAwareOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for CalldataAwareOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn calldata_aware_priority_per_byte() {
+        let ordering = CalldataAwareOrdering::<MockTransaction>::default();
+        let small = MockTransaction::eip1559()
+            .with_gas_limit(100_000)
+            .with_priority_fee(10)
+            .with_max_fee(100)
+            .with_size(200);
+        let large = small.clone().with_size(120 * 1024);
+
+        assert_eq!(ordering.priority(&small, 0), Priority::Value(U256::from(5_000)));
+        assert_eq!(ordering.priority(&large, 0), Priority::Value(U256::from(8)));
+        assert!(ordering.priority(&small, 0) > ordering.priority(&large, 0));
+
+        // same tip per byte ranks equally
+        let small = small.with_size(1_000);
+        let large = small.clone().with_size(4_000).with_priority_fee(40).with_max_fee(400);
+        assert_eq!(ordering.priority(&small, 0), ordering.priority(&large, 0));
+
+        // underpriced transactions have no priority
+        assert_eq!(ordering.priority(&small, 1_000), Priority::None);
+    }
+}
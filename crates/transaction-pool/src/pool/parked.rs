@@ -41,6 +41,8 @@ pub struct ParkedPool<T: ParkedOrd> {
     /// Keeps track of the number of transactions in the pool by the sender and the last submission
     /// id.
     sender_transaction_count: FxHashMap<SenderId, SenderTransactionCount>,
+    /// WVM: Keeps track of the combined size of the transactions in the pool by sender.
+    sender_size: FxHashMap<SenderId, usize>,
     /// Keeps track of the size of this pool.
     ///
     /// See also [`PoolTransaction::size`].
@@ -66,6 +68,7 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of += tx.size();
+        *self.sender_size.entry(tx.sender_id()).or_default() += tx.size();
 
         // update or create sender entry
         self.add_sender_count(tx.sender_id(), submission_id);
@@ -147,6 +150,12 @@ impl<T: ParkedOrd> ParkedPool<T> {
 
         // keep track of size
         self.size_of -= tx.transaction.size();
+        if let Entry::Occupied(mut entry) = self.sender_size.entry(tx.transaction.sender_id()) {
+            *entry.get_mut() -= tx.transaction.size();
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
 
         Some(tx.transaction.into())
     }
@@ -183,6 +192,10 @@ impl<T: ParkedOrd> ParkedPool<T> {
     /// Then, for each sender, all transactions for that sender are removed, until the pool limits
     /// have been met.
     ///
+    /// WVM: if only the size limit is exceeded, the sender with the largest combined size of
+    /// transactions is evicted first instead, so a few large transactions don't push out many small
+    /// ones.
+    ///
     /// Any removed transactions are returned.
     pub fn truncate_pool(
         &mut self,
@@ -198,7 +211,11 @@ impl<T: ParkedOrd> ParkedPool<T> {
         while limit.is_exceeded(self.len(), self.size()) && !self.last_sender_submission.is_empty()
         {
             // NOTE: This will not panic due to `!last_sender_transaction.is_empty()`
-            let sender_id = self.last_sender_submission.last().expect("not empty").sender_id;
+            let sender_id = if self.len() <= limit.max_txs {
+                self.largest_sender().expect("not empty")
+            } else {
+                self.last_sender_submission.last().expect("not empty").sender_id
+            };
             let list = self.get_txs_by_sender(sender_id);
 
             // Drop transactions from this sender until the pool is under limits
@@ -216,6 +233,14 @@ impl<T: ParkedOrd> ParkedPool<T> {
         removed
     }
 
+    /// WVM: Returns the sender with the largest combined size of transactions in the pool.
+    fn largest_sender(&self) -> Option<SenderId> {
+        self.sender_size
+            .iter()
+            .max_by_key(|(sender, size)| (**size, **sender))
+            .map(|(sender, _)| *sender)
+    }
+
     fn next_id(&mut self) -> u64 {
         let id = self.submission_id;
         self.submission_id = self.submission_id.wrapping_add(1);
@@ -332,6 +357,7 @@ impl<T: ParkedOrd> Default for ParkedPool<T> {
             best: Default::default(),
             last_sender_submission: Default::default(),
             sender_transaction_count: Default::default(),
+            sender_size: Default::default(),
             size_of: Default::default(),
         }
     }
@@ -683,6 +709,33 @@ mod tests {
         assert_eq!(removed.len(), 1);
     }
 
+    #[test]
+    fn test_truncate_parked_by_size_evicts_largest_sender() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+
+        let a_sender = address!("000000000000000000000000000000000000000a");
+        let b_sender = address!("000000000000000000000000000000000000000b");
+
+        // B submits small transactions first, then A submits a single large one
+        let b = MockTransactionSet::dependent(b_sender, 0, 3, TxType::Eip1559)
+            .into_iter()
+            .map(|tx| tx.with_size(100));
+        let a = MockTransaction::eip1559().with_sender(a_sender).with_size(800);
+
+        for tx in b.chain(std::iter::once(a)) {
+            pool.add_transaction(f.validated_arc(tx));
+        }
+
+        // the pool is over the size limit only, the large transaction is evicted even though B
+        // submitted first
+        let removed = pool.truncate_pool(SubPoolLimit { max_txs: 10, max_size: 1_000 });
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].sender(), a_sender);
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.size(), 300);
+    }
+
     #[test]
     fn test_senders_by_submission_id() {
         // this test ensures that we evict from the pending pool by sender
@@ -1,7 +1,10 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{
+        LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    },
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
@@ -24,9 +27,7 @@ use alloy_consensus::constants::{
 };
 use alloy_eips::eip4844::BLOB_TX_MIN_BLOB_GASPRICE;
 use alloy_primitives::{Address, TxHash, B256};
-use reth_primitives::constants::{
-    get_latest_min_protocol_base_fee, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
-};
+use reth_primitives::constants::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
@@ -1049,12 +1050,16 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Max combined size (in bytes) of the transactions of a single account
+    max_account_bytes: usize,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// WVM: Tracks the combined size of the transactions by sender that are currently in the pool.
+    tx_bytes: FxHashMap<SenderId, usize>,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1074,6 +1079,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
+            max_account_bytes: config.max_account_bytes,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
@@ -1126,6 +1132,22 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// WVM: Adds the size of a transaction to the tracked size of the sender
+    fn tx_bytes_inc(&mut self, sender: SenderId, size: usize) {
+        *self.tx_bytes.entry(sender).or_default() += size;
+    }
+
+    /// WVM: Subtracts the size of a transaction from the tracked size of the sender
+    fn tx_bytes_decr(&mut self, sender: SenderId, size: usize) {
+        if let hash_map::Entry::Occupied(mut entry) = self.tx_bytes.entry(sender) {
+            let bytes = entry.get_mut();
+            *bytes = bytes.saturating_sub(size);
+            if *bytes == 0 {
+                entry.remove();
+            }
+        }
+    }
+
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
         let BlockInfo {
@@ -1404,6 +1426,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(&tx.transaction_id)?;
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
+        self.tx_bytes_decr(tx.sender_id(), tx.size());
         self.update_size_metrics();
         Some((tx, internal.subpool))
     }
@@ -1421,6 +1444,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
+        self.tx_bytes_decr(internal.transaction.sender_id(), internal.transaction.size());
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1446,7 +1470,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///
     /// This will enforce all additional rules in the context of this pool, such as:
     ///   - Spam protection: reject new non-local transaction from a sender that exhausted its slot
-    ///     capacity.
+    ///     or byte capacity.
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
     ///   - Ensures transaction types are not conflicting for the sender: blob vs normal
     ///     transactions are mutually exclusive for the same sender.
@@ -1466,6 +1490,26 @@ impl<T: PoolTransaction> AllTransactions<T> {
                     transaction: Arc::new(transaction),
                 })
             }
+
+            // WVM: the same applies to the combined size of the sender's transactions, a
+            // replaced transaction does not count towards it
+            let current_bytes = self
+                .tx_bytes
+                .get(&transaction.sender_id())
+                .copied()
+                .unwrap_or_default()
+                .saturating_sub(
+                    self.txs
+                        .get(&transaction.transaction_id)
+                        .map_or(0, |replaced| replaced.transaction.size()),
+                );
+            if current_bytes + transaction.size() > self.max_account_bytes &&
+                transaction.nonce() > on_chain_nonce
+            {
+                return Err(InsertErr::ExceededSenderTransactionsCapacity {
+                    transaction: Arc::new(transaction),
+                })
+            }
         }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
@@ -1758,6 +1802,12 @@ impl<T: PoolTransaction> AllTransactions<T> {
             self.tx_inc(inserted_tx_id.sender);
         }
 
+        // WVM: a replacement only changes the tracked size of the sender by the difference
+        if let Some((replaced, _)) = &replaced_tx {
+            self.tx_bytes_decr(inserted_tx_id.sender, replaced.size());
+        }
+        self.tx_bytes_inc(inserted_tx_id.sender, transaction.size());
+
         self.update_size_metrics();
 
         Ok(InsertOk { transaction, move_to: state.into(), state, replaced_tx, updates })
@@ -1788,17 +1838,24 @@ impl<T: PoolTransaction> AllTransactions<T> {
     pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
         self.tx_counter.get(&sender).copied().unwrap_or_default()
     }
+
+    /// WVM: Returns the tracked combined size of the transactions of the sender.
+    pub(crate) fn tx_bytes(&self, sender: SenderId) -> usize {
+        self.tx_bytes.get(&sender).copied().unwrap_or_default()
+    }
 }
 
 impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-            minimal_protocol_basefee: get_latest_min_protocol_base_fee(),
-            block_gas_limit: *ETHEREUM_BLOCK_GAS_LIMIT,
+            max_account_bytes: TXPOOL_MAX_ACCOUNT_BYTES_PER_SENDER,
+            minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
+            block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            tx_bytes: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
@@ -1845,7 +1902,7 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     ///
     /// See also [`MIN_PROTOCOL_BASE_FEE`]
     FeeCapBelowMinimumProtocolFeeCap { transaction: Arc<ValidPoolTransaction<T>>, fee_cap: u128 },
-    /// Sender currently exceeds the configured limit for max account slots.
+    /// Sender currently exceeds the configured limit for max account slots or bytes.
     ///
     /// The sender can be considered a spammer at this point.
     ExceededSenderTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
@@ -2640,6 +2697,39 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn rejects_sender_exceeding_byte_capacity() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        pool.max_account_bytes = 1_000;
+
+        let first = MockTransaction::eip1559().with_size(400);
+        let second = first.next();
+        pool.insert_tx(f.validated(first.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        pool.insert_tx(f.validated(second.clone()), on_chain_balance, on_chain_nonce).unwrap();
+
+        let err = pool
+            .insert_tx(f.validated(second.next()), on_chain_balance, on_chain_nonce)
+            .unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderTransactionsCapacity { .. }));
+
+        // replacing a transaction only counts the replacement
+        let replacement = second.rng_hash().inc_price().with_size(600);
+        let replacement = f.validated(replacement);
+        let sender = replacement.sender_id();
+        assert!(pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).is_ok());
+        assert_eq!(pool.tx_bytes(sender), 1_000);
+
+        // removed transactions no longer count towards the sender's size
+        pool.remove_transaction_by_hash(&first.get_hash()).unwrap();
+        assert_eq!(pool.tx_bytes(sender), 600);
+        assert!(pool
+            .insert_tx(f.validated(second.next().with_size(400)), on_chain_balance, on_chain_nonce)
+            .is_ok());
+    }
+
     #[test]
     fn allow_local_spamming() {
         let on_chain_balance = U256::from(1_000);
@@ -2849,7 +2939,9 @@ mod tests {
 
         // this test adds large txs to the parked pool, then attempting to discard worst
         let mut f = MockTransactionFactory::default();
-        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        // WVM: the transactions are larger than the per-sender byte quota
+        let config = PoolConfig { max_account_bytes: usize::MAX, ..Default::default() };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
         let default_limits = pool.config.blob_limit;
 
         // create a chain of transactions by sender A
@@ -2891,7 +2983,9 @@ mod tests {
 
         // this test adds large txs to the parked pool, then attempting to discard worst
         let mut f = MockTransactionFactory::default();
-        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        // WVM: the transactions are larger than the per-sender byte quota
+        let config = PoolConfig { max_account_bytes: usize::MAX, ..Default::default() };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
         let default_limits = pool.config.queued_limit;
 
         // create a chain of transactions by sender A
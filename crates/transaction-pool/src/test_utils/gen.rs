@@ -5,8 +5,7 @@ use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
 use rand::Rng;
 use reth_chainspec::MAINNET;
 use reth_primitives::{
-    constants::{get_latest_min_protocol_base_fee, MIN_PROTOCOL_BASE_FEE},
-    sign_message, Transaction, TransactionSigned,
+    constants::MIN_PROTOCOL_BASE_FEE, sign_message, Transaction, TransactionSigned,
 };
 
 /// A generator for transactions for testing purposes.
@@ -33,7 +32,7 @@ impl<R: Rng> TransactionGenerator<R> {
         Self {
             rng,
             signer_keys: (0..num_signers).map(|_| B256::random()).collect(),
-            base_fee: get_latest_min_protocol_base_fee() as u128,
+            base_fee: MIN_PROTOCOL_BASE_FEE as u128,
             gas_limit: 300_000,
         }
     }
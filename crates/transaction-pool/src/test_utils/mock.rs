@@ -12,7 +12,6 @@ use alloy_consensus::{
     TxEip1559, TxEip2930, TxEip4844, TxLegacy,
 };
 use alloy_eips::{
-    eip1559::MIN_PROTOCOL_BASE_FEE,
     eip2930::AccessList,
     eip4844::{BlobTransactionValidationError, DATA_GAS_PER_BLOB},
 };
@@ -23,12 +22,10 @@ use rand::{
     prelude::Distribution,
 };
 use reth_primitives::{
-    transaction::TryFromRecoveredTransactionError, BlobTransactionSidecar,
-    PooledTransactionsElementEcRecovered, Transaction, TransactionSigned,
+    constants::MIN_PROTOCOL_BASE_FEE, transaction::TryFromRecoveredTransactionError,
+    BlobTransactionSidecar, PooledTransactionsElementEcRecovered, Transaction, TransactionSigned,
     TransactionSignedEcRecovered, TxType,
 };
-
-use reth_primitives::constants::get_latest_min_protocol_base_fee;
 use std::{ops::Range, sync::Arc, time::Instant, vec::IntoIter};
 
 /// A transaction pool implementation using [`MockOrdering`] for transaction ordering.
@@ -262,8 +259,8 @@ impl MockTransaction {
             hash: B256::random(),
             sender: Address::random(),
             nonce: 0,
-            max_fee_per_gas: get_latest_min_protocol_base_fee() as u128,
-            max_priority_fee_per_gas: get_latest_min_protocol_base_fee() as u128,
+            max_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            max_priority_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
             gas_limit: 0,
             to: Address::random().into(),
             value: Default::default(),
@@ -280,8 +277,8 @@ impl MockTransaction {
             hash: B256::random(),
             sender: Address::random(),
             nonce: 0,
-            max_fee_per_gas: get_latest_min_protocol_base_fee() as u128,
-            max_priority_fee_per_gas: get_latest_min_protocol_base_fee() as u128,
+            max_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
+            max_priority_fee_per_gas: MIN_PROTOCOL_BASE_FEE as u128,
             max_fee_per_blob_gas: DATA_GAS_PER_BLOB as u128,
             gas_limit: 0,
             to: Address::random(),
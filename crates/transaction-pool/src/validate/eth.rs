@@ -1,6 +1,5 @@
 //! Ethereum transaction validator.
 
-use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
 use crate::{
     blobstore::BlobStore,
     error::{
@@ -26,7 +25,10 @@ use revm::{
 };
 use std::{
     marker::PhantomData,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
 };
 use tokio::sync::Mutex;
 
@@ -124,16 +126,19 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     eip4844: bool,
     /// Fork indicator whether we are using EIP-7702 type transactions.
     eip7702: bool,
-    /// The current max gas limit
-    block_gas_limit: u64,
+    /// The max gas limit, read from the chain spec if not configured
+    block_gas_limit: Option<u64>,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Number of the next block, the chain spec parameters are read at.
+    next_block: AtomicU64,
     /// Stores the setup and parameters needed for validating KZG proofs.
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
-    /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
-    max_tx_input_bytes: usize,
+    /// Maximum size in bytes a single transaction can have in order to be accepted into the pool,
+    /// read from the chain spec if not configured.
+    max_tx_input_bytes: Option<usize>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -208,18 +213,34 @@ where
             }
         };
 
+        let next_block = self.next_block.load(Ordering::Relaxed);
+
         // Reject transactions over defined size to prevent DOS attacks
         let transaction_size = transaction.size();
-        if transaction_size > self.max_tx_input_bytes {
+        let max_tx_input_bytes = self
+            .max_tx_input_bytes
+            .unwrap_or_else(|| self.chain_spec.max_tx_input_bytes_at_block(next_block));
+        if transaction_size > max_tx_input_bytes {
             return TransactionValidationOutcome::Invalid(
                 transaction,
-                InvalidPoolTransactionError::OversizedData(
-                    transaction_size,
-                    self.max_tx_input_bytes,
-                ),
+                InvalidPoolTransactionError::OversizedData(transaction_size, max_tx_input_bytes),
             )
         }
 
+        // WVM: reject transactions that can never fit into a block
+        let encoded_length = transaction.encoded_length();
+        if let Some(max_block_size) = self.chain_spec.max_block_size_at_block(next_block) {
+            if encoded_length > max_block_size {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidPoolTransactionError::ExceedsMaxBlockSize(
+                        encoded_length,
+                        max_block_size,
+                    ),
+                )
+            }
+        }
+
         // Check whether the init code size has been exceeded.
         if self.fork_tracker.is_shanghai_activated() {
             if let Err(err) = transaction.ensure_max_init_code_size(MAX_INIT_CODE_BYTE_SIZE) {
@@ -229,12 +250,15 @@ where
 
         // Checks for gas limit
         let transaction_gas_limit = transaction.gas_limit();
-        if transaction_gas_limit > self.block_gas_limit {
+        let block_gas_limit = self
+            .block_gas_limit
+            .unwrap_or_else(|| self.chain_spec.block_gas_limit_at_block(next_block));
+        if transaction_gas_limit > block_gas_limit {
             return TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidPoolTransactionError::ExceedsGasLimit(
                     transaction_gas_limit,
-                    self.block_gas_limit,
+                    block_gas_limit,
                 ),
             )
         }
@@ -259,6 +283,16 @@ where
             )
         }
 
+        // WVM: drop transactions that can't pay the base fee floor of the next block
+        if transaction.max_fee_per_gas() <
+            self.chain_spec.wvm.base_fee_floor_at_block(next_block) as u128
+        {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidTransactionError::FeeCapTooLow.into(),
+            )
+        }
+
         // Checks for chainid
         if let Some(chain_id) = transaction.chain_id() {
             if chain_id != self.chain_id() {
@@ -290,6 +324,19 @@ where
             return TransactionValidationOutcome::Invalid(transaction, err)
         }
 
+        // WVM: the transaction must also cover the chain's minimum transaction gas, otherwise it
+        // would be rejected by block execution
+        if self
+            .chain_spec
+            .min_transaction_gas_at_block(next_block)
+            .is_some_and(|min_gas| transaction.gas_limit() < min_gas)
+        {
+            return TransactionValidationOutcome::Invalid(
+                transaction,
+                InvalidPoolTransactionError::IntrinsicGasTooLow,
+            )
+        }
+
         // light blob tx pre-checks
         if transaction.is_eip4844() {
             // Cancun fork is required for blob txs
@@ -467,6 +514,8 @@ where
         if self.chain_spec.is_prague_active_at_timestamp(new_tip_block.timestamp) {
             self.fork_tracker.prague.store(true, std::sync::atomic::Ordering::Relaxed);
         }
+
+        self.next_block.store(new_tip_block.number + 1, Ordering::Relaxed);
     }
 }
 
@@ -488,10 +537,12 @@ pub struct EthTransactionValidatorBuilder {
     eip4844: bool,
     /// Whether using EIP-7702 type transactions is allowed
     eip7702: bool,
-    /// The current max gas limit
-    block_gas_limit: u64,
+    /// The max gas limit, read from the chain spec if not configured
+    block_gas_limit: Option<u64>,
     /// Minimum priority fee to enforce for acceptance into the pool.
     minimum_priority_fee: Option<u128>,
+    /// Number of the next block, the chain spec parameters are read at.
+    next_block: u64,
     /// Determines how many additional tasks to spawn
     ///
     /// Default is 1
@@ -501,8 +552,9 @@ pub struct EthTransactionValidatorBuilder {
     kzg_settings: EnvKzgSettings,
     /// How to handle [`TransactionOrigin::Local`](TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
-    /// Max size in bytes of a single transaction allowed
-    max_tx_input_bytes: usize,
+    /// Max size in bytes of a single transaction allowed, read from the chain spec if not
+    /// configured
+    max_tx_input_bytes: Option<usize>,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -516,13 +568,14 @@ impl EthTransactionValidatorBuilder {
     ///  - EIP-4844
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
         Self {
-            block_gas_limit: chain_spec.max_gas_limit,
+            block_gas_limit: None,
+            next_block: 0,
             chain_spec,
             minimum_priority_fee: None,
             additional_tasks: 1,
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
-            max_tx_input_bytes: *DEFAULT_MAX_TX_INPUT_BYTES,
+            max_tx_input_bytes: None,
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -644,17 +697,27 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Configures the chain spec parameters, like the base fee floor, based on the head block's
+    /// number.
+    pub const fn with_head_number(mut self, number: u64) -> Self {
+        self.next_block = number + 1;
+        self
+    }
+
     /// Sets a max size in bytes of a single transaction allowed into the pool
+    ///
+    /// Defaults to the limit of the chain spec at the next block.
     pub const fn with_max_tx_input_bytes(mut self, max_tx_input_bytes: usize) -> Self {
-        self.max_tx_input_bytes = max_tx_input_bytes;
+        self.max_tx_input_bytes = Some(max_tx_input_bytes);
         self
     }
 
     /// Sets the block gas limit
     ///
-    /// Transactions with a gas limit greater than this will be rejected.
+    /// Transactions with a gas limit greater than this will be rejected. Defaults to the gas limit
+    /// of the chain spec at the next block.
     pub const fn set_block_gas_limit(mut self, block_gas_limit: u64) -> Self {
-        self.block_gas_limit = block_gas_limit;
+        self.block_gas_limit = Some(block_gas_limit);
         self
     }
 
@@ -678,6 +741,7 @@ impl EthTransactionValidatorBuilder {
             eip7702,
             block_gas_limit,
             minimum_priority_fee,
+            next_block,
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
@@ -700,6 +764,7 @@ impl EthTransactionValidatorBuilder {
             eip7702,
             block_gas_limit,
             minimum_priority_fee,
+            next_block: AtomicU64::new(next_block),
             blob_store: Box::new(blob_store),
             kzg_settings,
             local_transactions_config,
@@ -820,9 +885,9 @@ mod tests {
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
-    use reth_chainspec::MAINNET;
-    use reth_primitives::PooledTransactionsElement;
+    use alloy_primitives::{hex, B256, U256};
+    use reth_chainspec::{BlockSchedule, WvmChainParams, MAINNET};
+    use reth_primitives::{Header, PooledTransactionsElement, SealedHeader};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 
     fn get_transaction() -> EthPooledTransaction {
@@ -904,4 +969,143 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[test]
+    fn invalid_on_scheduled_gas_limit() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        // gas limit lowered at the next block
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                gas_limit: BlockSchedule::from([(11, 1_000_000)]),
+                ..Default::default()
+            },
+            ..(**MAINNET).clone()
+        });
+        let validator = EthTransactionValidatorBuilder::new(chain_spec)
+            .with_head_number(9)
+            .build(provider, InMemoryBlobStore::default());
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        validator.on_new_head_block(&SealedBlock {
+            header: SealedHeader::new(Header { number: 10, ..Default::default() }, B256::ZERO),
+            ..Default::default()
+        });
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::ExceedsGasLimit(1_015_288, 1_000_000)
+            )
+        ));
+    }
+
+    #[test]
+    fn invalid_below_min_transaction_gas() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        // minimum transaction gas raised above the tx gas limit of 1_015_288 at the next block
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                min_transaction_gas: BlockSchedule::from([(11, 2_000_000)]),
+                ..Default::default()
+            },
+            ..(**MAINNET).clone()
+        });
+        let validator = EthTransactionValidatorBuilder::new(chain_spec)
+            .with_head_number(9)
+            .build(provider, InMemoryBlobStore::default());
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        validator.on_new_head_block(&SealedBlock {
+            header: SealedHeader::new(Header { number: 10, ..Default::default() }, B256::ZERO),
+            ..Default::default()
+        });
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::IntrinsicGasTooLow
+            )
+        ));
+    }
+
+    #[test]
+    fn invalid_above_max_block_size() {
+        let transaction = get_transaction();
+        let encoded_length = transaction.encoded_length();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                max_block_size: BlockSchedule::from([(0, 1_000)]),
+                ..Default::default()
+            },
+            ..(**MAINNET).clone()
+        });
+        let validator = EthTransactionValidatorBuilder::new(chain_spec)
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::ExceedsMaxBlockSize(size, 1_000)
+            ) if size == encoded_length
+        ));
+    }
+
+    #[test]
+    fn invalid_below_base_fee_floor() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        // floor activated at the next block
+        let chain_spec = Arc::new(ChainSpec {
+            wvm: WvmChainParams {
+                base_fee_floor: BlockSchedule::from([(11, u64::MAX)]),
+                ..Default::default()
+            },
+            ..(**MAINNET).clone()
+        });
+        let validator = EthTransactionValidatorBuilder::new(chain_spec)
+            .with_head_number(10)
+            .build(provider, InMemoryBlobStore::default());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(InvalidTransactionError::FeeCapTooLow)
+            )
+        ));
+    }
 }
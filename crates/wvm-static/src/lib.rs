@@ -1,28 +1,41 @@
 // TODO: move to load network crate and make it subcrate
 
-use load_db::{drivers::planetscale::PlanetScaleDriver, LoadDbConnection};
+use eyre::eyre;
+use load_db::{config::LoadDbConfig, LoadDbConnection};
 use once_cell::sync::Lazy;
 use std::{
     future::Future,
     sync::{Arc, LazyLock},
     time::Instant,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 pub static SUPERVISOR_RT: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
     tokio::runtime::Builder::new_multi_thread().enable_all().thread_name("wvm").build().unwrap()
 });
 
-pub static PRECOMPILE_LOADDB_CLIENT: LazyLock<Arc<PlanetScaleDriver>> = LazyLock::new(|| {
-    let host = std::env::var("PS_HOST").unwrap_or_default();
-    let username = std::env::var("PS_USERNAME").unwrap_or_default();
-    let password = std::env::var("PS_PASSWORD").unwrap_or_default();
-
-    let planet_scale_driver = PlanetScaleDriver::new(host, username, password);
-
-    Arc::new(planet_scale_driver)
+/// LoadDb client shared by the RPC, the precompiles and the Arweave actor, or why it couldn't be
+/// initialized. The driver is selected with `LOADDB_DRIVER`, see [`LoadDbConfig`].
+static PRECOMPILE_LOADDB_CLIENT: LazyLock<
+    Result<Arc<dyn LoadDbConnection + Send + Sync + 'static>, String>,
+> = LazyLock::new(|| {
+    let client = LoadDbConfig::from_env().and_then(|config| {
+        info!(target: "wvm::loaddb", driver = config.driver_name(), "Connecting to LoadDb");
+        config.connect()
+    });
+    client.map_err(|e| {
+        warn!(target: "wvm::loaddb", %e, "LoadDb client could not be initialized");
+        e.to_string()
+    })
 });
 
+/// Returns the shared LoadDb client, initialized on first use.
+pub fn load_db_client() -> eyre::Result<Arc<dyn LoadDbConnection + Send + Sync + 'static>> {
+    PRECOMPILE_LOADDB_CLIENT
+        .clone()
+        .map_err(|e| eyre!("LoadDb client could not be initialized: {e}"))
+}
+
 pub fn internal_block<F: Future>(f: F) -> Result<F::Output, ()> {
     let careful_tokio = std::env::var("CAREFUL_TOKIO").unwrap_or("true".to_string());
     if careful_tokio == "true" {
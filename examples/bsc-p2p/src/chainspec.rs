@@ -25,6 +25,7 @@ pub(crate) fn bsc_chain_spec() -> Arc<ChainSpec> {
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         max_gas_limit: 140_000_000,
         prune_delete_limit: 0,
+        wvm: Default::default(),
     }
     .into()
 }
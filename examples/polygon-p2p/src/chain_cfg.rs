@@ -31,6 +31,7 @@ pub(crate) fn polygon_chain_spec() -> Arc<ChainSpec> {
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         max_gas_limit: 30_000_000,
         prune_delete_limit: 0,
+        wvm: Default::default(),
     }
     .into()
 }
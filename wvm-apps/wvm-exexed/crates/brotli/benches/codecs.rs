@@ -0,0 +1,132 @@
+#![allow(missing_docs)]
+//! Compressed size and throughput of the archive codecs.
+//!
+//! Archives downloaded from Arweave can be benchmarked by putting them in a directory given with
+//! `WVM_BENCH_ARCHIVES`, synthetic blocks are used otherwise.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rbrotli::codec::{
+    detect_encoding, train_dictionary, ArchiveCodec, ZstdDictionary, ARCHIVE_CODECS,
+    DEFAULT_DICTIONARY_SIZE, MAX_ARCHIVE_SIZE,
+};
+use reth::primitives::{
+    revm_primitives::alloy_primitives::{Bytes, Signature, U256},
+    Header, SealedBlockWithSenders, SealedHeader, Transaction, TransactionSigned,
+};
+use std::{hint::black_box, sync::Arc};
+use wvm_borsh::block::BorshSealedBlockWithSenders;
+use wvm_tx::wvm::{v1::V1WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
+
+/// Borsh encoded blocks, decompressed from the archives of `WVM_BENCH_ARCHIVES`.
+fn archive_samples() -> Option<Vec<Vec<u8>>> {
+    let dir = std::env::var("WVM_BENCH_ARCHIVES").ok()?;
+    let samples: Vec<Vec<u8>> = std::fs::read_dir(dir)
+        .expect("WVM_BENCH_ARCHIVES is not a directory")
+        .filter_map(|entry| std::fs::read(entry.ok()?.path()).ok())
+        .filter_map(|data| {
+            ARCHIVE_CODECS
+                .decompress(detect_encoding(&data, false), None, &data, MAX_ARCHIVE_SIZE, |_| None)
+                .ok()
+        })
+        .collect();
+
+    (!samples.is_empty()).then_some(samples)
+}
+
+fn synthetic_samples() -> Vec<Vec<u8>> {
+    (0..128u64)
+        .map(|number| {
+            let mut block = SealedBlockWithSenders::default();
+            let header = Header {
+                number,
+                timestamp: 1_720_000_000 + number,
+                gas_used: 21_000 * (number % 16),
+                ..Default::default()
+            };
+            block.block.header = SealedHeader::new(header, Default::default());
+            block.block.body.transactions = (0..number % 16)
+                .map(|nonce| {
+                    let mut transaction = Transaction::Legacy(Default::default());
+                    transaction.set_nonce(nonce);
+                    transaction.set_gas_limit(21_000);
+                    transaction.set_value(U256::from(number * nonce));
+                    transaction.set_input(Bytes::from(vec![nonce as u8; 68]));
+                    TransactionSigned::from_transaction_and_signature(
+                        transaction,
+                        Signature::test_signature(),
+                    )
+                })
+                .collect();
+
+            borsh::to_vec(&BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V1(
+                V1WvmSealedBlockWithSenders::from(block),
+            )))
+            .unwrap()
+        })
+        .collect()
+}
+
+fn codecs(c: &mut Criterion) {
+    let samples = archive_samples().unwrap_or_else(synthetic_samples);
+    let raw_size: usize = samples.iter().map(Vec::len).sum();
+
+    let dictionary = ZstdDictionary {
+        id: String::from("bench"),
+        data: Arc::new(train_dictionary(&samples, DEFAULT_DICTIONARY_SIZE).unwrap()),
+    };
+    let codecs = [
+        ("brotli-11", ArchiveCodec::default()),
+        ("brotli-6", ArchiveCodec::Brotli { quality: 6, window: 22 }),
+        ("zstd-3", ArchiveCodec::Zstd { level: 3 }),
+        ("zstd-19", ArchiveCodec::Zstd { level: 19 }),
+        ("zstd-dict-19", ArchiveCodec::ZstdDict { level: 19, dictionary: dictionary.clone() }),
+    ];
+    ARCHIVE_CODECS.register_dictionary(dictionary);
+
+    let mut group = c.benchmark_group("Archive codecs");
+    group.throughput(Throughput::Bytes(raw_size as u64));
+
+    for (name, codec) in codecs {
+        let compressed: Vec<Vec<u8>> =
+            samples.iter().map(|sample| codec.compress(sample).unwrap()).collect();
+        let compressed_size: usize = compressed.iter().map(Vec::len).sum();
+        println!(
+            "{}: {} blocks, {} -> {} bytes, ratio {:.2}",
+            name,
+            samples.len(),
+            raw_size,
+            compressed_size,
+            raw_size as f64 / compressed_size as f64
+        );
+
+        group.bench_function(BenchmarkId::new("compress", name), |b| {
+            b.iter(|| {
+                for sample in &samples {
+                    black_box(codec.compress(sample).unwrap());
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("decompress", name), |b| {
+            b.iter(|| {
+                for data in &compressed {
+                    black_box(
+                        ARCHIVE_CODECS
+                            .decompress(
+                                codec.encoding(),
+                                codec.dictionary_id(),
+                                data,
+                                MAX_ARCHIVE_SIZE,
+                                |_| None,
+                            )
+                            .unwrap(),
+                    );
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, codecs);
+criterion_main!(benches);
@@ -0,0 +1,391 @@
+//! Compression codecs of archived blocks.
+//!
+//! The codec of an archive is recorded in its `WeaveVM:Encoding` tag. Archives compressed with a
+//! zstd dictionary also carry the Arweave id of the dictionary in `WeaveVM:Compression-Dictionary`,
+//! dictionaries are trained on historical blocks and stored on Arweave like any other data.
+
+use crate::{compress_with, from_brotli, BrotliError};
+use brotlic::{BrotliEncoderOptions, Quality, WindowSize};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Read},
+    sync::{Arc, LazyLock, RwLock},
+};
+
+pub const ENCODING_TAG: &str = "WeaveVM:Encoding";
+pub const DICTIONARY_TAG: &str = "WeaveVM:Compression-Dictionary";
+
+pub const BROTLI_ENCODING: &str = "Borsh-Brotli";
+pub const ZSTD_ENCODING: &str = "Borsh-Zstd";
+pub const ZSTD_DICT_ENCODING: &str = "Borsh-Zstd-Dict";
+
+/// Same as the defaults of the Brotli encoder, which archives were compressed with so far.
+pub const DEFAULT_BROTLI_QUALITY: u8 = 11;
+pub const DEFAULT_BROTLI_WINDOW: u8 = 22;
+pub const DEFAULT_ZSTD_LEVEL: i32 = 19;
+
+/// Default size of trained zstd dictionaries.
+pub const DEFAULT_DICTIONARY_SIZE: usize = 112_640;
+
+/// Largest decompressed archive accepted.
+pub const MAX_ARCHIVE_SIZE: usize = 256 * 1024 * 1024;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Codecs archives can be read with, along with the dictionaries already loaded.
+pub static ARCHIVE_CODECS: LazyLock<CodecRegistry> = LazyLock::new(CodecRegistry::default);
+
+#[derive(Debug)]
+pub enum CodecError {
+    UnknownEncoding(String),
+    MissingDictionary,
+    DictionaryUnavailable(String),
+    InvalidParameter(&'static str),
+    /// Payload is corrupted.
+    InvalidPayload,
+    /// Payload decompresses into more than `limit` bytes.
+    OutputTooLarge {
+        limit: usize,
+    },
+    Io(io::Error),
+}
+
+impl std::error::Error for CodecError {}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnknownEncoding(encoding) => write!(f, "Unknown encoding {}", encoding),
+            CodecError::MissingDictionary => write!(f, "Archive doesn't reference its dictionary"),
+            CodecError::DictionaryUnavailable(id) => {
+                write!(f, "Dictionary {} could not be loaded", id)
+            }
+            CodecError::InvalidParameter(parameter) => {
+                write!(f, "Invalid compression parameter {}", parameter)
+            }
+            CodecError::InvalidPayload => write!(f, "Invalid compressed payload"),
+            CodecError::OutputTooLarge { limit } => {
+                write!(f, "Payload decompresses into more than {} bytes", limit)
+            }
+            CodecError::Io(e) => write!(f, "Compression failed: {}", e),
+        }
+    }
+}
+
+impl From<BrotliError> for CodecError {
+    fn from(value: BrotliError) -> Self {
+        match value {
+            BrotliError::Decompression(_) => CodecError::InvalidPayload,
+            BrotliError::OutputTooLarge { limit } => CodecError::OutputTooLarge { limit },
+            BrotliError::Compression(e) | BrotliError::Output(e) => CodecError::Io(e),
+        }
+    }
+}
+
+impl From<io::Error> for CodecError {
+    fn from(value: io::Error) -> Self {
+        CodecError::Io(value)
+    }
+}
+
+#[derive(Clone)]
+pub struct ZstdDictionary {
+    /// Arweave id of the dictionary.
+    pub id: String,
+    pub data: Arc<Vec<u8>>,
+}
+
+impl fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZstdDictionary")
+            .field("id", &self.id)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ArchiveCodec {
+    Brotli { quality: u8, window: u8 },
+    Zstd { level: i32 },
+    ZstdDict { level: i32, dictionary: ZstdDictionary },
+}
+
+impl Default for ArchiveCodec {
+    fn default() -> Self {
+        ArchiveCodec::Brotli { quality: DEFAULT_BROTLI_QUALITY, window: DEFAULT_BROTLI_WINDOW }
+    }
+}
+
+impl ArchiveCodec {
+    /// Codec configured with `WVM_ARCHIVE_CODEC`, one of `brotli` (default), `zstd` or
+    /// `zstd-dict`.
+    ///
+    /// Brotli is tuned with `WVM_ARCHIVE_BROTLI_QUALITY` and `WVM_ARCHIVE_BROTLI_WINDOW`, zstd with
+    /// `WVM_ARCHIVE_ZSTD_LEVEL`. `zstd-dict` needs the Arweave id of the dictionary in
+    /// `WVM_ARCHIVE_ZSTD_DICTIONARY`, it's loaded with `fetch_dictionary`.
+    pub fn from_env<F>(fetch_dictionary: F) -> Result<Self, CodecError>
+    where
+        F: FnOnce(&str) -> Option<Vec<u8>>,
+    {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+        }
+
+        let level = var("WVM_ARCHIVE_ZSTD_LEVEL", DEFAULT_ZSTD_LEVEL);
+
+        let codec = match std::env::var("WVM_ARCHIVE_CODEC").unwrap_or_default().as_str() {
+            "" | "brotli" => ArchiveCodec::Brotli {
+                quality: var("WVM_ARCHIVE_BROTLI_QUALITY", DEFAULT_BROTLI_QUALITY),
+                window: var("WVM_ARCHIVE_BROTLI_WINDOW", DEFAULT_BROTLI_WINDOW),
+            },
+            "zstd" => ArchiveCodec::Zstd { level },
+            "zstd-dict" => {
+                let id = std::env::var("WVM_ARCHIVE_ZSTD_DICTIONARY")
+                    .map_err(|_| CodecError::MissingDictionary)?;
+                let data = fetch_dictionary(&id)
+                    .ok_or_else(|| CodecError::DictionaryUnavailable(id.clone()))?;
+                ArchiveCodec::ZstdDict {
+                    level,
+                    dictionary: ZstdDictionary { id, data: Arc::new(data) },
+                }
+            }
+            codec => return Err(CodecError::UnknownEncoding(codec.to_string())),
+        };
+
+        // Fail on startup rather than on the first archived block.
+        codec.compress(&[])?;
+
+        Ok(codec)
+    }
+
+    /// Value of the `WeaveVM:Encoding` tag.
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            ArchiveCodec::Brotli { .. } => BROTLI_ENCODING,
+            ArchiveCodec::Zstd { .. } => ZSTD_ENCODING,
+            ArchiveCodec::ZstdDict { .. } => ZSTD_DICT_ENCODING,
+        }
+    }
+
+    /// Value of the `WeaveVM:Compression-Dictionary` tag.
+    pub fn dictionary_id(&self) -> Option<&str> {
+        match self {
+            ArchiveCodec::ZstdDict { dictionary, .. } => Some(dictionary.id.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        match self {
+            ArchiveCodec::Brotli { quality, window } => {
+                let encoder = BrotliEncoderOptions::new()
+                    .quality(
+                        Quality::new(*quality)
+                            .map_err(|_| CodecError::InvalidParameter("brotli quality"))?,
+                    )
+                    .window_size(
+                        WindowSize::new(*window)
+                            .map_err(|_| CodecError::InvalidParameter("brotli window"))?,
+                    )
+                    .build()
+                    .map_err(|_| CodecError::InvalidParameter("brotli encoder"))?;
+
+                let mut compressed = Vec::new();
+                compress_with(encoder, data, &mut compressed)?;
+                Ok(compressed)
+            }
+            ArchiveCodec::Zstd { level } => Ok(zstd::bulk::compress(data, *level)?),
+            ArchiveCodec::ZstdDict { level, dictionary } => {
+                let mut compressor =
+                    zstd::bulk::Compressor::with_dictionary(*level, &dictionary.data)?;
+                Ok(compressor.compress(data)?)
+            }
+        }
+    }
+}
+
+/// Decompressors of every encoding, dictionaries are loaded once and kept for the next archives.
+#[derive(Debug, Default)]
+pub struct CodecRegistry {
+    dictionaries: RwLock<HashMap<String, Arc<Vec<u8>>>>,
+}
+
+impl CodecRegistry {
+    pub const ENCODINGS: [&'static str; 3] = [BROTLI_ENCODING, ZSTD_ENCODING, ZSTD_DICT_ENCODING];
+
+    pub fn supports(encoding: &str) -> bool {
+        Self::ENCODINGS.contains(&encoding)
+    }
+
+    pub fn register_dictionary(&self, dictionary: ZstdDictionary) {
+        if let Ok(mut dictionaries) = self.dictionaries.write() {
+            dictionaries.insert(dictionary.id, dictionary.data);
+        }
+    }
+
+    pub fn dictionary(&self, id: &str) -> Option<Arc<Vec<u8>>> {
+        self.dictionaries.read().ok()?.get(id).cloned()
+    }
+
+    /// Decompresses an archive tagged with `encoding` into at most `limit` bytes.
+    ///
+    /// `fetch_dictionary` is only called for dictionaries which aren't registered yet.
+    pub fn decompress<F>(
+        &self,
+        encoding: &str,
+        dictionary_id: Option<&str>,
+        data: &[u8],
+        limit: usize,
+        fetch_dictionary: F,
+    ) -> Result<Vec<u8>, CodecError>
+    where
+        F: FnOnce(&str) -> Option<Vec<u8>>,
+    {
+        match encoding {
+            BROTLI_ENCODING => Ok(from_brotli(data, limit)?),
+            ZSTD_ENCODING => read_with_limit(zstd::stream::read::Decoder::new(data)?, limit),
+            ZSTD_DICT_ENCODING => {
+                let id = dictionary_id.ok_or(CodecError::MissingDictionary)?;
+                let dictionary = match self.dictionary(id) {
+                    Some(dictionary) => dictionary,
+                    None => {
+                        let data = fetch_dictionary(id)
+                            .ok_or_else(|| CodecError::DictionaryUnavailable(id.to_string()))?;
+                        let dictionary =
+                            ZstdDictionary { id: id.to_string(), data: Arc::new(data) };
+                        self.register_dictionary(dictionary.clone());
+                        dictionary.data
+                    }
+                };
+
+                let decoder = zstd::stream::read::Decoder::with_dictionary(
+                    io::BufReader::new(data),
+                    &dictionary,
+                )?;
+                read_with_limit(decoder, limit)
+            }
+            _ => Err(CodecError::UnknownEncoding(encoding.to_string())),
+        }
+    }
+}
+
+/// Encoding of an archive whose tags aren't known, zstd frames are recognised by their magic
+/// number, anything else is assumed to be Brotli.
+pub fn detect_encoding(data: &[u8], with_dictionary: bool) -> &'static str {
+    match (data.starts_with(&ZSTD_MAGIC), with_dictionary) {
+        (true, true) => ZSTD_DICT_ENCODING,
+        (true, false) => ZSTD_ENCODING,
+        (false, _) => BROTLI_ENCODING,
+    }
+}
+
+/// Trains a zstd dictionary on decompressed archives.
+pub fn train_dictionary(samples: &[Vec<u8>], max_size: usize) -> Result<Vec<u8>, CodecError> {
+    Ok(zstd::dict::from_samples(samples, max_size)?)
+}
+
+/// Reads a decoder into at most `limit` bytes.
+pub fn read_with_limit<R: Read>(reader: R, limit: usize) -> Result<Vec<u8>, CodecError> {
+    let mut decoded = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|_| CodecError::InvalidPayload)?;
+
+    if decoded.len() > limit {
+        return Err(CodecError::OutputTooLarge { limit });
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod codec_tests {
+    use crate::codec::{
+        detect_encoding, train_dictionary, ArchiveCodec, CodecError, CodecRegistry, ZstdDictionary,
+        BROTLI_ENCODING, ZSTD_DICT_ENCODING, ZSTD_ENCODING,
+    };
+    use std::sync::Arc;
+
+    fn samples() -> Vec<Vec<u8>> {
+        (0..64u32)
+            .map(|i| {
+                format!(
+                    r#"{{"number":{},"miner":"0x{:040x}","gasLimit":300000000,"transactions":[]}}"#,
+                    i,
+                    i % 4
+                )
+                .repeat(8)
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_codecs_roundtrip() {
+        let samples = samples();
+        let dictionary = train_dictionary(&samples, 1024).unwrap();
+        let codecs = [
+            ArchiveCodec::default(),
+            ArchiveCodec::Brotli { quality: 5, window: 18 },
+            ArchiveCodec::Zstd { level: 3 },
+            ArchiveCodec::ZstdDict {
+                level: 3,
+                dictionary: ZstdDictionary { id: "dict".to_string(), data: Arc::new(dictionary) },
+            },
+        ];
+
+        let registry = CodecRegistry::default();
+        for codec in codecs {
+            let compressed = codec.compress(&samples[0]).unwrap();
+            assert_eq!(
+                detect_encoding(&compressed, codec.dictionary_id().is_some()),
+                codec.encoding()
+            );
+
+            let fetch = |id: &str| match &codec {
+                ArchiveCodec::ZstdDict { dictionary, .. } if dictionary.id == id => {
+                    Some(dictionary.data.to_vec())
+                }
+                _ => None,
+            };
+            let decompressed = registry
+                .decompress(codec.encoding(), codec.dictionary_id(), &compressed, 1 << 20, fetch)
+                .unwrap();
+            assert_eq!(decompressed, samples[0]);
+        }
+
+        // The dictionary was kept after the first fetch.
+        assert!(registry.dictionary("dict").is_some());
+    }
+
+    #[test]
+    pub fn test_decompress_errors() {
+        let registry = CodecRegistry::default();
+        let data = vec![7u8; 4096];
+
+        let compressed = ArchiveCodec::Zstd { level: 3 }.compress(&data).unwrap();
+        assert!(matches!(
+            registry.decompress(ZSTD_ENCODING, None, &compressed, 4095, |_| None),
+            Err(CodecError::OutputTooLarge { limit: 4095 })
+        ));
+        assert!(matches!(
+            registry.decompress(ZSTD_DICT_ENCODING, None, &compressed, 4096, |_| None),
+            Err(CodecError::MissingDictionary)
+        ));
+        assert!(matches!(
+            registry.decompress(ZSTD_DICT_ENCODING, Some("unknown"), &compressed, 4096, |_| None),
+            Err(CodecError::DictionaryUnavailable(_))
+        ));
+        assert!(matches!(
+            registry.decompress("Borsh-Lz4", None, &compressed, 4096, |_| None),
+            Err(CodecError::UnknownEncoding(_))
+        ));
+        assert!(matches!(
+            registry.decompress(BROTLI_ENCODING, None, b"not brotli", 4096, |_| None),
+            Err(CodecError::InvalidPayload)
+        ));
+    }
+}
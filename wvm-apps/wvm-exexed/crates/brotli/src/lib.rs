@@ -1,38 +1,148 @@
-use brotlic::{CompressorWriter, DecompressorReader};
-use std::io::{Read, Write};
+use brotlic::{BrotliEncoder, CompressorWriter, DecompressorReader};
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
 
-pub fn to_brotli(data: Vec<u8>) -> Vec<u8> {
-    let buff: Vec<u8> = vec![];
-    let mut compressor = CompressorWriter::new(buff);
-    compressor.write_all(data.as_slice()).unwrap();
-    compressor.into_inner().unwrap()
+pub mod codec;
+
+#[derive(Debug)]
+pub enum BrotliError {
+    Compression(io::Error),
+    /// Payload is not valid Brotli, or reading it failed.
+    Decompression(io::Error),
+    /// Payload decompresses into more than the allowed size.
+    OutputTooLarge {
+        limit: usize,
+    },
+    Output(io::Error),
+}
+
+impl std::error::Error for BrotliError {}
+
+impl fmt::Display for BrotliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BrotliError::Compression(e) => write!(f, "Brotli compression failed: {}", e),
+            BrotliError::Decompression(e) => write!(f, "Invalid Brotli payload: {}", e),
+            BrotliError::OutputTooLarge { limit } => {
+                write!(f, "Brotli payload decompresses into more than {} bytes", limit)
+            }
+            BrotliError::Output(e) => write!(f, "Brotli output could not be written: {}", e),
+        }
+    }
+}
+
+/// Compresses everything read from `reader` into `writer` with the default encoder.
+///
+/// Returns the number of bytes read.
+pub fn compress<R: Read, W: Write>(reader: R, writer: W) -> Result<u64, BrotliError> {
+    compress_with(BrotliEncoder::new(), reader, writer)
+}
+
+/// Same as [`compress`] with a configured encoder.
+pub fn compress_with<R: Read, W: Write>(
+    encoder: BrotliEncoder,
+    mut reader: R,
+    writer: W,
+) -> Result<u64, BrotliError> {
+    let mut compressor = CompressorWriter::with_encoder(encoder, writer);
+    let read = io::copy(&mut reader, &mut compressor).map_err(BrotliError::Compression)?;
+    compressor.into_inner().map_err(|_| {
+        BrotliError::Compression(io::Error::new(
+            io::ErrorKind::Other,
+            "Brotli stream could not be finished",
+        ))
+    })?;
+
+    Ok(read)
 }
 
-pub fn from_brotli(data: Vec<u8>) -> Vec<u8> {
-    // create a wrapper around BufRead that supports on the fly brotli decompression.
-    let mut decompressed_reader = DecompressorReader::new(data.as_slice());
-    let mut decoded_input: Vec<u8> = Vec::new();
+/// Decompresses `reader` into `writer`, failing once more than `limit` bytes were decompressed.
+///
+/// Returns the number of bytes written. Output is written as it's decompressed, so `writer` may
+/// hold partial data on errors.
+pub fn decompress<R: Read, W: Write>(
+    reader: R,
+    mut writer: W,
+    limit: usize,
+) -> Result<u64, BrotliError> {
+    let mut decompressor = DecompressorReader::new(io::BufReader::new(reader));
+    let mut buffer = [0u8; 8192];
+    let mut written = 0u64;
+
+    loop {
+        let read = match decompressor.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(BrotliError::Decompression(e)),
+        };
 
-    decompressed_reader.read_to_end(&mut decoded_input).unwrap();
+        written += read as u64;
+        if written > limit as u64 {
+            return Err(BrotliError::OutputTooLarge { limit });
+        }
+        writer.write_all(&buffer[..read]).map_err(BrotliError::Output)?;
+    }
 
-    decoded_input
+    Ok(written)
+}
+
+pub fn to_brotli(data: &[u8]) -> Result<Vec<u8>, BrotliError> {
+    let mut compressed = Vec::new();
+    compress(data, &mut compressed)?;
+    Ok(compressed)
+}
+
+/// Decompresses `data` into at most `limit` bytes.
+pub fn from_brotli(data: &[u8], limit: usize) -> Result<Vec<u8>, BrotliError> {
+    let mut decompressed = Vec::new();
+    decompress(data, &mut decompressed, limit)?;
+    Ok(decompressed)
 }
 
 #[cfg(test)]
 mod brotlic_tests {
-    use crate::{from_brotli, to_brotli};
+    use crate::{decompress, from_brotli, to_brotli, BrotliError};
     use reth::primitives::SealedBlockWithSenders;
     use wvm_borsh::block::BorshSealedBlockWithSenders;
+    use wvm_tx::wvm::{v1::V1WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
 
     #[test]
     pub fn test_brotlic_block() {
         let sealed_block_with_senders = SealedBlockWithSenders::default();
-        // TODO: fix it
-        let borsh_block = BorshSealedBlockWithSenders(sealed_block_with_senders);
+        let borsh_block = BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V1(
+            V1WvmSealedBlockWithSenders::from(sealed_block_with_senders),
+        ));
         let borsh_vec = borsh::to_vec(&borsh_block).unwrap();
-        let brotli = to_brotli(borsh_vec.clone());
+        let brotli = to_brotli(&borsh_vec).unwrap();
         assert!(brotli.len() < borsh_vec.len());
-        let unbrotli = from_brotli(brotli);
+        let unbrotli = from_brotli(&brotli, borsh_vec.len()).unwrap();
         assert_eq!(borsh_vec, unbrotli);
     }
+
+    #[test]
+    pub fn test_brotli_with_limit() {
+        let data = vec![7u8; 4096];
+        let brotli = to_brotli(&data).unwrap();
+        assert_eq!(from_brotli(&brotli, 4096).unwrap(), data);
+        assert!(matches!(
+            from_brotli(&brotli, 4095),
+            Err(BrotliError::OutputTooLarge { limit: 4095 })
+        ));
+        assert!(matches!(from_brotli(b"not brotli", 4096), Err(BrotliError::Decompression(_))));
+    }
+
+    #[test]
+    pub fn test_decompression_bomb() {
+        // 64MiB of zeros compress into a few hundred bytes.
+        let bomb = to_brotli(&vec![0u8; 64 * 1024 * 1024]).unwrap();
+        assert!(bomb.len() < 64 * 1024);
+
+        let mut output = Vec::new();
+        let res = decompress(bomb.as_slice(), &mut output, 1024 * 1024);
+        assert!(matches!(res, Err(BrotliError::OutputTooLarge { .. })));
+        assert!(output.len() <= 1024 * 1024);
+    }
 }
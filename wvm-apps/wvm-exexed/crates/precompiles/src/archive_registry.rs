@@ -0,0 +1,318 @@
+//! WVM: payload builder recording archived blocks in the on-chain archive registry.
+//!
+//! Blocks carry the Arweave id of the block `archive_registry_delay` blocks before them in their
+//! `extra_data`, the pre-block system call of the executor then stores it in the registry. The ids
+//! are resolved from LoadDb in the background, as new blocks become canonical, so building a
+//! payload never waits on LoadDb.
+
+use alloy_primitives::{Bytes, B256};
+use load_db::LoadDbConnection;
+use parking_lot::RwLock;
+use reth::{
+    api::{ConfigureEvm, FullNodeTypes, NodeTypesWithEngine, PayloadTypes},
+    builder::{components::PayloadServiceBuilder, BuilderContext, PayloadBuilderConfig},
+    payload::{
+        EthBuiltPayload, EthPayloadBuilderAttributes, PayloadBuilderError, PayloadBuilderHandle,
+        PayloadBuilderService,
+    },
+    primitives::Header,
+    providers::{CanonStateSubscriptions, ChainSpecProvider, StateProviderFactory},
+    transaction_pool::TransactionPool,
+};
+use reth_basic_payload_builder::{
+    BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig, BuildArguments, BuildOutcome,
+    PayloadBuilder, PayloadConfig,
+};
+use reth_chainspec::ChainSpec;
+use reth_ethereum_engine_primitives::EthPayloadAttributes;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_tracing::tracing::warn;
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::sync::broadcast::error::RecvError;
+use wvm_static::load_db_client;
+
+/// Length of an Arweave id encoded in base64url without padding.
+const ARWEAVE_ID_LEN: usize = 43;
+
+/// Number of upcoming blocks the archived ids are resolved for, so the id a block records is
+/// usually known before its payload is built and gets another chance if it's not confirmed yet.
+const RESOLVE_AHEAD: u64 = 2;
+
+/// Maximum number of resolved ids kept.
+const MAX_ARCHIVE_IDS: usize = 64;
+
+/// Decodes a base64url Arweave id into its 32 bytes.
+pub fn decode_arweave_id(id: &str) -> Option<B256> {
+    if id.len() != ARWEAVE_ID_LEN {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(B256::len_bytes());
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for c in id.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => return None,
+        };
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    Some(B256::from_slice(&bytes))
+}
+
+/// Arweave ids of archived blocks confirmed in LoadDb, keyed by block number.
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveIds(Arc<RwLock<BTreeMap<u64, B256>>>);
+
+impl ArchiveIds {
+    /// Arweave id of the archived block `block_number`, if it's been resolved.
+    pub fn get(&self, block_number: u64) -> Option<B256> {
+        self.0.read().get(&block_number).copied()
+    }
+
+    /// Records the Arweave id of the archived block `block_number`, dropping the oldest ids.
+    pub fn insert(&self, block_number: u64, id: B256) {
+        let mut ids = self.0.write();
+        ids.insert(block_number, id);
+        while ids.len() > MAX_ARCHIVE_IDS {
+            ids.pop_first();
+        }
+    }
+}
+
+/// Resolves from LoadDb the Arweave ids the blocks after `tip` record, if the archive registry is
+/// active for them.
+pub async fn resolve_archive_ids(chain_spec: &ChainSpec, ids: &ArchiveIds, tip: u64) {
+    for block_number in tip + 1..=tip + RESOLVE_AHEAD {
+        let Some(archived_block) = chain_spec
+            .archive_registry_delay_at_block(block_number)
+            .and_then(|delay| block_number.checked_sub(delay))
+        else {
+            continue;
+        };
+        if ids.get(archived_block).is_some() {
+            continue;
+        }
+
+        let load_db = match load_db_client() {
+            Ok(load_db) => load_db,
+            Err(e) => {
+                warn!(target: "wvm::archive_registry", archived_block, %e, "LoadDb is unavailable");
+                return;
+            }
+        };
+        let state = match load_db.query_raw_state(archived_block.to_string()).await {
+            Ok(state) => state,
+            Err(e) => {
+                warn!(target: "wvm::archive_registry", archived_block, %e, "Archive lookup failed");
+                return;
+            }
+        };
+        if let Some(id) = state.and_then(|state| decode_arweave_id(&state.arweave_id)) {
+            ids.insert(archived_block, id);
+        }
+    }
+}
+
+/// Extra data of the block `block_number`, `None` if the archive registry is not active at this
+/// block.
+///
+/// It's the Arweave id of the archived block once it's been resolved, empty otherwise so nothing
+/// gets recorded.
+pub fn archive_registry_extra_data(
+    chain_spec: &ChainSpec,
+    ids: &ArchiveIds,
+    block_number: u64,
+) -> Option<Bytes> {
+    let delay = chain_spec.archive_registry_delay_at_block(block_number)?;
+    let id = block_number.checked_sub(delay).and_then(|archived_block| ids.get(archived_block));
+    Some(id.map(|id| Bytes::copy_from_slice(id.as_slice())).unwrap_or_default())
+}
+
+/// Ethereum payload builder setting the `extra_data` of the blocks it builds for the archive
+/// registry.
+#[derive(Debug, Clone)]
+pub struct WvmPayloadBuilder<EvmConfig> {
+    inner: reth_ethereum_payload_builder::EthereumPayloadBuilder<EvmConfig>,
+    archive_ids: ArchiveIds,
+}
+
+impl<EvmConfig> WvmPayloadBuilder<EvmConfig> {
+    pub const fn new(evm_config: EvmConfig, archive_ids: ArchiveIds) -> Self {
+        Self {
+            inner: reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config),
+            archive_ids,
+        }
+    }
+
+    fn with_archive_registry_extra_data(
+        &self,
+        chain_spec: &ChainSpec,
+        config: &mut PayloadConfig<EthPayloadBuilderAttributes>,
+    ) {
+        if let Some(extra_data) = archive_registry_extra_data(
+            chain_spec,
+            &self.archive_ids,
+            config.parent_header.number + 1,
+        ) {
+            config.extra_data = extra_data;
+        }
+    }
+}
+
+impl<EvmConfig, Pool, Client> PayloadBuilder<Pool, Client> for WvmPayloadBuilder<EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Header = Header>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec>,
+    Pool: TransactionPool,
+{
+    type Attributes = EthPayloadBuilderAttributes;
+    type BuiltPayload = EthBuiltPayload;
+
+    fn try_build(
+        &self,
+        mut args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        self.with_archive_registry_extra_data(&args.client.chain_spec(), &mut args.config);
+        self.inner.try_build(args)
+    }
+
+    fn build_empty_payload(
+        &self,
+        client: &Client,
+        mut config: PayloadConfig<Self::Attributes>,
+    ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        self.with_archive_registry_extra_data(&client.chain_spec(), &mut config);
+        <reth_ethereum_payload_builder::EthereumPayloadBuilder<EvmConfig> as PayloadBuilder<
+            Pool,
+            Client,
+        >>::build_empty_payload(&self.inner, client, config)
+    }
+}
+
+/// Payload service building blocks with [`WvmPayloadBuilder`].
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct WvmPayloadServiceBuilder;
+
+impl<Types, Node, Pool> PayloadServiceBuilder<Node, Pool> for WvmPayloadServiceBuilder
+where
+    Types: NodeTypesWithEngine<ChainSpec = ChainSpec>,
+    Node: FullNodeTypes<Types = Types>,
+    Pool: TransactionPool + Unpin + 'static,
+    Types::Engine: PayloadTypes<
+        BuiltPayload = EthBuiltPayload,
+        PayloadAttributes = EthPayloadAttributes,
+        PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+    >,
+{
+    async fn spawn_payload_service(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<PayloadBuilderHandle<Types::Engine>> {
+        // resolve the archived ids as blocks become canonical
+        let archive_ids = ArchiveIds::default();
+        let chain_spec = ctx.chain_spec();
+        let ids = archive_ids.clone();
+        let head = ctx.head().number;
+        let mut notifications = ctx.provider().subscribe_to_canonical_state();
+        ctx.task_executor().spawn(async move {
+            resolve_archive_ids(&chain_spec, &ids, head).await;
+            loop {
+                match notifications.recv().await {
+                    Ok(notification) => {
+                        resolve_archive_ids(&chain_spec, &ids, notification.tip().number).await
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+
+        let payload_builder =
+            WvmPayloadBuilder::new(EthEvmConfig::new(ctx.chain_spec()), archive_ids);
+        let conf = ctx.payload_builder_config();
+
+        let payload_job_config = BasicPayloadJobGeneratorConfig::default()
+            .interval(conf.interval())
+            .deadline(conf.deadline())
+            .max_payload_tasks(conf.max_payload_tasks())
+            .extradata(conf.extradata_bytes());
+
+        let payload_generator = BasicPayloadJobGenerator::with_builder(
+            ctx.provider().clone(),
+            pool,
+            ctx.task_executor().clone(),
+            payload_job_config,
+            payload_builder,
+        );
+        let (payload_service, payload_builder) =
+            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
+
+        ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
+
+        Ok(payload_builder)
+    }
+}
+
+#[cfg(test)]
+mod archive_registry_tests {
+    use crate::archive_registry::{
+        archive_registry_extra_data, decode_arweave_id, ArchiveIds, MAX_ARCHIVE_IDS,
+    };
+    use alloy_primitives::{b256, Bytes, B256};
+    use reth_chainspec::{BlockSchedule, ChainSpec, WvmChainParams};
+
+    #[test]
+    pub fn test_decode_arweave_id() {
+        assert_eq!(
+            decode_arweave_id("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8"),
+            Some(b256!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"))
+        );
+        assert_eq!(
+            decode_arweave_id("_-8AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+            Some(b256!("ffef000000000000000000000000000000000000000000000000000000000000"))
+        );
+        assert_eq!(decode_arweave_id("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="), None);
+        assert_eq!(decode_arweave_id("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh+"), None);
+    }
+
+    #[test]
+    pub fn test_archive_registry_extra_data() {
+        let chain_spec = ChainSpec {
+            wvm: WvmChainParams {
+                archive_registry_delay: BlockSchedule::from([(10, 2)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let ids = ArchiveIds::default();
+        let id = B256::repeat_byte(1);
+        ids.insert(10, id);
+
+        assert_eq!(archive_registry_extra_data(&chain_spec, &ids, 9), None);
+        assert_eq!(archive_registry_extra_data(&chain_spec, &ids, 11), Some(Bytes::new()));
+        assert_eq!(
+            archive_registry_extra_data(&chain_spec, &ids, 12),
+            Some(Bytes::copy_from_slice(id.as_slice()))
+        );
+
+        // only the latest ids are kept
+        for block_number in 11..=10 + MAX_ARCHIVE_IDS as u64 {
+            ids.insert(block_number, id);
+        }
+        assert_eq!(ids.get(10), None);
+        assert_eq!(ids.get(11), Some(id));
+    }
+}
@@ -17,7 +17,9 @@ pub const SOLANA_SILLY_PRIVATE_KEY: &str =
     "kNykCXNxgePDjFbDWjPNvXQRa8U12Ywc19dFVaQ7tebUj3m7H4sF4KKdJwM7yxxb3rqxchdjezX9Szh8bLcQAjb";
 
 fn arweave_upload(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let input = to_brotli(input.to_vec());
+    let input = to_brotli(input).map_err(|_| {
+        PrecompileErrors::Error(PrecompileError::Other("Data could not be compressed".to_string()))
+    })?;
     let data_size = input.len();
     let gas_used: u64 = (10_000 + data_size * 3) as u64;
 
@@ -69,6 +71,7 @@ mod arupload_pc_tests {
     use std::env;
 
     #[test]
+    #[ignore = "uploads to the live Irys bundler"]
     pub fn test_arweave_precompile() {
         let input = Bytes::from("Hello world".as_bytes());
         unsafe {
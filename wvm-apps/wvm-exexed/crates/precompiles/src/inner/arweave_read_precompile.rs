@@ -1,6 +1,6 @@
 use crate::inner::{
     graphql_util::{build_transaction_query, send_graphql},
-    util::{clean_gateway_url, download_tx, DEFAULT_ARWEAVE_TX_ENDPOINT},
+    util::{clean_gateway_url, download_tx, ARWEAVE_GATEWAY, DEFAULT_ARWEAVE_TX_ENDPOINT},
 };
 use alloy_primitives::Bytes;
 use reth::primitives::revm_primitives::{
@@ -48,7 +48,10 @@ struct NodeData {
 }
 
 pub fn parse_gateway_content(input: &str) -> (String, String) {
-    let default_endpoint = DEFAULT_ARWEAVE_TX_ENDPOINT;
+    parse_gateway_content_with_default(input, DEFAULT_ARWEAVE_TX_ENDPOINT)
+}
+
+pub fn parse_gateway_content_with_default(input: &str, default_endpoint: &str) -> (String, String) {
     let mut parts = input.split(';');
     let first_part = parts.next().unwrap_or(default_endpoint);
     let second_part = parts.next().unwrap_or(first_part);
@@ -59,6 +62,16 @@ pub fn parse_gateway_content(input: &str) -> (String, String) {
 }
 
 fn arweave_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    arweave_read_from(input, gas_limit, ARWEAVE_GATEWAY.as_str())
+}
+
+/// Same as [`arweave_read`] but resolving transactions against `default_gateway` when the input
+/// doesn't specify one.
+pub(crate) fn arweave_read_from(
+    input: &Bytes,
+    gas_limit: u64,
+    default_gateway: &str,
+) -> PrecompileResult {
     let data_size = input.len();
     let gas_used: u64 = (ARWEAVE_PC_READ_BASE as usize + data_size * 3) as u64;
 
@@ -76,9 +89,10 @@ fn arweave_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
 
     let res = match id_str {
         Ok(id) => {
-            let (gateway, tx_id) = parse_gateway_content(id.as_str());
+            let (gateway, tx_id) = parse_gateway_content_with_default(id.as_str(), default_gateway);
             let clean_gateway = clean_gateway_url(gateway.as_str());
-            let query = build_transaction_query(Some(&[tx_id.clone()]), None, None, None, true);
+            let query =
+                build_transaction_query(Some(&[tx_id.clone()]), None, None, None, true, &[]);
 
             let data = send_graphql(clean_gateway.as_str(), query.as_str());
             let tx_size = match data {
@@ -113,14 +127,83 @@ fn arweave_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
 
 #[cfg(test)]
 mod arweave_read_pc_tests {
-    use crate::inner::arweave_read_precompile::{arweave_read, parse_gateway_content};
+    use crate::inner::{
+        arweave_read_precompile::{
+            arweave_read, arweave_read_from, parse_gateway_content,
+            parse_gateway_content_with_default,
+        },
+        test_utils::MockGateway,
+    };
     use alloy_primitives::Bytes;
     use borsh::BorshDeserialize;
+    use proptest::prelude::*;
     use reth::primitives::revm_primitives::PrecompileOutput;
     use std::time::Instant;
     use wvm_borsh::block::BorshSealedBlockWithSenders;
 
+    const HELLO_WORLD_TX: &str = "bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI";
+
+    #[test]
+    pub fn test_arweave_read_precompile_offline() {
+        let mut gateway = MockGateway::new();
+        gateway.graphql("arweave_graphql_tx.json");
+        gateway.tx_data(HELLO_WORLD_TX, "Hello world".as_bytes());
+
+        let input = Bytes::from(HELLO_WORLD_TX.as_bytes());
+        let PrecompileOutput { bytes, .. } =
+            arweave_read_from(&input, 100_000, gateway.url().as_str()).unwrap();
+        assert_eq!(bytes.to_vec(), "Hello world".as_bytes().to_vec());
+
+        // gateway can also be given in the input
+        let input = Bytes::from(format!("{};{}", gateway.url(), HELLO_WORLD_TX).into_bytes());
+        let PrecompileOutput { bytes, .. } =
+            arweave_read_from(&input, 100_000, "http://unreachable.invalid").unwrap();
+        assert_eq!(bytes.to_vec(), "Hello world".as_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_arweave_read_precompile_too_big_offline() {
+        let mut gateway = MockGateway::new();
+        gateway.graphql("arweave_graphql_tx_too_big.json");
+
+        let input = Bytes::from(HELLO_WORLD_TX.as_bytes());
+        let res = arweave_read_from(&input, 100_000, gateway.url().as_str());
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Arweave Transaction size is greater than allowed (18mb)"
+        );
+    }
+
+    #[test]
+    pub fn test_arweave_read_precompile_missing_tx_offline() {
+        let mut gateway = MockGateway::new();
+        gateway.graphql("graphql_empty.json");
+
+        let input = Bytes::from(HELLO_WORLD_TX.as_bytes());
+        assert!(arweave_read_from(&input, 100_000, gateway.url().as_str()).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_parse_gateway_content(gateway in "[^;]+", tx_id in "[^;]*") {
+            let (endpoint, id) =
+                parse_gateway_content_with_default(&format!("{gateway};{tx_id}"), "default");
+            prop_assert_eq!(endpoint, gateway);
+            prop_assert_eq!(id, tx_id.clone());
+
+            let (endpoint, id) = parse_gateway_content_with_default(&tx_id, "default");
+            prop_assert_eq!(endpoint, "default");
+            prop_assert_eq!(id, tx_id);
+        }
+
+        #[test]
+        fn proptest_parse_gateway_content_never_panics(input in ".*") {
+            let _ = parse_gateway_content(&input);
+        }
+    }
+
     #[test]
+    #[ignore = "hits live gateways, see test_arweave_read_precompile_offline"]
     pub fn test_arweave_read_precompile() {
         std::env::set_var("CAREFUL_TOKIO", "false");
         let input = Bytes::from("bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI".as_bytes());
@@ -132,6 +215,7 @@ mod arweave_read_pc_tests {
     }
 
     #[test]
+    #[ignore = "hits live gateways"]
     pub fn test_arweave_read_precompile_custom_gateway() {
         std::env::set_var("DURATION_SECONDS", "20000");
         let input =
@@ -155,6 +239,7 @@ mod arweave_read_pc_tests {
     }
 
     #[tokio::test]
+    #[ignore = "hits live gateways"]
     pub async fn test_graphql() {
         let client = reqwest::Client::builder().build().unwrap();
 
@@ -186,6 +271,7 @@ mod arweave_read_pc_tests {
     }
 
     #[tokio::test]
+    #[ignore = "hits live gateways"]
     pub async fn test_graphql_ureq() {
         let data = r#"
 {
@@ -0,0 +1,249 @@
+use alloy_primitives::Bytes;
+use rbrotli::{
+    codec::{read_with_limit, CodecError},
+    from_brotli, to_brotli,
+};
+use reth::primitives::revm_primitives::{
+    Precompile, PrecompileError, PrecompileErrors, PrecompileOutput, PrecompileResult,
+};
+
+pub const COMPRESS_PC: Precompile = Precompile::Standard(compress_pc);
+pub const DECOMPRESS_PC: Precompile = Precompile::Standard(decompress_pc);
+
+pub const COMPRESSION_PC_BASE: u64 = 3_000;
+/// Gas charged per byte read by the codec.
+pub const COMPRESSION_PC_INPUT_BYTE: u64 = 6;
+/// Gas charged per byte written by the codec.
+pub const COMPRESSION_PC_OUTPUT_BYTE: u64 = 3;
+
+/// Hard upper bound of any decompressed payload, regardless of the ratio.
+pub const DECOMPRESS_MAX_OUTPUT: usize = 4 * 1024 * 1024; // 4MB
+/// Maximum `output / input` ratio accepted by the decompression precompile.
+/// Anything above this is treated as a decompression bomb.
+pub const DECOMPRESS_MAX_RATIO: usize = 32;
+
+pub const ZSTD_LEVEL: i32 = 3;
+/// Largest zstd window accepted when decompressing, `2^22` bytes or [`DECOMPRESS_MAX_OUTPUT`].
+/// Frames declaring a larger window are rejected before the decoder allocates it.
+pub const ZSTD_WINDOW_LOG_MAX: u32 = 22;
+
+/// Codecs supported by the compression precompiles. The codec is selected by the first byte of
+/// the precompile input, the rest of the input is the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionCodec {
+    Brotli = 0,
+    Zstd = 1,
+}
+
+impl TryFrom<u8> for CompressionCodec {
+    type Error = PrecompileErrors;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CompressionCodec::Brotli),
+            1 => Ok(CompressionCodec::Zstd),
+            _ => Err(PrecompileErrors::Error(PrecompileError::Other(
+                "Unknown compression codec".to_string(),
+            ))),
+        }
+    }
+}
+
+fn gas_for(input_len: usize, output_len: usize) -> u64 {
+    COMPRESSION_PC_BASE +
+        input_len as u64 * COMPRESSION_PC_INPUT_BYTE +
+        output_len as u64 * COMPRESSION_PC_OUTPUT_BYTE
+}
+
+fn split_codec(input: &Bytes) -> Result<(CompressionCodec, &[u8]), PrecompileErrors> {
+    let (codec, payload) = input.split_first().ok_or_else(|| {
+        PrecompileErrors::Error(PrecompileError::Other(
+            "A codec and payload must be provided".to_string(),
+        ))
+    })?;
+
+    if payload.is_empty() {
+        return Err(PrecompileErrors::Error(PrecompileError::Other(
+            "Payload cannot be empty".to_string(),
+        )));
+    }
+
+    Ok((CompressionCodec::try_from(*codec)?, payload))
+}
+
+/// Maximum number of bytes a payload of `input_len` bytes is allowed to decompress into.
+pub fn max_decompressed_size(input_len: usize) -> usize {
+    input_len.saturating_mul(DECOMPRESS_MAX_RATIO).min(DECOMPRESS_MAX_OUTPUT)
+}
+
+fn compress_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let (codec, payload) = split_codec(input)?;
+
+    // Charge for the input up-front so we never compress data we can't pay for.
+    if gas_for(payload.len(), 0) > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+    }
+
+    let compressed = match codec {
+        CompressionCodec::Brotli => to_brotli(payload).map_err(CodecError::from),
+        CompressionCodec::Zstd => {
+            zstd::stream::encode_all(payload, ZSTD_LEVEL).map_err(CodecError::from)
+        }
+    };
+
+    let compressed = compressed.map_err(|e| {
+        PrecompileErrors::Error(PrecompileError::Other(format!(
+            "Data could not be compressed: {}",
+            e
+        )))
+    })?;
+
+    let gas_used = gas_for(payload.len(), compressed.len());
+    if gas_used > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+    }
+
+    Ok(PrecompileOutput::new(gas_used, compressed.into()))
+}
+
+fn decompress_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    let (codec, payload) = split_codec(input)?;
+
+    if gas_for(payload.len(), 0) > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+    }
+
+    // Never decompress more than what the caller could pay for, nor more than the ratio allows.
+    let affordable =
+        ((gas_limit - gas_for(payload.len(), 0)) / COMPRESSION_PC_OUTPUT_BYTE) as usize;
+    let limit = max_decompressed_size(payload.len()).min(affordable);
+
+    let decompressed = match codec {
+        CompressionCodec::Brotli => from_brotli(payload, limit).map_err(CodecError::from),
+        CompressionCodec::Zstd => zstd_decode_with_limit(payload, limit),
+    };
+
+    let decompressed = decompressed.map_err(|e| match e {
+        // Running out of gas is only reported when the gas, not the ratio, capped the output.
+        CodecError::OutputTooLarge { .. } if limit == affordable => {
+            PrecompileErrors::Error(PrecompileError::OutOfGas)
+        }
+        CodecError::OutputTooLarge { .. } => PrecompileErrors::Error(PrecompileError::Other(
+            "Data exceeds the allowed decompression ratio".to_string(),
+        )),
+        e => PrecompileErrors::Error(PrecompileError::Other(format!(
+            "Data could not be decompressed: {}",
+            e
+        ))),
+    })?;
+
+    let gas_used = gas_for(payload.len(), decompressed.len());
+    if gas_used > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+    }
+
+    Ok(PrecompileOutput::new(gas_used, decompressed.into()))
+}
+
+/// Decodes a zstd frame reading at most `limit` bytes of output.
+fn zstd_decode_with_limit(data: &[u8], limit: usize) -> Result<Vec<u8>, CodecError> {
+    let mut decoder = zstd::stream::read::Decoder::new(data)?;
+    decoder.window_log_max(ZSTD_WINDOW_LOG_MAX)?;
+    read_with_limit(decoder, limit)
+}
+
+#[cfg(test)]
+mod compression_pc_tests {
+    use crate::inner::compression_precompile::{
+        compress_pc, decompress_pc, max_decompressed_size, CompressionCodec, DECOMPRESS_MAX_OUTPUT,
+        DECOMPRESS_MAX_RATIO, ZSTD_LEVEL, ZSTD_WINDOW_LOG_MAX,
+    };
+    use alloy_primitives::Bytes;
+    use reth::primitives::revm_primitives::{PrecompileError, PrecompileErrors};
+    use std::io::Write;
+
+    fn with_codec(codec: CompressionCodec, data: &[u8]) -> Bytes {
+        let mut input = vec![codec as u8];
+        input.extend_from_slice(data);
+        Bytes::from(input)
+    }
+
+    #[test]
+    pub fn test_compress_roundtrip() {
+        let data = "Hello world ".repeat(64);
+
+        for codec in [CompressionCodec::Brotli, CompressionCodec::Zstd] {
+            let compressed = compress_pc(&with_codec(codec, data.as_bytes()), 100_000).unwrap();
+            assert!(compressed.bytes.len() < data.len());
+
+            let decompressed =
+                decompress_pc(&with_codec(codec, &compressed.bytes), 100_000).unwrap();
+            assert_eq!(decompressed.bytes.to_vec(), data.as_bytes().to_vec());
+            assert!(decompressed.gas_used > compressed.gas_used);
+        }
+    }
+
+    #[test]
+    pub fn test_invalid_input() {
+        assert!(compress_pc(&Bytes::new(), 100_000).is_err());
+        assert!(compress_pc(&Bytes::from(vec![0u8]), 100_000).is_err());
+        assert!(compress_pc(&Bytes::from(vec![9u8, 1, 2, 3]), 100_000).is_err());
+        assert!(decompress_pc(&with_codec(CompressionCodec::Zstd, b"garbage"), 100_000).is_err());
+    }
+
+    #[test]
+    pub fn test_out_of_gas() {
+        let input = with_codec(CompressionCodec::Brotli, "Hello world".as_bytes());
+        let res = compress_pc(&input, 100);
+        assert!(matches!(res, Err(PrecompileErrors::Error(PrecompileError::OutOfGas))));
+    }
+
+    #[test]
+    pub fn test_decompression_bomb() {
+        let bomb = vec![0u8; 1024 * 1024];
+
+        for codec in [CompressionCodec::Brotli, CompressionCodec::Zstd] {
+            let compressed = compress_pc(&with_codec(codec, &bomb), u64::MAX).unwrap();
+            assert!(compressed.bytes.len() * DECOMPRESS_MAX_RATIO < bomb.len());
+
+            let res = decompress_pc(&with_codec(codec, &compressed.bytes), u64::MAX);
+            assert!(matches!(res, Err(PrecompileErrors::Error(PrecompileError::Other(_)))));
+        }
+    }
+
+    #[test]
+    pub fn test_decompress_out_of_gas() {
+        let data = "Hello world ".repeat(64);
+
+        for codec in [CompressionCodec::Brotli, CompressionCodec::Zstd] {
+            let compressed = compress_pc(&with_codec(codec, data.as_bytes()), 100_000).unwrap();
+            let input = with_codec(codec, &compressed.bytes);
+            let gas = decompress_pc(&input, 100_000).unwrap().gas_used;
+
+            assert_eq!(decompress_pc(&input, gas).unwrap().gas_used, gas);
+            let res = decompress_pc(&input, gas - 1);
+            assert!(matches!(res, Err(PrecompileErrors::Error(PrecompileError::OutOfGas))));
+        }
+    }
+
+    #[test]
+    pub fn test_zstd_window_limit() {
+        assert_eq!(1 << ZSTD_WINDOW_LOG_MAX, DECOMPRESS_MAX_OUTPUT);
+
+        // Without a known content size the frame declares the full window.
+        let data = "Hello world ".repeat(64);
+        let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL).unwrap();
+        encoder.window_log(ZSTD_WINDOW_LOG_MAX + 1).unwrap();
+        encoder.write_all(data.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let res = decompress_pc(&with_codec(CompressionCodec::Zstd, &compressed), 100_000);
+        assert!(matches!(res, Err(PrecompileErrors::Error(PrecompileError::Other(_)))));
+    }
+
+    #[test]
+    pub fn test_max_decompressed_size() {
+        assert_eq!(max_decompressed_size(10), 10 * DECOMPRESS_MAX_RATIO);
+        assert_eq!(max_decompressed_size(usize::MAX), DECOMPRESS_MAX_OUTPUT);
+    }
+}
@@ -8,7 +8,7 @@ use alloy_primitives::Bytes;
 use load_db::LoadDbConnection;
 use revm_primitives::{Precompile, PrecompileError, PrecompileErrors, PrecompileResult};
 use serde_json::Value;
-use wvm_static::{internal_block, PRECOMPILE_LOADDB_CLIENT};
+use wvm_static::{internal_block, load_db_client};
 
 pub const GBQ_READ_PC: Precompile = Precompile::Standard(gbq_read);
 
@@ -39,7 +39,7 @@ fn gbq_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     //         };
     //
     //         // It needs to be obtained OUTSIDE the thread
-    //         let wvm_bgc = (&*PRECOMPILE_LOADDB_CLIENT).clone();
+    //         let wvm_bgc = load_db_client()?;
     //
     //         let res_from_bgc =
     //             internal_block(async { wvm_bgc.query_state(block_id.to_string()).await
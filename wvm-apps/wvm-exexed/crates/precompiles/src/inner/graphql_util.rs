@@ -62,6 +62,7 @@ pub fn build_transaction_query(
     owners: Option<&[String]>,
     order: Option<String>,
     include_data_size: bool, // New parameter to control inclusion of data { size }
+    fields: &[&str],         // Further fields selected on each node, e.g. `block { height }`
 ) -> String {
     let mut query = String::new();
 
@@ -106,6 +107,10 @@ pub fn build_transaction_query(
         query.push_str("        data {\n          size\n        }\n");
     }
 
+    for field in fields {
+        query.push_str(&format!("        {}\n", field));
+    }
+
     query.push_str("      }\n    }\n  }\n}\n");
 
     query
@@ -119,5 +124,5 @@ pub fn send_graphql(gateway: &str, query: &str) -> Result<Response, Error> {
             "query": query
         }));
 
-    res.unwrap().into_json::<Response>().map_err(|e| Error::new(e))
+    res.map_err(|e| Error::new(e))?.into_json::<Response>().map_err(|e| Error::new(e))
 }
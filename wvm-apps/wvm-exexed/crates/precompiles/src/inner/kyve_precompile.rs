@@ -3,6 +3,7 @@ use alloy_primitives::Bytes;
 use revm_primitives::{
     Precompile, PrecompileError, PrecompileErrors, PrecompileOutput, PrecompileResult,
 };
+use std::cell::LazyCell;
 use wvm_static::internal_block;
 
 pub const KYVE_PC_BASE: u64 = 10_000;
@@ -10,29 +11,26 @@ pub const KYVE_API_URL: &str = "https://data.services.kyve.network";
 
 pub const KYVE_READ_PC: Precompile = Precompile::Standard(kyve_read);
 
-fn kyve_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let data_size = input.len();
-    let gas_used: u64 = (KYVE_PC_BASE as usize + data_size * 3) as u64;
+/// KYVE data services endpoint, can be overridden with `KYVE_API_URL`.
+pub const KYVE_API: LazyCell<String> =
+    LazyCell::new(|| std::env::var("KYVE_API_URL").unwrap_or(KYVE_API_URL.to_string()));
 
-    if input.is_empty() {
-        return Err(PrecompileErrors::Error(PrecompileError::Other(
-            "A block number and field must be provided".to_string(),
-        )));
-    }
+/// First Ethereum block with blob sidecars available on KYVE.
+pub const KYVE_FIRST_BLOCK: usize = 19426589;
 
-    if gas_used > gas_limit {
-        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
-    }
+/// Field of the blob sidecars response requested through the precompile.
+#[derive(Debug, PartialEq, Eq)]
+pub enum KyveField {
+    Slot,
+    Blob { index: usize, field: String },
+}
 
-    let input_str = match String::from_utf8(input.0.to_vec()) {
-        Ok(s) => s,
-        Err(_) => {
-            return Err(PrecompileErrors::Error(PrecompileError::Other(
-                "Invalid input".to_string(),
-            )));
-        }
-    };
+fn invalid(msg: &str) -> PrecompileErrors {
+    PrecompileErrors::Error(PrecompileError::Other(msg.to_string()))
+}
 
+/// Parses `<block_number>;<blob_index>.<field>` inputs.
+pub fn parse_kyve_input(input_str: &str) -> Result<(usize, KyveField), PrecompileErrors> {
     let (block_number, field) = {
         let mut parts = input_str.split(";");
         let block_number = parts.next();
@@ -41,103 +39,192 @@ fn kyve_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
         (block_number, field)
     };
 
-    if block_number.is_none() {
-        return Err(PrecompileErrors::Error(PrecompileError::Other(
-            "A block number must be provided".to_string(),
-        )));
-    } else if field.is_none() {
-        return Err(PrecompileErrors::Error(PrecompileError::Other(
-            "Field must be provided".to_string(),
-        )));
+    let Some(blk_number) = block_number else {
+        return Err(invalid("A block number must be provided"));
+    };
+    let Some(field) = field else {
+        return Err(invalid("Field must be provided"));
+    };
+
+    let usize_blk_number =
+        blk_number.parse::<usize>().map_err(|_| invalid("Invalid Block Number"))?;
+
+    if !(usize_blk_number >= KYVE_FIRST_BLOCK) {
+        return Err(invalid("Can only read from block 19426589"));
     }
 
-    let blk_number = block_number.unwrap();
-    let usize_blk_number = blk_number.to_string().parse::<usize>().map_err(|_| {
-        PrecompileErrors::Error(PrecompileError::Other("Invalid Block Number".to_string()))
-    })?;
+    Ok((usize_blk_number, parse_kyve_field(field)?))
+}
+
+/// Parses the `<blob_index>.<field>` part of the input.
+pub fn parse_kyve_field(field: &str) -> Result<KyveField, PrecompileErrors> {
+    let (blob_indx, field) =
+        field.split_once('.').ok_or_else(|| invalid("Invalid field format"))?;
+
+    if field == "slot" {
+        return Ok(KyveField::Slot);
+    }
+
+    let index = blob_indx.parse::<usize>().map_err(|_| invalid("Invalid blob index"))?;
+
+    Ok(KyveField::Blob { index, field: field.to_string() })
+}
+
+fn kyve_read(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    kyve_read_from(input, gas_limit, KYVE_API.as_str())
+}
 
-    if !(usize_blk_number >= 19426589) {
-        return Err(PrecompileErrors::Error(PrecompileError::Other(
-            "Can only read from block 19426589".to_string(),
-        )));
+/// Same as [`kyve_read`] but querying the given KYVE endpoint.
+pub(crate) fn kyve_read_from(input: &Bytes, gas_limit: u64, api_url: &str) -> PrecompileResult {
+    let data_size = input.len();
+    let gas_used: u64 = (KYVE_PC_BASE as usize + data_size * 3) as u64;
+
+    if input.is_empty() {
+        return Err(invalid("A block number and field must be provided"));
+    }
+
+    if gas_used > gas_limit {
+        return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
     }
 
-    let field = field.unwrap();
+    let input_str = match String::from_utf8(input.0.to_vec()) {
+        Ok(s) => s,
+        Err(_) => {
+            return Err(invalid("Invalid input"));
+        }
+    };
+
+    let (blk_number, field) = parse_kyve_input(&input_str)?;
 
     let req = ureq::get(
-        format!("{}/ethereum/beacon/blob_sidecars?block_height={}", KYVE_API_URL, blk_number)
-            .as_str(),
+        format!("{}/ethereum/beacon/blob_sidecars?block_height={}", api_url, blk_number).as_str(),
     )
     .timeout((&*REQ_TIMEOUT).clone())
     .call();
 
     match req {
         Ok(resp) => {
-            let json_val = resp.into_json::<serde_json::Value>().map_err(|_| {
-                PrecompileErrors::Error(PrecompileError::Other(
-                    "Invalid Response from server".to_string(),
-                ))
-            })?;
+            let json_val = resp
+                .into_json::<serde_json::Value>()
+                .map_err(|_| invalid("Invalid Response from server"))?;
 
-            let main_val = json_val.get("value").ok_or_else(|| {
-                PrecompileErrors::Error(PrecompileError::Other("Missing 'value' field".to_string()))
-            })?;
+            let main_val = json_val.get("value").ok_or_else(|| invalid("Missing 'value' field"))?;
 
             let slot = main_val
                 .get("slot")
                 .and_then(|s| s.as_u64())
-                .ok_or_else(|| {
-                    PrecompileErrors::Error(PrecompileError::Other(
-                        "Missing or invalid 'slot' field".to_string(),
-                    ))
-                })?
+                .ok_or_else(|| invalid("Missing or invalid 'slot' field"))?
                 .to_string();
 
-            let blobs = main_val.get("blobs").and_then(|b| b.as_array()).ok_or_else(|| {
-                PrecompileErrors::Error(PrecompileError::Other(
-                    "Missing or invalid 'blobs' field".to_string(),
-                ))
-            })?;
-
-            let (blob_indx, field) = field.split_once('.').ok_or_else(|| {
-                PrecompileErrors::Error(PrecompileError::Other("Invalid field format".to_string()))
-            })?;
-
-            if field == "slot" {
-                return Ok(PrecompileOutput::new(gas_used, slot.into_bytes().into()));
-            }
+            let blobs = main_val
+                .get("blobs")
+                .and_then(|b| b.as_array())
+                .ok_or_else(|| invalid("Missing or invalid 'blobs' field"))?;
 
-            let blob_index = blob_indx.parse::<usize>().map_err(|_| {
-                PrecompileErrors::Error(PrecompileError::Other("Invalid blob index".to_string()))
-            })?;
+            let (blob_index, field) = match field {
+                KyveField::Slot => {
+                    return Ok(PrecompileOutput::new(gas_used, slot.into_bytes().into()))
+                }
+                KyveField::Blob { index, field } => (index, field),
+            };
 
-            let get_field = blobs.get(blob_index).ok_or_else(|| {
-                PrecompileErrors::Error(PrecompileError::Other(
-                    "Blob index does not exist".to_string(),
-                ))
-            })?;
+            let get_field =
+                blobs.get(blob_index).ok_or_else(|| invalid("Blob index does not exist"))?;
 
-            let field_val = get_field.get(field).and_then(|val| val.as_str()).ok_or_else(|| {
-                PrecompileErrors::Error(PrecompileError::Other("Field does not exist".to_string()))
-            })?;
+            let field_val = get_field
+                .get(field.as_str())
+                .and_then(|val| val.as_str())
+                .ok_or_else(|| invalid("Field does not exist"))?;
 
             Ok(PrecompileOutput::new(gas_used, field_val.to_string().into_bytes().into()))
         }
         Err(e) => {
             println!("{:?}", e);
-            Err(PrecompileErrors::Error(PrecompileError::Other(
-                "Could not connect with KYVE".to_string(),
-            )))
+            Err(invalid("Could not connect with KYVE"))
         }
     }
 }
 
 #[cfg(test)]
 mod kyve_tests {
-    use crate::inner::kyve_precompile::kyve_read;
+    use crate::inner::{
+        kyve_precompile::{
+            kyve_read, kyve_read_from, parse_kyve_field, parse_kyve_input, KyveField,
+        },
+        test_utils::MockGateway,
+    };
     use alloy_primitives::Bytes;
+    use proptest::prelude::*;
+
+    #[test]
+    pub fn test_kyve_precompile_offline() {
+        let mut kyve = MockGateway::new();
+        kyve.kyve_blob_sidecars(20033062, "kyve_blob_sidecars.json");
+
+        let input = Bytes::from("20033062;0.kzg_commitment".as_bytes());
+        let read = kyve_read_from(&input, 100_000, kyve.url().as_str()).unwrap();
+        assert_eq!(String::from_utf8(read.bytes.to_vec()).unwrap(), "0x81eb4254a890fd840a6bc60de54fb6fcd3b91242153386b9e83337f00f641a12bf6ebd876134e8703edce6725e29046c");
+
+        let input = Bytes::from("20033062;0.slot".as_bytes());
+        let read = kyve_read_from(&input, 100_000, kyve.url().as_str()).unwrap();
+        assert_eq!(String::from_utf8(read.bytes.to_vec()).unwrap(), "9238016");
+
+        let input = Bytes::from("20033062;7.kzg_commitment".as_bytes());
+        let read = kyve_read_from(&input, 100_000, kyve.url().as_str());
+        assert_eq!("Blob index does not exist", read.err().unwrap().to_string());
+
+        let input = Bytes::from("20033062;0.unknown".as_bytes());
+        let read = kyve_read_from(&input, 100_000, kyve.url().as_str());
+        assert_eq!("Field does not exist", read.err().unwrap().to_string());
+    }
+
+    #[test]
+    pub fn test_kyve_precompile_unavailable_offline() {
+        let kyve = MockGateway::new();
+
+        let input = Bytes::from("20033062;0.kzg_commitment".as_bytes());
+        let read = kyve_read_from(&input, 100_000, kyve.url().as_str());
+        assert_eq!("Could not connect with KYVE", read.err().unwrap().to_string());
+    }
+
+    #[test]
+    pub fn test_parse_kyve_input() {
+        assert_eq!(parse_kyve_input("20033062;0.slot").unwrap(), (20033062, KyveField::Slot));
+        assert_eq!(
+            parse_kyve_input("20033062;1.blob").unwrap(),
+            (20033062, KyveField::Blob { index: 1, field: "blob".to_string() })
+        );
+        assert!(parse_kyve_input("20033062").is_err());
+        assert!(parse_kyve_input("abc;0.slot").is_err());
+        assert!(parse_kyve_input("20033062;slot").is_err());
+        assert!(parse_kyve_input("20033062;a.blob").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_parse_kyve_input_never_panics(input in ".*") {
+            let _ = parse_kyve_input(&input);
+        }
+
+        #[test]
+        fn proptest_parse_kyve_field(index in any::<usize>(), field in "[a-z_]+") {
+            let parsed = parse_kyve_field(&format!("{index}.{field}")).unwrap();
+            if field == "slot" {
+                prop_assert_eq!(parsed, KyveField::Slot);
+            } else {
+                prop_assert_eq!(parsed, KyveField::Blob { index, field });
+            }
+        }
+
+        #[test]
+        fn proptest_parse_kyve_block_number(block in any::<usize>()) {
+            let parsed = parse_kyve_input(&format!("{block};0.slot"));
+            prop_assert_eq!(parsed.is_ok(), block >= 19426589);
+        }
+    }
 
     #[test]
+    #[ignore = "hits live KYVE services, see test_kyve_precompile_offline"]
     pub fn test_kyve_precompile() {
         let input = Bytes::from("20033062;0.kzg_commitment".as_bytes());
         let read = kyve_read(&input, 100_000).unwrap();
@@ -154,6 +241,7 @@ mod kyve_tests {
     }
 
     #[test]
+    #[ignore = "hits live KYVE services, see test_kyve_precompile_offline"]
     pub fn test_kyve_precompile_slot() {
         let input = Bytes::from("20033062;0.slot".as_bytes());
         let read = kyve_read(&input, 100_000).unwrap();
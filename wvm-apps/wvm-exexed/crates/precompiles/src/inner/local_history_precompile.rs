@@ -0,0 +1,377 @@
+use crate::inner::{
+    string_block::from_sealed_block_senders,
+    wvm_block_precompile::{process_block_to_field, process_pc_response_from_str_bytes},
+};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Bytes, B256};
+use reth::{
+    primitives::{
+        revm_primitives::{Precompile, PrecompileResult},
+        BlockBody, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    },
+    providers::{BlockReader, HeaderProvider, ProviderResult, ReceiptProvider, TransactionVariant},
+    revm::{ContextStatefulPrecompile, Database, InnerEvmContext},
+};
+use reth_prune_types::MINIMUM_PRUNING_DISTANCE;
+use revm_primitives::{PrecompileError, PrecompileErrors};
+use std::{fmt, sync::Arc};
+
+/// Registry entry of the local history precompile. It's replaced by a [`LocalHistoryPrecompile`]
+/// reading the node's history when the EVM is built, see
+/// [`crate::wvm_node_config::WvmEthEvmConfig::with_local_history`].
+pub const LOCAL_HISTORY_PC: Precompile = Precompile::Standard(local_history_unavailable);
+
+/// Cost of a header lookup.
+pub const LOCAL_HISTORY_PC_HEADER_BASE: u64 = 5_000;
+/// Cost of a block body (transactions and senders) lookup.
+pub const LOCAL_HISTORY_PC_BODY_BASE: u64 = 15_000;
+/// Cost of a lookup of the receipts of a block.
+pub const LOCAL_HISTORY_PC_RECEIPTS_BASE: u64 = 15_000;
+/// Cost per returned byte.
+pub const LOCAL_HISTORY_PC_OUTPUT_BYTE: u64 = 3;
+
+/// Minimum depth of the blocks that can be read, below the block being executed. Two epochs,
+/// shallower blocks could still be reorged.
+pub const LOCAL_HISTORY_MIN_DEPTH: u64 = 64;
+/// Maximum depth of the blocks that can be read. Headers and bodies are never pruned, and every
+/// prune config has to keep at least [`MINIMUM_PRUNING_DISTANCE`] blocks of receipts, so all the
+/// nodes can serve the whole window. Receipts pruned by a log filter make the call revert.
+pub const LOCAL_HISTORY_WINDOW: u64 = MINIMUM_PRUNING_DISTANCE;
+
+/// Read access to the node's own chain history, by block hash.
+pub trait WvmHistoryProvider: Send + Sync + 'static {
+    fn sealed_header(&self, hash: B256) -> ProviderResult<Option<SealedHeader>>;
+    fn sealed_block_with_senders(
+        &self,
+        hash: B256,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>>;
+    fn receipts(&self, hash: B256) -> ProviderResult<Option<Vec<Receipt>>>;
+}
+
+impl<T> WvmHistoryProvider for T
+where
+    T: BlockReader + Send + Sync + 'static,
+{
+    fn sealed_header(&self, hash: B256) -> ProviderResult<Option<SealedHeader>> {
+        Ok(HeaderProvider::header(self, &hash)?.map(|header| SealedHeader::new(header, hash)))
+    }
+
+    fn sealed_block_with_senders(
+        &self,
+        hash: B256,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        BlockReader::sealed_block_with_senders(self, hash.into(), TransactionVariant::WithHash)
+    }
+
+    fn receipts(&self, hash: B256) -> ProviderResult<Option<Vec<Receipt>>> {
+        ReceiptProvider::receipts_by_block(self, hash.into())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HistoryQuery {
+    /// Any header field supported by [`process_block_to_field`].
+    Header(String),
+    /// `transactions` and `size` fields, which need the block body.
+    Body(String),
+    /// `tx.<index>`, the EIP-2718 encoded transaction.
+    Transaction(usize),
+    /// `receipt.<index>`, the EIP-2718 encoded receipt of the transaction.
+    Receipt(usize),
+}
+
+impl HistoryQuery {
+    pub const fn base_gas(&self) -> u64 {
+        match self {
+            HistoryQuery::Header(_) => LOCAL_HISTORY_PC_HEADER_BASE,
+            HistoryQuery::Body(_) | HistoryQuery::Transaction(_) => LOCAL_HISTORY_PC_BODY_BASE,
+            HistoryQuery::Receipt(_) => LOCAL_HISTORY_PC_RECEIPTS_BASE,
+        }
+    }
+}
+
+/// Parses `<block_number>;<field>` inputs.
+pub fn parse_history_input(input: &str) -> Option<(u64, HistoryQuery)> {
+    let (block_number, field) = input.split_once(';')?;
+    let block_number = block_number.trim().parse::<u64>().ok()?;
+
+    let query = match field.split_once('.') {
+        Some(("tx", index)) => HistoryQuery::Transaction(index.parse().ok()?),
+        Some(("receipt", index)) => HistoryQuery::Receipt(index.parse().ok()?),
+        Some(_) => return None,
+        None if field == "transactions" || field == "size" => HistoryQuery::Body(field.to_string()),
+        None if !field.is_empty() => HistoryQuery::Header(field.to_string()),
+        None => return None,
+    };
+
+    Some((block_number, query))
+}
+
+/// Whether `block_number` can be read from a block at height `current`, see
+/// [`LOCAL_HISTORY_MIN_DEPTH`] and [`LOCAL_HISTORY_WINDOW`].
+pub const fn is_in_history_window(block_number: u64, current: u64) -> bool {
+    block_number + LOCAL_HISTORY_MIN_DEPTH <= current &&
+        block_number + LOCAL_HISTORY_WINDOW >= current
+}
+
+fn other_err(msg: &str) -> PrecompileErrors {
+    PrecompileErrors::Error(PrecompileError::Other(msg.to_string()))
+}
+
+/// Missing or pruned history only reverts the call, the block is still executed.
+fn not_available_err() -> PrecompileErrors {
+    other_err("Local history is not available for this block")
+}
+
+fn local_history_unavailable(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+    Err(PrecompileErrors::Fatal { msg: "Local history is not available".to_string() })
+}
+
+/// Local history precompile. Blocks are resolved through the ancestry of the block being
+/// executed, then read by hash from the node's database.
+#[derive(Clone)]
+pub struct LocalHistoryPrecompile {
+    provider: Arc<dyn WvmHistoryProvider>,
+}
+
+impl LocalHistoryPrecompile {
+    pub fn new<P: WvmHistoryProvider>(provider: P) -> Self {
+        Self { provider: Arc::new(provider) }
+    }
+
+    fn read_block(&self, hash: B256) -> Result<SealedBlockWithSenders, PrecompileErrors> {
+        match self.provider.sealed_block_with_senders(hash) {
+            Ok(Some(block)) => Ok(block),
+            _ => Err(not_available_err()),
+        }
+    }
+}
+
+impl fmt::Debug for LocalHistoryPrecompile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalHistoryPrecompile").finish_non_exhaustive()
+    }
+}
+
+impl<DB: Database> ContextStatefulPrecompile<DB> for LocalHistoryPrecompile {
+    fn call(
+        &self,
+        input: &Bytes,
+        gas_limit: u64,
+        evmctx: &mut InnerEvmContext<DB>,
+    ) -> PrecompileResult {
+        if input.is_empty() {
+            return Err(other_err("A block number and field must be provided"));
+        }
+
+        let input_str =
+            String::from_utf8(input.0.to_vec()).map_err(|_| other_err("Invalid input"))?;
+        let (block_number, query) =
+            parse_history_input(&input_str).ok_or_else(|| other_err("Invalid input"))?;
+
+        let base_gas = query.base_gas();
+        if base_gas > gas_limit {
+            return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+        }
+
+        if !is_in_history_window(block_number, evmctx.env.block.number.saturating_to::<u64>()) {
+            return Err(other_err("Block is outside of the local history window"));
+        }
+
+        // the hash comes from the ancestry of the block being executed, not from the canonical
+        // chain of the node
+        let hash = match evmctx.db.block_hash(block_number) {
+            Ok(hash) if hash != B256::ZERO => hash,
+            _ => return Err(not_available_err()),
+        };
+
+        let output = match query {
+            HistoryQuery::Header(field) => {
+                let header = match self.provider.sealed_header(hash) {
+                    Ok(Some(header)) => header,
+                    _ => return Err(not_available_err()),
+                };
+                let block = SealedBlockWithSenders {
+                    block: SealedBlock::new(header, BlockBody::default()),
+                    senders: vec![],
+                };
+                process_block_to_field(field, from_sealed_block_senders(block))
+            }
+            HistoryQuery::Body(field) => {
+                process_block_to_field(field, from_sealed_block_senders(self.read_block(hash)?))
+            }
+            HistoryQuery::Transaction(index) => {
+                let block = self.read_block(hash)?;
+                block.body.transactions.get(index).map(|tx| tx.encoded_2718())
+            }
+            HistoryQuery::Receipt(index) => {
+                let receipts = match self.provider.receipts(hash) {
+                    Ok(Some(receipts)) => receipts,
+                    _ => return Err(not_available_err()),
+                };
+                receipts.into_iter().nth(index).map(|receipt| receipt.with_bloom().encoded_2718())
+            }
+        };
+
+        let gas_used =
+            base_gas + output.as_ref().map_or(0, |o| o.len() as u64) * LOCAL_HISTORY_PC_OUTPUT_BYTE;
+        if gas_used > gas_limit {
+            return Err(PrecompileErrors::Error(PrecompileError::OutOfGas));
+        }
+
+        process_pc_response_from_str_bytes(gas_used, output)
+    }
+}
+
+#[cfg(test)]
+mod local_history_pc_tests {
+    use crate::inner::local_history_precompile::{
+        is_in_history_window, parse_history_input, HistoryQuery, LocalHistoryPrecompile,
+        WvmHistoryProvider, LOCAL_HISTORY_MIN_DEPTH, LOCAL_HISTORY_PC_BODY_BASE,
+        LOCAL_HISTORY_PC_HEADER_BASE, LOCAL_HISTORY_PC_OUTPUT_BYTE, LOCAL_HISTORY_PC_RECEIPTS_BASE,
+        LOCAL_HISTORY_WINDOW,
+    };
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Bytes, B256, U256};
+    use reth::{
+        primitives::{
+            revm_primitives::PrecompileResult, Receipt, SealedBlockWithSenders, SealedHeader,
+            TxType,
+        },
+        providers::ProviderResult,
+        revm::{db::EmptyDB, ContextStatefulPrecompile, InnerEvmContext},
+    };
+    use revm_primitives::{PrecompileError, PrecompileErrors};
+
+    /// History with a single block, returned for any hash. `receipts` is `None` when they were
+    /// pruned.
+    struct MockHistory {
+        receipts: Option<Vec<Receipt>>,
+    }
+
+    impl WvmHistoryProvider for MockHistory {
+        fn sealed_header(&self, _hash: B256) -> ProviderResult<Option<SealedHeader>> {
+            Ok(Some(SealedHeader::default()))
+        }
+
+        fn sealed_block_with_senders(
+            &self,
+            _hash: B256,
+        ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+            Ok(Some(SealedBlockWithSenders::default()))
+        }
+
+        fn receipts(&self, _hash: B256) -> ProviderResult<Option<Vec<Receipt>>> {
+            Ok(self.receipts.clone())
+        }
+    }
+
+    fn call_history(receipts: Option<Vec<Receipt>>, input: &str, current: u64) -> PrecompileResult {
+        let precompile = LocalHistoryPrecompile::new(MockHistory { receipts });
+        let mut ctx = InnerEvmContext::new(EmptyDB::default());
+        ctx.env.block.number = U256::from(current);
+        precompile.call(&Bytes::from(input.as_bytes().to_vec()), 1_000_000, &mut ctx)
+    }
+
+    fn receipt() -> Receipt {
+        Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_parse_history_input() {
+        assert_eq!(
+            parse_history_input("10;hash"),
+            Some((10, HistoryQuery::Header("hash".to_string())))
+        );
+        assert_eq!(
+            parse_history_input("10;transactions"),
+            Some((10, HistoryQuery::Body("transactions".to_string())))
+        );
+        assert_eq!(parse_history_input("10;tx.3"), Some((10, HistoryQuery::Transaction(3))));
+        assert_eq!(parse_history_input("10;receipt.0"), Some((10, HistoryQuery::Receipt(0))));
+
+        assert_eq!(parse_history_input("10"), None);
+        assert_eq!(parse_history_input("10;"), None);
+        assert_eq!(parse_history_input("abc;hash"), None);
+        assert_eq!(parse_history_input("10;tx.a"), None);
+        assert_eq!(parse_history_input("10;logs.1"), None);
+        assert_eq!(parse_history_input("10;receipt.a"), None);
+    }
+
+    #[test]
+    pub fn test_lookup_gas() {
+        assert_eq!(
+            HistoryQuery::Header("hash".to_string()).base_gas(),
+            LOCAL_HISTORY_PC_HEADER_BASE
+        );
+        assert_eq!(HistoryQuery::Transaction(0).base_gas(), LOCAL_HISTORY_PC_BODY_BASE);
+        assert_eq!(HistoryQuery::Receipt(0).base_gas(), LOCAL_HISTORY_PC_RECEIPTS_BASE);
+    }
+
+    #[test]
+    pub fn test_receipt_lookup() {
+        let current = LOCAL_HISTORY_WINDOW + 1_000;
+        let block = current - LOCAL_HISTORY_MIN_DEPTH;
+        let encoded = receipt().with_bloom().encoded_2718();
+
+        let output =
+            call_history(Some(vec![receipt()]), &format!("{};receipt.0", block), current).unwrap();
+        assert_eq!(output.bytes, Bytes::from(encoded.clone()));
+        assert_eq!(
+            output.gas_used,
+            LOCAL_HISTORY_PC_RECEIPTS_BASE + encoded.len() as u64 * LOCAL_HISTORY_PC_OUTPUT_BYTE
+        );
+
+        // a missing index is an unknown field, the call reverts
+        assert!(matches!(
+            call_history(Some(vec![receipt()]), &format!("{};receipt.1", block), current),
+            Err(PrecompileErrors::Error(PrecompileError::Other(_)))
+        ));
+    }
+
+    #[test]
+    pub fn test_pruned_receipts_revert() {
+        let current = LOCAL_HISTORY_WINDOW + 1_000;
+        let block = current - LOCAL_HISTORY_MIN_DEPTH;
+
+        assert!(matches!(
+            call_history(None, &format!("{};receipt.0", block), current),
+            Err(PrecompileErrors::Error(PrecompileError::Other(_)))
+        ));
+    }
+
+    #[test]
+    pub fn test_outside_window_reverts() {
+        let current = LOCAL_HISTORY_WINDOW + 1_000;
+        let receipts = Some(vec![receipt()]);
+
+        let too_old = current - LOCAL_HISTORY_WINDOW - 1;
+        assert!(matches!(
+            call_history(receipts.clone(), &format!("{};receipt.0", too_old), current),
+            Err(PrecompileErrors::Error(PrecompileError::Other(_)))
+        ));
+        let too_recent = current - LOCAL_HISTORY_MIN_DEPTH + 1;
+        assert!(matches!(
+            call_history(receipts, &format!("{};hash", too_recent), current),
+            Err(PrecompileErrors::Error(PrecompileError::Other(_)))
+        ));
+    }
+
+    #[test]
+    pub fn test_history_window() {
+        let current = LOCAL_HISTORY_WINDOW + 1_000;
+
+        assert!(is_in_history_window(current - LOCAL_HISTORY_MIN_DEPTH, current));
+        assert!(is_in_history_window(current - LOCAL_HISTORY_WINDOW, current));
+        assert!(!is_in_history_window(current - LOCAL_HISTORY_MIN_DEPTH + 1, current));
+        assert!(!is_in_history_window(current - LOCAL_HISTORY_WINDOW - 1, current));
+        assert!(!is_in_history_window(current, current));
+        assert!(!is_in_history_window(current + 1, current));
+        assert!(!is_in_history_window(0, LOCAL_HISTORY_MIN_DEPTH - 1));
+    }
+}
@@ -1,19 +1,30 @@
 use crate::inner::{
-    arweave_precompile::ARWEAVE_UPLOAD_PC, arweave_read_precompile::ARWEAVE_READ_PC,
-    gbq_precompile::GBQ_READ_PC, kyve_precompile::KYVE_READ_PC, test_precompile::HELLO_WORLD_PC,
+    arweave_precompile::ARWEAVE_UPLOAD_PC,
+    arweave_read_precompile::ARWEAVE_READ_PC,
+    compression_precompile::{COMPRESS_PC, DECOMPRESS_PC},
+    gbq_precompile::GBQ_READ_PC,
+    kyve_precompile::KYVE_READ_PC,
+    local_history_precompile::LOCAL_HISTORY_PC,
+    test_precompile::HELLO_WORLD_PC,
     wvm_block_precompile::WVM_BLOCK_PC,
 };
-use reth::revm::precompile::{u64_to_address, PrecompileWithAddress};
+use alloy_primitives::Address;
+use reth::revm::precompile::{u64_to_address, Precompile, PrecompileWithAddress};
+use revm_primitives::SpecId;
 use std::{cell::LazyCell, time::Duration};
 
 pub mod arweave_precompile;
 mod arweave_read_precompile;
+pub mod compression_precompile;
 mod gbq_precompile;
 pub mod graphql_util;
 mod kyve_precompile;
+pub mod local_history_precompile;
 mod string_block;
 mod test_precompile;
-mod util;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod util;
 mod wvm_block_precompile;
 
 fn hex_to_u64(hex_str: &str) -> u64 {
@@ -40,29 +51,68 @@ pub const REQ_SIZE: LazyCell<u64> = LazyCell::new(|| {
         .unwrap_or((1024 * 1024) * 4)
 });
 
-pub fn wvm_precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
+/// First (decimal) slot of the registry, see [`wvm_precompiles`].
+const PC_START_ADDR: i32 = 17;
+
+/// WVM precompile registry.
+///
+/// Each entry is the precompile and the fork from which it is available. Addresses are assigned
+/// by position, starting at `0x17`, so new precompiles must always be appended.
+fn pc_registry() -> [(Precompile, SpecId); 9] {
     // ORDER OF THINGS MATTER
     // ORDER OF THINGS MATTER
+    [
+        (ARWEAVE_UPLOAD_PC, SpecId::FRONTIER),
+        (ARWEAVE_READ_PC, SpecId::FRONTIER),
+        (HELLO_WORLD_PC, SpecId::FRONTIER),
+        (WVM_BLOCK_PC, SpecId::FRONTIER),
+        (KYVE_READ_PC, SpecId::FRONTIER),
+        (GBQ_READ_PC, SpecId::FRONTIER),
+        // 0x23
+        (COMPRESS_PC, SpecId::PRAGUE),
+        // 0x24
+        (DECOMPRESS_PC, SpecId::PRAGUE),
+        // 0x25
+        (LOCAL_HISTORY_PC, SpecId::PRAGUE),
+    ]
+}
 
-    let pcs_funcs = [
-        ARWEAVE_UPLOAD_PC,
-        ARWEAVE_READ_PC,
-        HELLO_WORLD_PC,
-        WVM_BLOCK_PC,
-        KYVE_READ_PC,
-        GBQ_READ_PC,
-    ];
+fn pc_address(slot: i32) -> Address {
+    u64_to_address(hex_to_u64(format!("0x{}", slot).as_str()))
+}
+
+/// Address of [`LOCAL_HISTORY_PC`].
+pub fn local_history_pc_address() -> Address {
+    pc_address(25)
+}
+
+/// Returns the fork at which the WVM precompile at `address` gets activated, `None` if the
+/// address is not part of the registry.
+pub fn pc_activation(address: &Address) -> Option<SpecId> {
+    pc_registry()
+        .into_iter()
+        .zip(PC_START_ADDR..)
+        .find(|(_, slot)| pc_address(*slot) == *address)
+        .map(|((_, activation), _)| activation)
+}
+
+/// Whether the WVM precompile at `address` is active under `spec_id`. Addresses that are not
+/// part of the registry are always considered active.
+pub fn is_pc_active(address: &Address, spec_id: SpecId) -> bool {
+    pc_activation(address).map_or(true, |activation| spec_id.is_enabled_in(activation))
+}
+
+pub fn wvm_precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
     let mut pcs = vec![];
 
     // IT MATTERS BC OF THIS
-    let mut start_addr = 17;
+    let mut start_addr = PC_START_ADDR;
     let blocked_pcs = std::env::var("BLOCKED_PC").unwrap_or("".to_string());
     let blocked_pcs = blocked_pcs.split(",").collect::<Vec<&str>>();
 
-    for pc in pcs_funcs.into_iter() {
+    for (pc, _) in pc_registry().into_iter() {
         if !is_pc_blocked(&blocked_pcs, start_addr) {
-            let addr = hex_to_u64(format!("0x{}", start_addr).as_str());
-            pcs.push(PrecompileWithAddress(u64_to_address(addr), pc));
+            pcs.push(PrecompileWithAddress(pc_address(start_addr), pc));
         }
         start_addr = start_addr + 1;
     }
@@ -71,9 +121,12 @@ pub fn wvm_precompiles() -> impl Iterator<Item = PrecompileWithAddress> {
 }
 #[cfg(test)]
 mod pc_inner_tests {
-    use crate::inner::{is_pc_blocked, wvm_precompiles};
+    use crate::inner::{
+        is_pc_active, is_pc_blocked, local_history_pc_address, pc_activation, wvm_precompiles,
+    };
     use reth::revm::precompile::u64_to_address;
     use reth_revm::precompile::PrecompileWithAddress;
+    use revm_primitives::SpecId;
 
     #[test]
     pub fn test_verify_pc() {
@@ -85,11 +138,26 @@ mod pc_inner_tests {
         assert!(!is_pc_blocked(&vec, 1));
         let pcs = wvm_precompiles();
         let pcs: Vec<PrecompileWithAddress> = pcs.collect();
-        assert_eq!(pcs.len(), 4);
+        assert_eq!(pcs.len(), 7);
 
         std::env::remove_var("BLOCKED_PC");
         let mut get_pcs = wvm_precompiles();
         let first = get_pcs.next().unwrap();
         assert_eq!(first.0, u64_to_address(0x17));
     }
+
+    #[test]
+    pub fn test_pc_activation() {
+        assert_eq!(pc_activation(&u64_to_address(0x17)), Some(SpecId::FRONTIER));
+        assert_eq!(pc_activation(&u64_to_address(0x23)), Some(SpecId::PRAGUE));
+        assert_eq!(pc_activation(&u64_to_address(0x24)), Some(SpecId::PRAGUE));
+        assert_eq!(pc_activation(&u64_to_address(0x25)), Some(SpecId::PRAGUE));
+        assert_eq!(local_history_pc_address(), u64_to_address(0x25));
+        assert_eq!(pc_activation(&u64_to_address(0x26)), None);
+
+        assert!(is_pc_active(&u64_to_address(0x17), SpecId::CANCUN));
+        assert!(!is_pc_active(&u64_to_address(0x23), SpecId::CANCUN));
+        assert!(is_pc_active(&u64_to_address(0x23), SpecId::PRAGUE));
+        assert!(is_pc_active(&u64_to_address(0x01), SpecId::CANCUN));
+    }
 }
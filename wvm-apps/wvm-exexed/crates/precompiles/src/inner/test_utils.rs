@@ -0,0 +1,74 @@
+//! Offline gateway used by the precompile tests. Responses are served from the recorded
+//! fixtures under `test_fixtures/` so tests don't depend on live Arweave or KYVE services.
+
+use mockito::{Matcher, Mock, Server, ServerGuard};
+
+pub(crate) fn fixture(name: &str) -> String {
+    let path = format!("{}/test_fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("Missing test fixture {}", path))
+}
+
+pub(crate) struct MockGateway {
+    server: ServerGuard,
+    mocks: Vec<Mock>,
+}
+
+impl MockGateway {
+    pub(crate) fn new() -> Self {
+        Self { server: Server::new(), mocks: vec![] }
+    }
+
+    pub(crate) fn url(&self) -> String {
+        self.server.url()
+    }
+
+    /// Answers every `/graphql` query with the given fixture.
+    pub(crate) fn graphql(&mut self, fixture_name: &str) -> &mut Self {
+        let mock = self
+            .server
+            .mock("POST", "/graphql")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(fixture(fixture_name))
+            .create();
+        self.mocks.push(mock);
+        self
+    }
+
+    /// Answers every `/graphql` query with the given status code and an empty body.
+    pub(crate) fn graphql_status(&mut self, status: usize) -> &mut Self {
+        let mock = self.server.mock("POST", "/graphql").with_status(status).create();
+        self.mocks.push(mock);
+        self
+    }
+
+    /// Serves `data` as the content of the Arweave transaction `id`.
+    pub(crate) fn tx_data(&mut self, id: &str, data: &[u8]) -> &mut Self {
+        let mock = self
+            .server
+            .mock("GET", format!("/{}", id).as_str())
+            .with_status(200)
+            .with_body(data)
+            .create();
+        self.mocks.push(mock);
+        self
+    }
+
+    /// Serves the KYVE blob sidecars of `block_height` from the given fixture.
+    pub(crate) fn kyve_blob_sidecars(
+        &mut self,
+        block_height: usize,
+        fixture_name: &str,
+    ) -> &mut Self {
+        let mock = self
+            .server
+            .mock("GET", "/ethereum/beacon/blob_sidecars")
+            .match_query(Matcher::UrlEncoded("block_height".into(), block_height.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(fixture(fixture_name))
+            .create();
+        self.mocks.push(mock);
+        self
+    }
+}
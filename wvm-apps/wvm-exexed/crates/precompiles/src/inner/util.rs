@@ -1,10 +1,20 @@
 use crate::inner::{REQ_SIZE, REQ_TIMEOUT};
 use alloy_primitives::Bytes;
 use revm_primitives::{PrecompileError, PrecompileErrors, PrecompileOutput};
-use std::io::Read;
+use std::{cell::LazyCell, io::Read};
 use wvm_static::internal_block;
 
 pub const DEFAULT_ARWEAVE_TX_ENDPOINT: &str = "https://arweave.net/";
+pub const DEFAULT_IRYS_GATEWAY: &str = "https://arweave.mainnet.irys.xyz";
+
+/// Gateway used by the precompiles when the input doesn't specify one.
+pub const ARWEAVE_GATEWAY: LazyCell<String> = LazyCell::new(|| {
+    std::env::var("ARWEAVE_GATEWAY_URL").unwrap_or(DEFAULT_ARWEAVE_TX_ENDPOINT.to_string())
+});
+
+/// Gateway used as a fallback when the primary gateway can't resolve a query.
+pub const IRYS_GATEWAY: LazyCell<String> =
+    LazyCell::new(|| std::env::var("IRYS_GATEWAY_URL").unwrap_or(DEFAULT_IRYS_GATEWAY.to_string()));
 
 pub fn clean_gateway_url(gateway: &str) -> String {
     let clean_gateway =
@@ -2,12 +2,12 @@ use crate::{
     inner::{
         graphql_util::{build_transaction_query, send_graphql, Edge},
         string_block::Block,
-        util::{clean_gateway_url, download_tx, DEFAULT_ARWEAVE_TX_ENDPOINT},
+        util::{clean_gateway_url, download_tx, ARWEAVE_GATEWAY, IRYS_GATEWAY},
     },
     WVM_DATA_PUBLISHERS,
 };
 use alloy_primitives::Bytes;
-use rbrotli::from_brotli;
+use rbrotli::codec::{CodecError, ARCHIVE_CODECS, DICTIONARY_TAG, ENCODING_TAG, MAX_ARCHIVE_SIZE};
 use reth::primitives::revm_primitives::{Precompile, PrecompileOutput, PrecompileResult};
 use revm_primitives::{PrecompileError, PrecompileErrors};
 use wvm_borsh::block::BorshSealedBlockWithSenders;
@@ -16,9 +16,17 @@ use wvm_static::internal_block;
 pub const WVM_BLOCK_PC: Precompile = Precompile::Standard(wvm_read_block_pc);
 
 pub const WVM_BLOCK_PC_READ_BASE: u64 = 10_000;
+/// Gas charged per byte of decompressed archive.
+pub const WVM_BLOCK_PC_DECOMPRESSED_BYTE: u64 = 1;
 
 pub fn parse_req_input(input: &str) -> (String, String, String) {
-    let default_endpoint = DEFAULT_ARWEAVE_TX_ENDPOINT;
+    parse_req_input_with_default(input, ARWEAVE_GATEWAY.as_str())
+}
+
+pub fn parse_req_input_with_default(
+    input: &str,
+    default_endpoint: &str,
+) -> (String, String, String) {
     let mut parts = input.split(';');
 
     let first_part = parts.next().unwrap_or("");
@@ -56,6 +64,16 @@ fn fetch_with_fallback(primary_gateway: &str, fallback_gateway: &str, query: &st
 }
 
 fn wvm_read_block_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    wvm_read_block_from(input, gas_limit, ARWEAVE_GATEWAY.as_str(), IRYS_GATEWAY.as_str())
+}
+
+/// Same as [`wvm_read_block_pc`] but with explicit default and fallback gateways.
+pub(crate) fn wvm_read_block_from(
+    input: &Bytes,
+    gas_limit: u64,
+    default_gateway: &str,
+    fallback_gateway: &str,
+) -> PrecompileResult {
     let data_size = input.len();
     let gas_used: u64 = (WVM_BLOCK_PC_READ_BASE as usize + data_size * 3) as u64;
 
@@ -73,7 +91,8 @@ fn wvm_read_block_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
 
     match block_id {
         Ok(input_data) => {
-            let (gateway, block_id, field) = parse_req_input(input_data.as_str());
+            let (gateway, block_id, field) =
+                parse_req_input_with_default(input_data.as_str(), default_gateway);
             if field.len() == 0 {
                 Err(PrecompileErrors::Error(PrecompileError::Other(
                     "A field must be specified".to_string(),
@@ -86,11 +105,12 @@ fn wvm_read_block_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
                     Some(&WVM_DATA_PUBLISHERS.map(|i| i.to_string())),
                     None,
                     false,
+                    &[],
                 );
 
                 let edge = fetch_with_fallback(
                     clean_gateway.as_str(),
-                    "https://arweave.mainnet.irys.xyz",
+                    clean_gateway_url(fallback_gateway).as_str(),
                     query.as_str(),
                 );
 
@@ -104,47 +124,60 @@ fn wvm_read_block_pc(input: &Bytes, gas_limit: u64) -> PrecompileResult {
                 };
 
                 let tags = edge.node.tags.unwrap_or_else(Vec::new);
-                let encoding =
-                    tags.iter().find(|i| i.name == "WeaveVM:Encoding").ok_or_else(|| {
-                        PrecompileErrors::Error(PrecompileError::Other(
-                            "Missing WeaveVM:Encoding tag".to_string(),
-                        ))
-                    })?;
+                let encoding = tags.iter().find(|i| i.name == ENCODING_TAG).ok_or_else(|| {
+                    PrecompileErrors::Error(PrecompileError::Other(format!(
+                        "Missing {} tag",
+                        ENCODING_TAG
+                    )))
+                })?;
+                let dictionary_id =
+                    tags.iter().find(|i| i.name == DICTIONARY_TAG).map(|i| i.value.as_str());
 
                 let get_data = download_tx(gas_used, clean_gateway.clone(), edge.node.id);
 
-                let output = match get_data {
+                // The archive is never decompressed into more than the caller can pay for.
+                let limit = ((gas_limit - gas_used) / WVM_BLOCK_PC_DECOMPRESSED_BYTE)
+                    .min(MAX_ARCHIVE_SIZE as u64) as usize;
+
+                match get_data {
                     Ok(resp) => {
-                        let bytes = resp.bytes.to_vec();
-                        match encoding.value.as_str() {
-                            "Borsh-Brotli" => {
-                                let unbrotli = from_brotli(bytes);
-                                let unborsh =
-                                    borsh::from_slice::<BorshSealedBlockWithSenders>(&unbrotli);
-
-                                let unborsh = unborsh.map_err(|_| {
-                                    PrecompileError::Other(
-                                        "Block could not be deserialized".to_string(),
-                                    )
-                                })?;
-
-                                let str_block = Block::from(unborsh);
-                                let data = process_block_to_field(field, str_block);
-                                process_pc_response_from_str_bytes(gas_used, data)
-                            }
-                            _ => Err(PrecompileErrors::Error(PrecompileError::Other(
-                                "Unknown encoding".to_string(),
-                            ))),
-                        }
+                        // Dictionaries are loaded when the node starts, never while executing.
+                        let borsh_data = ARCHIVE_CODECS
+                            .decompress(
+                                encoding.value.as_str(),
+                                dictionary_id,
+                                &resp.bytes,
+                                limit,
+                                |_| None,
+                            )
+                            .map_err(|e| match e {
+                                CodecError::OutputTooLarge { limit }
+                                    if limit < MAX_ARCHIVE_SIZE =>
+                                {
+                                    PrecompileError::OutOfGas
+                                }
+                                e => PrecompileError::Other(format!(
+                                    "Block could not be decompressed: {}",
+                                    e
+                                )),
+                            })?;
+                        let gas_used =
+                            gas_used + borsh_data.len() as u64 * WVM_BLOCK_PC_DECOMPRESSED_BYTE;
+                        let unborsh = borsh::from_slice::<BorshSealedBlockWithSenders>(&borsh_data)
+                            .map_err(|_| {
+                                PrecompileError::Other(
+                                    "Block could not be deserialized".to_string(),
+                                )
+                            })?;
+
+                        let str_block = Block::from(unborsh);
+                        let data = process_block_to_field(field, str_block);
+                        process_pc_response_from_str_bytes(gas_used, data)
                     }
                     Err(_) => Err(PrecompileErrors::Error(PrecompileError::Other(
-                        "Invalid data".to_string(),
+                        "Block could not be read from gateway".to_string(),
                     ))),
-                };
-
-                Ok(output.map_err(|_| {
-                    PrecompileError::Other("Block could not be read from gateway".to_string())
-                })?)
+                }
             }
         }
         Err(_) => Err(PrecompileErrors::Error(PrecompileError::Other(
@@ -191,50 +224,174 @@ pub fn process_block_to_field(field: String, str_block: Block) -> Option<Vec<u8>
     data
 }
 
-// TODO: Re-enable for future versions when there's blocks using the magic identifiers
-
 #[cfg(test)]
 mod arweave_read_pc_tests {
-    use crate::inner::wvm_block_precompile::wvm_read_block_pc;
+    use crate::inner::{
+        test_utils::MockGateway,
+        util::DEFAULT_ARWEAVE_TX_ENDPOINT,
+        wvm_block_precompile::{
+            parse_req_input_with_default, wvm_read_block_from, WVM_BLOCK_PC_DECOMPRESSED_BYTE,
+            WVM_BLOCK_PC_READ_BASE,
+        },
+    };
     use alloy_primitives::Bytes;
-    use reth::primitives::revm_primitives::PrecompileOutput;
-
-    // #[test]
-    // pub fn test_read_wvm_block() {
-    //     let input = Bytes::from("https://arweave.mainnet.irys.xyz;1127975;hash".as_bytes());
-    //     let PrecompileOutput { gas_used, bytes } = wvm_read_block_pc(&input, 100_000).unwrap();
-    //     assert_eq!(bytes.len(), 66);
-    //     assert_eq!(
-    //         bytes.to_vec(),
-    //         "0xe0201f1e284fbe6fa0c90e811194a11a694a08d240f4691996b9182f2e767fee"
-    //             .as_bytes()
-    //             .to_vec()
-    //     );
-    // }
-    //
-    // #[test]
-    // pub fn test_read_wvm_block_arweave_fallback() {
-    //     let input = Bytes::from("https://arweave.net;1127975;hash".as_bytes());
-    //     let PrecompileOutput { gas_used, bytes } = wvm_read_block_pc(&input, 100_000).unwrap();
-    //     assert_eq!(bytes.len(), 66);
-    //     assert_eq!(
-    //         bytes.to_vec(),
-    //         "0xe0201f1e284fbe6fa0c90e811194a11a694a08d240f4691996b9182f2e767fee"
-    //             .as_bytes()
-    //             .to_vec()
-    //     );
-    // }
-    //
-    // #[test]
-    // pub fn test_read_wvm_block_fix_test_141550_hash() {
-    //     let input = Bytes::from("141550;hash".as_bytes());
-    //     let PrecompileOutput { gas_used, bytes } = wvm_read_block_pc(&input, 100_000).unwrap();
-    //     assert_eq!(bytes.len(), 66);
-    //     assert_eq!(
-    //         bytes.to_vec(),
-    //         "0xb69e1a4a19c665b0573f74b2bf8e4824cb5b54176f4ad45b730f047e880cf5cc"
-    //             .as_bytes()
-    //             .to_vec()
-    //     );
-    // }
+    use proptest::prelude::*;
+    use rbrotli::codec::{ArchiveCodec, ZstdDictionary, ARCHIVE_CODECS};
+    use reth::primitives::{
+        revm_primitives::{PrecompileError, PrecompileErrors, PrecompileOutput},
+        SealedBlockWithSenders,
+    };
+    use std::sync::Arc;
+    use wvm_borsh::block::BorshSealedBlockWithSenders;
+    use wvm_tx::wvm::{v1::V1WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
+
+    const BLOCK_TX: &str = "wvm-block-tx";
+    const DICTIONARY_TX: &str = "wvm-dictionary-tx";
+
+    fn borsh_block() -> (Vec<u8>, String) {
+        let block = SealedBlockWithSenders::default();
+        let hash = block.hash().to_string();
+        let borsh_block = BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V1(
+            V1WvmSealedBlockWithSenders::from(block),
+        ));
+
+        (borsh::to_vec(&borsh_block).unwrap(), hash)
+    }
+
+    /// Archived block as uploaded by the Arweave actor.
+    fn archived_block() -> (Vec<u8>, String) {
+        let (data, hash) = borsh_block();
+        (ArchiveCodec::default().compress(&data).unwrap(), hash)
+    }
+
+    #[test]
+    pub fn test_read_wvm_block() {
+        let (data, hash) = archived_block();
+        let mut gateway = MockGateway::new();
+        gateway.graphql("arweave_graphql_block.json");
+        gateway.tx_data(BLOCK_TX, &data);
+
+        let input = Bytes::from("0;hash".as_bytes());
+        let PrecompileOutput { bytes, .. } =
+            wvm_read_block_from(&input, 100_000, gateway.url().as_str(), "http://127.0.0.1:1")
+                .unwrap();
+        assert_eq!(bytes.len(), 66);
+        assert_eq!(bytes.to_vec(), hash.as_bytes().to_vec());
+
+        let input = Bytes::from(format!("{};0;unknown_field", gateway.url()).into_bytes());
+        let res = wvm_read_block_from(&input, 100_000, "http://127.0.0.1:1", "http://127.0.0.1:1");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    pub fn test_read_wvm_block_fallback() {
+        let (data, hash) = archived_block();
+        let mut primary = MockGateway::new();
+        primary.graphql_status(500);
+        primary.tx_data(BLOCK_TX, &data);
+
+        let mut fallback = MockGateway::new();
+        fallback.graphql("arweave_graphql_block.json");
+
+        let input = Bytes::from("0;hash".as_bytes());
+        let PrecompileOutput { bytes, .. } =
+            wvm_read_block_from(&input, 100_000, primary.url().as_str(), fallback.url().as_str())
+                .unwrap();
+        assert_eq!(bytes.to_vec(), hash.as_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_read_wvm_block_zstd_dict() {
+        let (data, hash) = borsh_block();
+        // Any content can be used as a zstd dictionary, the block itself does fine here.
+        let dictionary =
+            ZstdDictionary { id: DICTIONARY_TX.to_string(), data: Arc::new(data.clone()) };
+        let codec = ArchiveCodec::ZstdDict { level: 3, dictionary: dictionary.clone() };
+
+        let mut gateway = MockGateway::new();
+        gateway.graphql("arweave_graphql_block_zstd_dict.json");
+        gateway.tx_data(BLOCK_TX, &codec.compress(&data).unwrap());
+        gateway.tx_data(DICTIONARY_TX, &data);
+
+        // The dictionary isn't downloaded while executing, even if the gateway has it.
+        let input = Bytes::from("0;hash".as_bytes());
+        assert!(ARCHIVE_CODECS.dictionary(DICTIONARY_TX).is_none());
+        let res =
+            wvm_read_block_from(&input, 100_000, gateway.url().as_str(), "http://127.0.0.1:1");
+        assert!(res.is_err());
+        assert!(ARCHIVE_CODECS.dictionary(DICTIONARY_TX).is_none());
+
+        ARCHIVE_CODECS.register_dictionary(dictionary);
+        let PrecompileOutput { bytes, .. } =
+            wvm_read_block_from(&input, 100_000, gateway.url().as_str(), "http://127.0.0.1:1")
+                .unwrap();
+        assert_eq!(bytes.to_vec(), hash.as_bytes().to_vec());
+    }
+
+    #[test]
+    pub fn test_read_wvm_block_charges_decompressed_bytes() {
+        let (data, _) = archived_block();
+        let (borsh_data, _) = borsh_block();
+        let mut gateway = MockGateway::new();
+        gateway.graphql("arweave_graphql_block.json");
+        gateway.tx_data(BLOCK_TX, &data);
+
+        let input = Bytes::from("0;hash".as_bytes());
+        let read_gas = WVM_BLOCK_PC_READ_BASE + input.len() as u64 * 3;
+        let decompressed_gas = borsh_data.len() as u64 * WVM_BLOCK_PC_DECOMPRESSED_BYTE;
+
+        let PrecompileOutput { gas_used, .. } = wvm_read_block_from(
+            &input,
+            read_gas + decompressed_gas,
+            gateway.url().as_str(),
+            "http://127.0.0.1:1",
+        )
+        .unwrap();
+        assert_eq!(gas_used, read_gas + decompressed_gas);
+
+        // One byte short, the archive isn't decompressed.
+        let res = wvm_read_block_from(
+            &input,
+            read_gas + decompressed_gas - 1,
+            gateway.url().as_str(),
+            "http://127.0.0.1:1",
+        );
+        assert!(matches!(res, Err(PrecompileErrors::Error(PrecompileError::OutOfGas))));
+    }
+
+    #[test]
+    pub fn test_read_wvm_block_unknown() {
+        let mut gateway = MockGateway::new();
+        gateway.graphql("graphql_empty.json");
+
+        let input = Bytes::from("0;hash".as_bytes());
+        let res =
+            wvm_read_block_from(&input, 100_000, gateway.url().as_str(), gateway.url().as_str());
+        assert_eq!(res.err().unwrap().to_string(), "Unknown Block");
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_parse_req_input_never_panics(input in ".*") {
+            let _ = parse_req_input_with_default(&input, DEFAULT_ARWEAVE_TX_ENDPOINT);
+        }
+
+        #[test]
+        fn proptest_parse_req_input_with_gateway(
+            gateway in "[^;]+",
+            block in "[0-9]{1,10}",
+            field in "[a-z_]+",
+        ) {
+            let input = format!("{gateway};{block};{field}");
+            let parsed = parse_req_input_with_default(&input, DEFAULT_ARWEAVE_TX_ENDPOINT);
+            prop_assert_eq!(parsed, (gateway, block, field));
+        }
+
+        #[test]
+        fn proptest_parse_req_input_default_gateway(block in "[0-9]{1,10}", field in "[a-z_]+") {
+            let input = format!("{block};{field}");
+            let parsed = parse_req_input_with_default(&input, DEFAULT_ARWEAVE_TX_ENDPOINT);
+            prop_assert_eq!(parsed, (DEFAULT_ARWEAVE_TX_ENDPOINT.to_string(), block, field));
+        }
+    }
 }
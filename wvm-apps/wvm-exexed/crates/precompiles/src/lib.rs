@@ -1,5 +1,6 @@
 extern crate core;
 
+pub mod archive_registry;
 pub mod inner;
 pub mod node;
 pub mod wvm_node_config;
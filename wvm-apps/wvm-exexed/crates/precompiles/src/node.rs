@@ -1,4 +1,11 @@
-use crate::{inner::wvm_precompiles, wvm_node_config::WvmEthEvmConfig};
+use crate::{
+    archive_registry::WvmPayloadServiceBuilder,
+    inner::{
+        local_history_precompile::{LocalHistoryPrecompile, WvmHistoryProvider},
+        wvm_precompiles,
+    },
+    wvm_node_config::WvmEthEvmConfig,
+};
 use reth::{
     api::{FullNodeTypes, NodeTypes, PayloadTypes},
     builder::{
@@ -14,11 +21,12 @@ use reth_node_builder::{NodeAdapter, NodeComponentsBuilder};
 use reth_node_ethereum::{
     node::{
         EthPrimitives, EthereumAddOns, EthereumConsensusBuilder, EthereumExecutorBuilder,
-        EthereumNetworkBuilder, EthereumPayloadBuilder, EthereumPoolBuilder,
+        EthereumNetworkBuilder, EthereumPoolBuilder,
     },
     BasicBlockExecutorProvider, EthEngineTypes,
 };
 use reth_trie_db::MerklePatriciaTrie;
+use std::sync::Arc;
 
 /// Type configuration for a regular Ethereum node.
 #[derive(Debug, Default, Clone, Copy)]
@@ -30,7 +38,7 @@ impl WvmEthereumNode {
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
         EthereumPoolBuilder,
-        EthereumPayloadBuilder,
+        WvmPayloadServiceBuilder,
         EthereumNetworkBuilder,
         WvmEthExecutorBuilder,
         EthereumConsensusBuilder,
@@ -46,7 +54,7 @@ impl WvmEthereumNode {
         ComponentsBuilder::default()
             .node_types::<Node>()
             .pool(EthereumPoolBuilder::default())
-            .payload(EthereumPayloadBuilder::default())
+            .payload(WvmPayloadServiceBuilder::default())
             .network(EthereumNetworkBuilder::default())
             .executor(WvmEthExecutorBuilder::default())
             .consensus(EthereumConsensusBuilder::default())
@@ -72,7 +80,7 @@ where
     type ComponentsBuilder = ComponentsBuilder<
         N,
         EthereumPoolBuilder,
-        EthereumPayloadBuilder,
+        WvmPayloadServiceBuilder,
         EthereumNetworkBuilder,
         WvmEthExecutorBuilder,
         EthereumConsensusBuilder,
@@ -107,15 +115,23 @@ where
         self,
         ctx: &BuilderContext<Node>,
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
-        let evm_config =
-            WvmEthEvmConfig::new(ctx.chain_spec(), Default::default(), wvm_precompiles());
-
-        Ok((
-            evm_config.clone(),
-            BasicBlockExecutorProvider::new(EthExecutionStrategyFactory::new(
-                ctx.chain_spec(),
-                evm_config,
-            )),
-        ))
+        // the local history precompile reads from the node's own database
+        Ok(wvm_block_executor(ctx.chain_spec(), ctx.provider().clone()))
     }
 }
+
+/// EVM configuration and block executor with the WVM precompiles, also used to execute blocks
+/// outside of a running node. `history` serves the local history precompile.
+pub fn wvm_block_executor(
+    chain_spec: Arc<ChainSpec>,
+    history: impl WvmHistoryProvider,
+) -> (WvmEthEvmConfig, BasicBlockExecutorProvider<EthExecutionStrategyFactory<WvmEthEvmConfig>>) {
+    let evm_config =
+        WvmEthEvmConfig::new(chain_spec.clone(), Default::default(), wvm_precompiles())
+            .with_local_history(LocalHistoryPrecompile::new(history));
+
+    (
+        evm_config.clone(),
+        BasicBlockExecutorProvider::new(EthExecutionStrategyFactory::new(chain_spec, evm_config)),
+    )
+}
@@ -15,6 +15,9 @@ use reth::{
     },
 };
 
+use crate::inner::{
+    is_pc_active, local_history_pc_address, local_history_precompile::LocalHistoryPrecompile,
+};
 use reth_evm_ethereum::revm_spec_by_timestamp_after_merge;
 
 use reth::{
@@ -22,6 +25,7 @@ use reth::{
     primitives::constants::EIP1559_INITIAL_BASE_FEE,
 };
 use reth_chainspec::ChainSpec;
+use reth_ethereum_consensus::next_block_gas_limit;
 use reth_node_ethereum::EthEvmConfig;
 use revm_primitives::{BlobExcessGasAndPrice, BlockEnv, CfgEnv, EnvWithHandlerCfg};
 use schnellru::{ByLength, LruMap};
@@ -46,6 +50,8 @@ pub struct WvmEthEvmConfig {
     pub evm_config: EthEvmConfig,
     pub precompile_cache: Arc<RwLock<PrecompileCache>>,
     pub exts: Vec<PrecompileWithAddress>,
+    /// Replaces the registry entry of the local history precompile, not cached.
+    pub local_history: Option<LocalHistoryPrecompile>,
 }
 
 /// A custom precompile that contains the cache and precompile it wraps.
@@ -114,7 +120,7 @@ impl ConfigureEvmEnv for WvmEthEvmConfig {
             self.evm_config.chain_spec().base_fee_params_at_timestamp(attributes.timestamp),
         );
 
-        let mut gas_limit = U256::from(parent.gas_limit);
+        let mut gas_limit = U256::from(next_block_gas_limit(self.evm_config.chain_spec(), parent));
 
         // If we are on the London fork boundary, we need to multiply the parent's gas limit by the
         // elasticity multiplier to get the new gas limit.
@@ -137,6 +143,10 @@ impl ConfigureEvmEnv for WvmEthEvmConfig {
             basefee = Some(EIP1559_INITIAL_BASE_FEE)
         }
 
+        let base_fee_floor =
+            self.evm_config.chain_spec().wvm.base_fee_floor_at_block(parent.number + 1);
+        let basefee = basefee.map(|basefee| basefee.max(base_fee_floor));
+
         let block_env = BlockEnv {
             number: U256::from(parent.number + 1),
             coinbase: attributes.suggested_fee_recipient,
@@ -165,7 +175,18 @@ impl WvmEthEvmConfig {
     {
         let exts: Vec<PrecompileWithAddress> = precompiles_ext.collect();
 
-        Self { evm_config: EthEvmConfig::new(chain_spec), precompile_cache, exts }
+        Self {
+            evm_config: EthEvmConfig::new(chain_spec),
+            precompile_cache,
+            exts,
+            local_history: None,
+        }
+    }
+
+    /// Serves the local history precompile from `local_history`.
+    pub fn with_local_history(mut self, local_history: LocalHistoryPrecompile) -> Self {
+        self.local_history = Some(local_history);
+        self
     }
 
     /// Sets the precompiles to the EVM handler
@@ -178,6 +199,7 @@ impl WvmEthEvmConfig {
         handler: &mut EvmHandler<EXT, DB>,
         cache: Arc<RwLock<PrecompileCache>>,
         extensions: PCI,
+        local_history: Option<LocalHistoryPrecompile>,
     ) where
         DB: Database,
         PCI: Iterator<Item = PrecompileWithAddress>,
@@ -188,8 +210,18 @@ impl WvmEthEvmConfig {
         let mut loaded_precompiles: ContextPrecompiles<DB> =
             ContextPrecompiles::new(PrecompileSpecId::from_spec_id(spec_id));
 
-        loaded_precompiles.extend(extensions);
+        // WVM precompiles are fork activated, see [`crate::inner::pc_activation`]
+        loaded_precompiles.extend(extensions.filter(|pc| is_pc_active(&pc.0, spec_id)));
         for (address, precompile) in loaded_precompiles.to_mut().iter_mut() {
+            // the local history depends on the block being executed, it can't be cached
+            if *address == local_history_pc_address() {
+                if let Some(local_history) = &local_history {
+                    *precompile =
+                        ContextPrecompile::ContextStateful(Arc::new(local_history.clone()));
+                    continue;
+                }
+            }
+
             // get or insert the cache for this address / spec
             let mut cache = cache.write();
             let cache = cache
@@ -249,11 +281,17 @@ impl ConfigureEvm for WvmEthEvmConfig {
     fn evm<DB: Database>(&self, db: DB) -> Evm<'_, Self::DefaultExternalContext<'_>, DB> {
         let precompiles_cache = self.precompile_cache.clone();
         let exts = self.exts.clone().into_iter();
+        let local_history = self.local_history.clone();
 
         EvmBuilder::default()
             .with_db(db)
             .append_handler_register_box(Box::new(move |handler| {
-                WvmEthEvmConfig::set_precompiles(handler, precompiles_cache.clone(), exts.clone())
+                WvmEthEvmConfig::set_precompiles(
+                    handler,
+                    precompiles_cache.clone(),
+                    exts.clone(),
+                    local_history.clone(),
+                )
             }))
             .build()
     }
@@ -270,6 +308,7 @@ impl ConfigureEvm for WvmEthEvmConfig {
     {
         let precompiles_cache = self.precompile_cache.clone();
         let exts = self.exts.clone().into_iter();
+        let local_history = self.local_history.clone();
 
         EvmBuilder::default()
             .with_db(db)
@@ -277,7 +316,12 @@ impl ConfigureEvm for WvmEthEvmConfig {
             .with_external_context(inspector)
             // add additional precompiles
             .append_handler_register_box(Box::new(move |handler| {
-                WvmEthEvmConfig::set_precompiles(handler, precompiles_cache.clone(), exts.clone())
+                WvmEthEvmConfig::set_precompiles(
+                    handler,
+                    precompiles_cache.clone(),
+                    exts.clone(),
+                    local_history.clone(),
+                )
             }))
             .append_handler_register(inspector_handle_register)
             .build()
@@ -0,0 +1,214 @@
+//! Arweave gateway serving the storage proofs of `wvm_getArweaveStorageProof`.
+
+use crate::import_arweave::archives::{archive_from_tags, decode_archive, download, ArchiveRef};
+use load_db::archive::{ArchiveGateway, ArchiveItem, DecodedArchive};
+use precompiles::{
+    inner::{
+        graphql_util::{build_transaction_query, send_graphql, Tag},
+        REQ_TIMEOUT,
+    },
+    WVM_DATA_PUBLISHERS,
+};
+use serde::Deserialize;
+
+/// Size archives are downloaded and decompressed to when verifying a storage proof, unless
+/// `ARCHIVE_PROOF_MAX_SIZE` sets another one. Well below what importing archives allows, as any
+/// RPC client can request a verification.
+const DEFAULT_MAX_PROOF_ARCHIVE_SIZE: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct ItemResponse {
+    data: ItemData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemData {
+    transactions: ItemTransactions,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemTransactions {
+    edges: Vec<ItemEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemEdge {
+    node: ItemNode,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemNode {
+    id: String,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    bundled_in: Option<ItemRef>,
+    block: Option<ItemBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemBlock {
+    height: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct NetworkInfo {
+    height: u64,
+}
+
+/// [`ArchiveGateway`] backed by the GraphQL and data endpoints of an Arweave gateway.
+#[derive(Debug, Clone)]
+pub struct GraphQlArchiveGateway {
+    gateway: String,
+    /// Bytes an archive is downloaded and decompressed to at most by [`ArchiveGateway::decode`].
+    max_archive_size: usize,
+}
+
+impl GraphQlArchiveGateway {
+    pub fn new(gateway: &str, max_archive_size: usize) -> Self {
+        Self { gateway: gateway.trim_end_matches('/').to_string(), max_archive_size }
+    }
+
+    /// Gateway whose archives are limited to `ARCHIVE_PROOF_MAX_SIZE` bytes.
+    pub fn from_env(gateway: &str) -> Self {
+        let max_archive_size = std::env::var("ARCHIVE_PROOF_MAX_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .unwrap_or(DEFAULT_MAX_PROOF_ARCHIVE_SIZE);
+
+        Self::new(gateway, max_archive_size)
+    }
+}
+
+fn item_query(arweave_id: &str) -> String {
+    build_transaction_query(
+        Some(&[arweave_id.to_string()]),
+        None,
+        None,
+        None,
+        false,
+        &["tags { name value }", "bundledIn { id }", "block { height }"],
+    )
+}
+
+fn attestation_query(arweave_id: &str) -> String {
+    build_transaction_query(
+        None,
+        Some(&[
+            ("Type".to_string(), vec!["Attestation".to_string()]),
+            ("Message".to_string(), vec![arweave_id.to_string()]),
+        ]),
+        Some(&WVM_DATA_PUBLISHERS.map(|i| i.to_string())),
+        None,
+        false,
+        &[],
+    )
+}
+
+impl ArchiveGateway for GraphQlArchiveGateway {
+    fn item(&self, arweave_id: &str) -> eyre::Result<Option<ArchiveItem>> {
+        let response = ureq::post(&format!("{}/graphql", self.gateway))
+            .timeout(*REQ_TIMEOUT)
+            .send_json(ureq::json!({ "variables": {}, "query": item_query(arweave_id) }))?
+            .into_json::<ItemResponse>()?;
+
+        Ok(response.data.transactions.edges.into_iter().next().map(|edge| ArchiveItem {
+            arweave_id: edge.node.id,
+            bundle_id: edge.node.bundled_in.map(|bundle| bundle.id),
+            tags: edge.node.tags.into_iter().map(|tag| (tag.name, tag.value)).collect(),
+            block_height: edge.node.block.map(|block| block.height),
+        }))
+    }
+
+    fn attestation_id(&self, arweave_id: &str) -> eyre::Result<Option<String>> {
+        let response = send_graphql(&self.gateway, &attestation_query(arweave_id))?;
+        Ok(response.data.transactions.edges.into_iter().next().map(|edge| edge.node.id))
+    }
+
+    fn network_height(&self) -> eyre::Result<u64> {
+        let info = ureq::get(&format!("{}/info", self.gateway))
+            .timeout(*REQ_TIMEOUT)
+            .call()?
+            .into_json::<NetworkInfo>()?;
+
+        Ok(info.height)
+    }
+
+    fn decode(&self, item: &ArchiveItem) -> eyre::Result<DecodedArchive> {
+        let tags: Vec<Tag> = item
+            .tags
+            .iter()
+            .map(|(name, value)| Tag { name: name.clone(), value: value.clone() })
+            .collect();
+        // Items the gateway doesn't index yet are decoded without their tags.
+        let archive = archive_from_tags(item.arweave_id.clone(), &tags)
+            .map(|(_, archive)| archive)
+            .unwrap_or_else(|| ArchiveRef {
+                arweave_id: item.arweave_id.clone(),
+                block_hash: None,
+                encoding: None,
+                dictionary: None,
+            });
+
+        let data = download(&self.gateway, &item.arweave_id, self.max_archive_size)?;
+        let block = decode_archive(&data, &archive, self.max_archive_size, |id| {
+            download(&self.gateway, id, self.max_archive_size).ok()
+        })?;
+
+        Ok(DecodedArchive {
+            block_number: block.number,
+            block_hash: block.header.hash_slow().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod archive_gateway_tests {
+    use crate::archive_gateway::{attestation_query, item_query, GraphQlArchiveGateway};
+    use load_db::archive::{ArchiveGateway, ArchiveItem};
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    const ARWEAVE_ID: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8";
+
+    #[test]
+    pub fn test_item_query() {
+        let query = item_query(ARWEAVE_ID);
+        assert!(query.contains(&format!("ids: [\"{}\"]", ARWEAVE_ID)));
+        assert!(query.contains("bundledIn { id }"));
+        assert!(query.contains("block { height }"));
+        assert_eq!(query.matches("tags {").count(), 1);
+    }
+
+    #[test]
+    pub fn test_decode_size_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let gateway = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0u8; 4096]);
+            let body = vec![0u8; 1024];
+            let _ = write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+            let _ = stream.write_all(&body);
+        });
+
+        let item = ArchiveItem { arweave_id: ARWEAVE_ID.to_string(), ..Default::default() };
+        let err = GraphQlArchiveGateway::new(&gateway, 512).decode(&item).unwrap_err();
+        assert!(err.to_string().contains("exceeds 512 bytes"));
+    }
+
+    #[test]
+    pub fn test_attestation_query() {
+        let query = attestation_query(ARWEAVE_ID);
+        assert!(query.contains("{name: \"Type\", values: [\"Attestation\"]}"));
+        assert!(query.contains(&format!("{{name: \"Message\", values: [\"{}\"]}}", ARWEAVE_ID)));
+    }
+}
@@ -1,20 +1,22 @@
 use crate::network_tag::get_network_tag;
 
 use arweave_upload::{ArweaveRequest, UploaderProvider};
-use exex_wvm_da::{DefaultWvmDataSettler, WvmDataSettler};
 use futures::{stream::FuturesUnordered, StreamExt};
-use load_db::LoadDbConnection;
+use load_db::{
+    batch::{LoadDbBatchWriter, DEFAULT_BATCH_DELAY, DEFAULT_BATCH_SIZE},
+    ArchivedBlock, LoadDbConnection,
+};
+use rbrotli::codec::{ArchiveCodec, CodecError, DICTIONARY_TAG, ENCODING_TAG};
 use reth::primitives::revm_primitives::alloy_primitives::BlockNumber;
-use reth_primitives::SealedBlockWithSenders;
-use std::{fmt, sync::Arc};
+use std::{fmt, sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 use tracing::{error, info};
 use wvm_borsh::block::BorshSealedBlockWithSenders;
-use wvm_tx::wvm::{v1::V1WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
+use wvm_tx::wvm::{v2::V2WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
 
 #[derive(Clone)] // Add this
 enum ArActorMessage {
-    ProcessBlock { block: SealedBlockWithSenders, notification_type: String },
+    ProcessBlock { block: V2WvmSealedBlockWithSenders, notification_type: String },
     Shutdown,
 }
 
@@ -65,18 +67,26 @@ impl fmt::Display for ArActorError {
 /// Main actor struct that maintains state and processes messages
 struct ArActor {
     receiver: mpsc::Receiver<ArActorMessage>,
-    load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+    load_db_writer: LoadDbBatchWriter,
     ar_uploader: UploaderProvider,
+    codec: Arc<ArchiveCodec>,
     worker_id: usize,
 }
 
 impl ArActor {
     fn new(
         receiver: mpsc::Receiver<ArActorMessage>,
-        load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+        load_db_writer: LoadDbBatchWriter,
+        codec: Arc<ArchiveCodec>,
         worker_id: usize,
     ) -> Self {
-        Self { receiver, load_db_repo, ar_uploader: UploaderProvider::new(None), worker_id }
+        Self {
+            receiver,
+            load_db_writer,
+            ar_uploader: UploaderProvider::new(None),
+            codec,
+            worker_id,
+        }
     }
 
     async fn run(mut self) {
@@ -101,9 +111,10 @@ impl ArActor {
                             break;
                         }
                         ArActorMessage::ProcessBlock { block, notification_type } => {
-                            let load_db_repo = self.load_db_repo.clone();
+                            let load_db_writer = self.load_db_writer.clone();
                             let ar_uploader = self.ar_uploader.clone();
-                            let block_number = block.number;
+                            let codec = self.codec.clone();
+                            let block_number = block.block.header.header.number;
                             let worker_id = self.worker_id;
 
                             info!(
@@ -118,8 +129,9 @@ impl ArActor {
                                 match handle_block(
                                     block,
                                     &notification_type,
-                                load_db_repo,
+                                    load_db_writer,
                                     ar_uploader,
+                                    &codec,
                                 ).await {
                                     Ok(arweave_id) => {
                                         info!(
@@ -202,22 +214,24 @@ impl ArActor {
 
 // Keep in same file but separate from ArActor
 async fn handle_block(
-    block: SealedBlockWithSenders,
+    block: V2WvmSealedBlockWithSenders,
     notification_type: &str,
-    load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+    load_db_writer: LoadDbBatchWriter,
     ar_uploader: UploaderProvider,
+    codec: &ArchiveCodec,
 ) -> ArActorResponse {
-    let block_hash_str = block.hash().to_string();
-    let block_number = block.number;
+    let block_hash_str = block.block.header.hash.to_string();
+    let block_number = block.block.header.header.number;
 
     // 1. Serialize block
     info!(target: "wvm::exex", "Block {} processing: starting serialization", block_number);
-    let borsh_brotli = serialize_block(block.clone())?;
+    let archive = serialize_block(block.clone(), codec)?;
     // 2. Upload to Arweave
     info!(target: "wvm::exex", "Block {} processing: starting Arweave upload", block_number);
     let arweave_id = upload_to_arweave(
         &ar_uploader,
-        &borsh_brotli,
+        &archive,
+        codec,
         notification_type,
         block_number,
         &block_hash_str,
@@ -226,28 +240,32 @@ async fn handle_block(
 
     // 3. Save Block
     info!(target: "wvm::exex", "Block {} processing: starting to save block in load db store", block_number);
-    save_block(load_db_repo, &block, block.number, &arweave_id).await?;
+    save_block(&load_db_writer, &block, block_number, &arweave_id).await?;
 
     info!(target: "wvm::exex", "Block {} processing: completed", block_number);
     Ok(arweave_id)
 }
 
-fn serialize_block(msg: SealedBlockWithSenders) -> Result<Vec<u8>, ArActorError> {
-    let data_settler = DefaultWvmDataSettler;
-    let block_number = msg.block.header.header().number;
+fn serialize_block(
+    msg: V2WvmSealedBlockWithSenders,
+    codec: &ArchiveCodec,
+) -> Result<Vec<u8>, ArActorError> {
+    let block_number = msg.block.header.header.number;
 
-    let data = WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(msg));
+    let data = WvmSealedBlockWithSenders::V2(msg);
 
     let borsh_sealed_block = BorshSealedBlockWithSenders(data);
 
-    data_settler
-        .process_block(&borsh_sealed_block)
+    borsh::to_vec(&borsh_sealed_block)
+        .map_err(CodecError::from)
+        .and_then(|borsh_data| codec.compress(&borsh_data))
         .map_err(|e| ArActorError::SerializationFailed { block_number, error: e.to_string() })
 }
 
 async fn upload_to_arweave(
     ar_uploader: &UploaderProvider,
     data: &[u8],
+    codec: &ArchiveCodec,
     notification_type: &str,
     block_number: BlockNumber,
     block_hash: &str,
@@ -258,13 +276,16 @@ async fn upload_to_arweave(
     let mut request = ArweaveRequest::new();
     request
         .set_tag("Content-Type", "application/octet-stream")
-        .set_tag("WeaveVM:Encoding", "Borsh-Brotli")
+        .set_tag(ENCODING_TAG, codec.encoding())
         .set_tag("Block-Number", block_number.to_string().as_str())
         .set_tag("Block-Hash", block_hash)
         .set_tag("Client-Version", reth_primitives::constants::RETH_CLIENT_VERSION)
         .set_tag("Network", get_network_tag().as_str())
-        .set_tag("WeaveVM:Internal-Chain", notification_type)
-        .set_data(data.to_vec());
+        .set_tag("WeaveVM:Internal-Chain", notification_type);
+    if let Some(dictionary_id) = codec.dictionary_id() {
+        request.set_tag(DICTIONARY_TAG, dictionary_id);
+    }
+    request.set_data(data.to_vec());
 
     match request.send_with_provider(ar_uploader).await {
         Ok(response) => {
@@ -340,22 +361,23 @@ async fn upload_to_arweave(
 }
 
 async fn save_block(
-    load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
-    block: &SealedBlockWithSenders,
+    load_db_writer: &LoadDbBatchWriter,
+    block: &V2WvmSealedBlockWithSenders,
     block_number: BlockNumber,
     arweave_id: &str,
 ) -> Result<(), ArActorError> {
     let start_time = std::time::Instant::now();
 
-    update_tags(&load_db_repo, block).await?;
-
-    let save_block_start_time = std::time::Instant::now();
-    let block_hash = block.block.hash().to_string();
+    // Tags and block state are stored together, batched with the other in-flight blocks.
+    let archived_block = ArchivedBlock {
+        block_number,
+        arweave_id: arweave_id.to_string(),
+        block_hash: block.block.header.hash.to_string(),
+        tx_hashes: block.block.body.transactions.iter().map(|e| e.hash.to_string()).collect(),
+    };
 
-    let result =
-        load_db_repo.save_block(block, block_number, arweave_id.to_string(), block_hash).await;
+    let result = load_db_writer.save(archived_block).await;
 
-    let save_block_duration = save_block_start_time.elapsed();
     match result {
         Ok(_) => {
             let total_duration = start_time.elapsed();
@@ -386,35 +408,22 @@ async fn save_block(
     }
 }
 
-async fn update_tags(
-    load_db_repo: &Arc<dyn LoadDbConnection + Send + Sync + 'static>,
-    sealed_block: &SealedBlockWithSenders,
-) -> Result<(), ArActorError> {
-    let hashes: Vec<String> =
-        sealed_block.body.transactions.iter().map(|e| e.hash.to_string()).collect();
-
-    if hashes.is_empty() {
-        return Ok(());
-    }
-
-    let start_time = std::time::Instant::now();
-    load_db_repo.save_hashes(hashes.as_slice(), sealed_block.number).await.map_err(|e| {
-        ArActorError::StoreBlockDataError {
-            block_number: sealed_block.number,
-            operation: "update tags",
-            error: e.to_string(),
-        }
-    })?;
-    let duration = start_time.elapsed();
-
-    info!(
-        target: "wvm::exex",
-        block_number = sealed_block.number,
-        duration = ?duration.as_millis(),
-        "Tags updated successfully",
-    );
-
-    Ok(())
+/// Batch writer shared by the actor workers, configured by `LOADDB_BATCH_SIZE` and
+/// `LOADDB_BATCH_DELAY_MS`.
+fn load_db_batch_writer(
+    load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+) -> LoadDbBatchWriter {
+    let batch_size = std::env::var("LOADDB_BATCH_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+    let batch_delay = std::env::var("LOADDB_BATCH_DELAY_MS")
+        .ok()
+        .and_then(|delay| delay.parse().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_BATCH_DELAY);
+
+    LoadDbBatchWriter::spawn(load_db_repo, batch_size, batch_delay)
 }
 
 #[derive(Clone)]
@@ -426,12 +435,13 @@ impl ArweaveActorHandle {
     pub async fn new(
         buffer_size: usize,
         load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+        codec: ArchiveCodec,
     ) -> Self {
         info!(target: "wvm::exex", "Creating new ArweaveActor with buffer size {}", buffer_size);
 
         let (sender, receiver) = mpsc::channel(buffer_size);
 
-        let actor = ArActor::new(receiver, load_db_repo, 0);
+        let actor = ArActor::new(receiver, load_db_batch_writer(load_db_repo), Arc::new(codec), 0);
 
         tokio::spawn(async move {
             actor.run().await;
@@ -444,6 +454,7 @@ impl ArweaveActorHandle {
         buffer_size: usize,
         num_workers: usize,
         load_db_repo: Arc<dyn LoadDbConnection + Send + Sync + 'static>,
+        codec: ArchiveCodec,
     ) -> Self {
         info!(
             target: "wvm::exex",
@@ -453,11 +464,13 @@ impl ArweaveActorHandle {
         );
 
         let mut worker_channels = Vec::with_capacity(num_workers);
+        let load_db_writer = load_db_batch_writer(load_db_repo);
+        let codec = Arc::new(codec);
 
         for id in 0..num_workers {
             let (worker_sender, worker_receiver) = mpsc::channel(buffer_size);
 
-            let worker = ArActor::new(worker_receiver, load_db_repo.clone(), id);
+            let worker = ArActor::new(worker_receiver, load_db_writer.clone(), codec.clone(), id);
 
             tokio::spawn(async move {
                 worker.run().await;
@@ -502,7 +515,7 @@ impl ArweaveActorHandle {
 
     pub async fn process_block(
         &self,
-        block: SealedBlockWithSenders,
+        block: V2WvmSealedBlockWithSenders,
         notification_type: String,
     ) -> Result<(), ArActorError> {
         self.sender
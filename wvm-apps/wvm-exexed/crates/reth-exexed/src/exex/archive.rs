@@ -0,0 +1,173 @@
+//! Content of the archived blocks.
+//!
+//! Blocks are archived with their receipts, so they can be checked against the `receipts_root` of
+//! their header without re-executing them. Receipts the node already pruned are archived as
+//! missing. With `WVM_ARCHIVE_STATE_DIFF=true` the accounts, slots and bytecode changed by the
+//! block are archived as well.
+
+use reth::{
+    primitives::{
+        revm_primitives::{
+            alloy_primitives::{Address, U256},
+            KECCAK_EMPTY,
+        },
+        SealedBlockWithSenders,
+    },
+    providers::{Chain, ExecutionOutcome},
+};
+use std::collections::{BTreeMap, BTreeSet};
+use wvm_tx::wvm::v2::{
+    state_diff::{WvmAccountDiff, WvmAccountInfo, WvmStateDiff},
+    V2WvmSealedBlockWithSenders,
+};
+
+pub fn archive_state_diff_enabled() -> bool {
+    std::env::var("WVM_ARCHIVE_STATE_DIFF").unwrap_or(String::from("false")).to_lowercase() ==
+        "true"
+}
+
+/// Archive of the tip of `chain`, the block sent to Arweave.
+pub fn tip_archive(chain: &Chain, with_state_diff: bool) -> V2WvmSealedBlockWithSenders {
+    let block: SealedBlockWithSenders = chain.tip().clone();
+    let execution_outcome = chain.execution_outcome();
+    let receipts = execution_outcome.receipts_by_block(block.number).iter().cloned();
+    let state_diff =
+        if with_state_diff { tip_state_diff(execution_outcome, block.number) } else { None };
+
+    V2WvmSealedBlockWithSenders::new(block, receipts.collect(), state_diff)
+}
+
+/// State changes of `block_number`, which must be the last block of `execution_outcome`, as its
+/// bundle holds the state after that block.
+pub fn tip_state_diff(
+    execution_outcome: &ExecutionOutcome,
+    block_number: u64,
+) -> Option<WvmStateDiff> {
+    let index = execution_outcome.block_number_to_index(block_number)?;
+    if index + 1 != execution_outcome.len() {
+        return None;
+    }
+
+    // Reverts of the block list what it changed, along with the previous values.
+    let mut reverts = execution_outcome.bundle.reverts.clone().into_plain_state_reverts();
+    let account_reverts = reverts.accounts.get_mut(index).map(std::mem::take).unwrap_or_default();
+    let storage_reverts = reverts.storage.get_mut(index).map(std::mem::take).unwrap_or_default();
+
+    let mut changed: BTreeMap<Address, (bool, BTreeSet<U256>)> = BTreeMap::new();
+    let mut previous_code_hashes = BTreeMap::new();
+    for (address, previous_info) in account_reverts {
+        changed.entry(address).or_default();
+        previous_code_hashes.insert(address, previous_info.map(|info| info.code_hash));
+    }
+    for revert in storage_reverts {
+        let (wiped, slots) = changed.entry(revert.address).or_default();
+        *wiped |= revert.wiped;
+        slots.extend(revert.storage_revert.into_iter().map(|(slot, _)| slot));
+    }
+
+    let mut state_diff = WvmStateDiff::default();
+    for (address, (wiped_storage, slots)) in changed {
+        let account = execution_outcome.bundle.account(&address);
+        let info = account.and_then(|account| account.info.as_ref());
+
+        // Bytecode is only archived by the block deploying it.
+        if let Some(info) = info {
+            let deployed = previous_code_hashes
+                .get(&address)
+                .is_some_and(|previous| *previous != Some(info.code_hash));
+            if deployed && info.code_hash != KECCAK_EMPTY {
+                if let Some(bytecode) = execution_outcome.bundle.bytecode(&info.code_hash) {
+                    state_diff.contracts.push((info.code_hash, bytecode.original_bytes()));
+                }
+            }
+        }
+
+        state_diff.accounts.push(WvmAccountDiff {
+            address,
+            info: info.map(|info| WvmAccountInfo {
+                balance: info.balance,
+                nonce: info.nonce,
+                code_hash: info.code_hash,
+            }),
+            wiped_storage,
+            storage: slots
+                .into_iter()
+                .map(|slot| {
+                    let value =
+                        account.and_then(|account| account.storage_slot(slot)).unwrap_or_default();
+                    (slot, value)
+                })
+                .collect(),
+        });
+    }
+    state_diff.contracts.sort_by_key(|(code_hash, _)| *code_hash);
+    state_diff.contracts.dedup_by_key(|(code_hash, _)| *code_hash);
+
+    Some(state_diff)
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use crate::exex::archive::tip_state_diff;
+    use reth::{
+        primitives::{
+            revm_primitives::alloy_primitives::{Address, Bytes, U256},
+            Receipts,
+        },
+        providers::ExecutionOutcome,
+        revm::{
+            db::BundleState,
+            primitives::{AccountInfo, Bytecode},
+        },
+    };
+    use wvm_tx::wvm::v2::state_diff::{WvmAccountDiff, WvmAccountInfo};
+
+    #[test]
+    pub fn test_tip_state_diff() {
+        let sender = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let code = Bytes::from(vec![0x60, 0x80, 0x60, 0x40]);
+        let bytecode = Bytecode::new_raw(code.clone());
+        let code_hash = bytecode.hash_slow();
+        let sender_info = AccountInfo { balance: U256::from(90), nonce: 1, ..Default::default() };
+        let contract_info = AccountInfo::new(U256::ZERO, 1, code_hash, bytecode.clone());
+
+        // Block 1 funds the sender, block 2 deploys the contract and sets a slot.
+        let bundle = BundleState::new(
+            vec![
+                (sender, None, Some(sender_info), Default::default()),
+                (
+                    contract,
+                    None,
+                    Some(contract_info),
+                    vec![(U256::from(1), (U256::ZERO, U256::from(5)))].into_iter().collect(),
+                ),
+            ],
+            vec![
+                vec![(sender, Some(None), vec![])],
+                vec![(contract, Some(None), vec![(U256::from(1), U256::ZERO)])],
+            ],
+            vec![(code_hash, bytecode)],
+        );
+        let execution_outcome = ExecutionOutcome::new(
+            bundle,
+            Receipts { receipt_vec: vec![vec![], vec![]] },
+            1,
+            vec![],
+        );
+
+        assert_eq!(tip_state_diff(&execution_outcome, 1), None);
+
+        let state_diff = tip_state_diff(&execution_outcome, 2).unwrap();
+        assert_eq!(
+            state_diff.accounts,
+            vec![WvmAccountDiff {
+                address: contract,
+                info: Some(WvmAccountInfo { balance: U256::ZERO, nonce: 1, code_hash }),
+                wiped_storage: false,
+                storage: vec![(U256::from(1), U256::from(5))],
+            }]
+        );
+        assert_eq!(state_diff.contracts, vec![(code_hash, code)]);
+    }
+}
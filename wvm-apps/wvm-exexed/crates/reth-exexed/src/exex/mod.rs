@@ -1 +1,4 @@
 pub mod ar_actor;
+pub mod archive;
+pub mod state_snapshot;
+pub mod tag_index;
@@ -0,0 +1,310 @@
+//! State snapshots archived to Arweave.
+//!
+//! Every `WVM_STATE_SNAPSHOT_INTERVAL` blocks, the state of the block at the multiple of the
+//! interval is exported as a state dump, uploaded in compressed chunks and listed by a manifest.
+//! Snapshots are skipped when the node already executed past that block. Nodes are initialized
+//! from a snapshot with `reth init-state --arweave-manifest <id>`.
+
+use crate::network_tag::get_network_tag;
+use arweave_upload::{ArweaveRequest, UploaderProvider};
+use futures::StreamExt;
+use rbrotli::codec::ENCODING_TAG;
+use reth::{
+    api::FullNodeComponents,
+    primitives::{revm_primitives::alloy_primitives::keccak256, SealedHeader},
+    providers::{DBProvider, DatabaseProviderFactory, HeaderProvider, ProviderError},
+};
+use reth_cli_commands::init_state::arweave::{
+    SnapshotChunk, SnapshotChunks, StateSnapshotManifest, CHUNK_TYPE, DEFAULT_CHUNK_SIZE,
+    MANIFEST_TYPE, MANIFEST_VERSION, SNAPSHOT_ENCODING,
+};
+use reth_db::{tables, transaction::DbTx};
+use reth_db_common::init::export_state_dump;
+use reth_exex::{ExExContext, ExExEvent};
+use reth_stages::StageId;
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+const DEFAULT_ZSTD_LEVEL: i32 = 9;
+
+#[derive(Debug, Clone)]
+pub struct StateSnapshotConfig {
+    /// Blocks between two snapshots.
+    pub interval: u64,
+    /// Uncompressed size of the chunks.
+    pub chunk_size: usize,
+    pub zstd_level: i32,
+    /// Directory the state dump is written to before being uploaded.
+    pub dir: PathBuf,
+}
+
+impl StateSnapshotConfig {
+    /// `None` unless `WVM_STATE_SNAPSHOT_INTERVAL` is set to a number of blocks.
+    pub fn from_env() -> Option<Self> {
+        let interval = std::env::var("WVM_STATE_SNAPSHOT_INTERVAL").ok()?.parse().ok()?;
+        if interval == 0 {
+            return None;
+        }
+
+        Some(Self {
+            interval,
+            chunk_size: std::env::var("WVM_STATE_SNAPSHOT_CHUNK_SIZE")
+                .ok()
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(DEFAULT_CHUNK_SIZE),
+            zstd_level: std::env::var("WVM_STATE_SNAPSHOT_ZSTD_LEVEL")
+                .ok()
+                .and_then(|level| level.parse().ok())
+                .unwrap_or(DEFAULT_ZSTD_LEVEL),
+            dir: std::env::var("WVM_STATE_SNAPSHOT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| std::env::temp_dir()),
+        })
+    }
+
+    /// First multiple of the interval in `range`, if any.
+    fn due_block(&self, range: RangeInclusive<u64>) -> Option<u64> {
+        let block = range.start().div_ceil(self.interval) * self.interval;
+        (block <= *range.end()).then_some(block)
+    }
+}
+
+/// Opens a read transaction on the state of `block`.
+///
+/// Returns `None` if the last executed block isn't `block` anymore.
+fn open_state<P>(factory: &P, block: u64) -> eyre::Result<Option<(P::Provider, SealedHeader)>>
+where
+    P: DatabaseProviderFactory + HeaderProvider,
+{
+    // Plain state and checkpoint are read from the same transaction, which is kept open for the
+    // whole export
+    let provider = factory.database_provider_ro()?.disable_long_read_transaction_safety();
+    let executed = provider
+        .tx_ref()
+        .get::<tables::StageCheckpoints>(StageId::Execution.to_string())?
+        .map(|checkpoint| checkpoint.block_number)
+        .unwrap_or_default();
+    if executed != block {
+        return Ok(None);
+    }
+
+    let header =
+        factory.sealed_header(block)?.ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+
+    Ok(Some((provider, header)))
+}
+
+/// Writes the state read by `provider` to `dir`.
+///
+/// Returns the number of accounts and the path of the state dump.
+fn export_state<P: DBProvider>(
+    mut provider: P,
+    header: &SealedHeader,
+    dir: &Path,
+) -> eyre::Result<(usize, PathBuf)> {
+    let path = dir.join(format!("wvm-state-snapshot-{}.jsonl", header.number));
+    let accounts = export_state_dump(
+        provider.tx_mut(),
+        header.state_root,
+        BufWriter::new(File::create(&path)?),
+    )?;
+
+    Ok((accounts, path))
+}
+
+async fn upload_chunks(
+    uploader: &UploaderProvider,
+    path: &Path,
+    block_number: u64,
+    config: &StateSnapshotConfig,
+) -> eyre::Result<Vec<SnapshotChunk>> {
+    let mut chunks = SnapshotChunks::new(
+        BufReader::new(File::open(path)?),
+        config.chunk_size,
+        config.zstd_level,
+    );
+    let mut uploaded = Vec::new();
+
+    // Compression runs on the worker thread, chunks are uploaded one at a time
+    while let Some(data) = tokio::task::block_in_place(|| chunks.next()) {
+        let data = data?;
+        let chunk_index = uploaded.len();
+        let size = data.len() as u64;
+        let hash = keccak256(&data);
+
+        let mut request = ArweaveRequest::new();
+        request
+            .set_tag("Content-Type", "application/octet-stream")
+            .set_tag("Type", CHUNK_TYPE)
+            .set_tag(ENCODING_TAG, SNAPSHOT_ENCODING)
+            .set_tag("Block-Number", block_number.to_string().as_str())
+            .set_tag("Chunk-Index", chunk_index.to_string().as_str())
+            .set_tag("Network", get_network_tag().as_str())
+            .set_data(data);
+        let arweave_id = request.send_with_provider(uploader).await?;
+
+        info!(
+            target: "wvm::state_snapshot",
+            block_number,
+            chunk_index,
+            size,
+            "State snapshot chunk uploaded: {}",
+            arweave_id
+        );
+        uploaded.push(SnapshotChunk { arweave_id, size, hash });
+    }
+
+    Ok(uploaded)
+}
+
+/// Exports, uploads and removes the state snapshot of `header`.
+///
+/// Returns the Arweave id of its manifest.
+async fn archive_state_snapshot<P: DBProvider>(
+    provider: P,
+    header: SealedHeader,
+    config: &StateSnapshotConfig,
+) -> eyre::Result<String> {
+    let dir = config.dir.clone();
+    let (header, (accounts, path)) = tokio::task::spawn_blocking(move || {
+        let exported = export_state(provider, &header, &dir);
+        exported.map(|exported| (header, exported))
+    })
+    .await??;
+    info!(
+        target: "wvm::state_snapshot",
+        block_number = header.number,
+        accounts,
+        "State exported"
+    );
+
+    let uploader = UploaderProvider::new(None);
+    let chunks = upload_chunks(&uploader, &path, header.number, config).await;
+    fs::remove_file(&path)?;
+
+    let manifest = StateSnapshotManifest {
+        version: MANIFEST_VERSION,
+        block_number: header.number,
+        block_hash: header.hash(),
+        state_root: header.state_root,
+        encoding: SNAPSHOT_ENCODING.to_string(),
+        accounts,
+        chunks: chunks?,
+    };
+
+    let mut request = ArweaveRequest::new();
+    request
+        .set_tag("Content-Type", "application/json")
+        .set_tag("Type", MANIFEST_TYPE)
+        .set_tag("Block-Number", header.number.to_string().as_str())
+        .set_tag("Block-Hash", header.hash().to_string().as_str())
+        .set_tag("State-Root", header.state_root.to_string().as_str())
+        .set_tag("Network", get_network_tag().as_str())
+        .set_data(serde_json::to_vec(&manifest)?);
+
+    request.send_with_provider(&uploader).await
+}
+
+pub async fn exex_state_snapshot<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    config: StateSnapshotConfig,
+) -> eyre::Result<()> {
+    let config = Arc::new(config);
+    let mut snapshot: Option<JoinHandle<()>> = None;
+
+    while let Some(notification_result) = ctx.notifications.next().await {
+        let notification = match notification_result {
+            Ok(notification) => notification,
+            Err(e) => {
+                error!(
+                    target: "wvm::state_snapshot",
+                    %e,
+                    "Failed to receive notification from exex stream",
+                );
+                continue;
+            }
+        };
+
+        if let Some(committed_chain) = notification.committed_chain() {
+            // Snapshots taking longer than the interval skip the next one
+            let idle = snapshot.as_ref().map_or(true, JoinHandle::is_finished);
+            if let Some(block) = config.due_block(committed_chain.range()).filter(|_| idle) {
+                match open_state(ctx.provider(), block) {
+                    Ok(Some((provider, header))) => {
+                        let config = config.clone();
+                        snapshot = Some(tokio::spawn(async move {
+                            match archive_state_snapshot(provider, header, &config).await {
+                                Ok(manifest_id) => {
+                                    info!(
+                                        target: "wvm::state_snapshot",
+                                        "State snapshot archived, manifest: {}",
+                                        manifest_id
+                                    );
+                                }
+                                Err(err) => {
+                                    error!(
+                                        target: "wvm::state_snapshot",
+                                        %err,
+                                        "Failed to archive state snapshot"
+                                    );
+                                }
+                            }
+                        }));
+                    }
+                    Ok(None) => {
+                        warn!(
+                            target: "wvm::state_snapshot",
+                            block,
+                            "Node executed past the snapshot block, skipping its state snapshot"
+                        );
+                    }
+                    Err(err) => {
+                        error!(
+                            target: "wvm::state_snapshot",
+                            block,
+                            %err,
+                            "Failed to read the state of the snapshot block"
+                        );
+                    }
+                }
+            }
+
+            if let Err(err) =
+                ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))
+            {
+                error!(target: "wvm::state_snapshot", %err, "Failed to send FinishedHeight event");
+            }
+        }
+    }
+
+    info!(target: "wvm::state_snapshot", "State snapshot ExEx shutting down");
+    Ok(())
+}
+
+#[cfg(test)]
+mod state_snapshot_tests {
+    use crate::exex::state_snapshot::StateSnapshotConfig;
+
+    #[test]
+    pub fn test_snapshot_due_block() {
+        let config = StateSnapshotConfig {
+            interval: 100,
+            chunk_size: 1024,
+            zstd_level: 3,
+            dir: std::env::temp_dir(),
+        };
+
+        assert_eq!(config.due_block(100..=100), Some(100));
+        assert_eq!(config.due_block(95..=105), Some(100));
+        assert_eq!(config.due_block(1..=1000), Some(100));
+        assert_eq!(config.due_block(101..=199), None);
+        assert_eq!(config.due_block(1..=99), None);
+    }
+}
@@ -0,0 +1,588 @@
+//! Tag index kept in the node database.
+//!
+//! Tags sent with `wvm_sendTransaction` wait in `WvmPendingTags` until their transaction is
+//! included. The ExEx then moves them to `WvmTxTags`, keyed by the position of the transaction in
+//! the chain, and to `WvmTagIndex`, which lists the transactions carrying a tag in chain order.
+//! Reverted blocks put their tags back to pending. Included tags are pruned by the `WvmTags` prune
+//! segment, pending tags whose transaction is never included are expired by the ExEx.
+//!
+//! The ExEx is the only writer of these tables. The RPC only reads them, the pending tags it
+//! receives are queued to the ExEx, see [`PendingTagWrites`].
+
+use futures::StreamExt;
+use load_db::{
+    query::{TagCursor, TagIndex, TagMatch, TagQuery, TagQueryPage, TaggedTransaction},
+    reconcile::TagExpiryConfig,
+};
+use reth::{
+    api::FullNodeComponents,
+    primitives::revm_primitives::alloy_primitives::{Address, TxHash, B256},
+    providers::{Chain, DBProvider, DatabaseProviderFactory},
+};
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::{wvm_tag_key, WvmPendingTagEntry, WvmTagEntry, WvmTagIndexKey, WvmTags, WvmTxKey},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_exex::{ExExContext, ExExEvent};
+use reth_prune::PruneSegment;
+use std::{
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+use tracing::{debug, error, info};
+
+/// Pending tag writes the RPC can queue before it's told the tag index is busy.
+pub const PENDING_TAG_WRITES_CAPACITY: usize = 10_000;
+
+/// Index entries looked at for a single page. Filters which aren't part of the index, i.e. the
+/// sender, may skip most of them, the page then ends early with a cursor to continue from.
+pub const MAX_SCANNED_ENTRIES: usize = 10_000;
+
+/// Change to the pending tags, queued by the RPC and applied by the ExEx.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingTagWrite {
+    Save { tx_hash: TxHash, entry: WvmPendingTagEntry },
+    Discard { tx_hash: TxHash },
+}
+
+/// Receiving end of the pending tag writes, handed to [`exex_tag_index`].
+pub type PendingTagWrites = mpsc::Receiver<PendingTagWrite>;
+
+#[derive(Debug, Clone)]
+pub struct NodeTagIndex<P> {
+    provider: P,
+    writes: mpsc::Sender<PendingTagWrite>,
+}
+
+impl<P> NodeTagIndex<P>
+where
+    P: DatabaseProviderFactory + 'static,
+{
+    /// Creates the index and the receiver of its pending tag writes.
+    pub fn new(provider: P) -> (Self, PendingTagWrites) {
+        let (writes, receiver) = mpsc::channel(PENDING_TAG_WRITES_CAPACITY);
+        (Self { provider, writes }, receiver)
+    }
+
+    fn queue(&self, write: PendingTagWrite) -> eyre::Result<()> {
+        self.writes.try_send(write).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => eyre::eyre!("Tag index is busy"),
+            mpsc::error::TrySendError::Closed(_) => eyre::eyre!("Tag index is not running"),
+        })
+    }
+
+    /// Applies queued changes to the pending tags in a single transaction.
+    pub fn apply_pending_writes(&self, writes: Vec<PendingTagWrite>) -> eyre::Result<()> {
+        let provider = self.provider.database_provider_rw()?;
+        {
+            let tx = provider.tx_ref();
+            for write in writes {
+                match write {
+                    PendingTagWrite::Save { tx_hash, entry } => {
+                        tx.put::<tables::WvmPendingTags>(tx_hash, entry)?
+                    }
+                    PendingTagWrite::Discard { tx_hash } => {
+                        tx.delete::<tables::WvmPendingTags>(tx_hash, None)?;
+                    }
+                }
+            }
+        }
+        provider.commit()?;
+
+        Ok(())
+    }
+
+    /// Drops the pending tags received before `created_before`, in milliseconds since the unix
+    /// epoch. Returns how many were dropped.
+    pub fn expire_pending_tags(&self, created_before: u64) -> eyre::Result<usize> {
+        let provider = self.provider.database_provider_rw()?;
+        let mut expired = 0;
+        {
+            let mut pending = provider.tx_ref().cursor_write::<tables::WvmPendingTags>()?;
+            let mut walker = pending.walk(None)?;
+            while let Some(row) = walker.next() {
+                let (_, entry) = row?;
+                if entry.created_at < created_before {
+                    walker.delete_current()?;
+                    expired += 1;
+                }
+            }
+        }
+        provider.commit()?;
+
+        Ok(expired)
+    }
+
+    /// Moves the pending tags of the transactions included in `chain` to the index.
+    ///
+    /// Blocks the `WvmTags` segment was already pruned past, e.g. when the ExEx lags behind the
+    /// pruner, aren't indexed since nothing would prune them anymore. Their pending tags are
+    /// dropped.
+    pub fn commit_chain(&self, chain: &Chain) -> eyre::Result<()> {
+        let provider = self.provider.database_provider_rw()?;
+        {
+            let tx = provider.tx_ref();
+            let mut pending = tx.cursor_write::<tables::WvmPendingTags>()?;
+
+            // No tagged transaction waits for inclusion, which is the case for most blocks.
+            if pending.first()?.is_none() {
+                return Ok(());
+            }
+
+            let pruned_up_to = tx
+                .get::<tables::PruneCheckpoints>(PruneSegment::WvmTags)?
+                .and_then(|checkpoint| checkpoint.block_number);
+
+            for block in chain.blocks_iter() {
+                let pruned = pruned_up_to.is_some_and(|pruned_up_to| block.number <= pruned_up_to);
+
+                for (tx_index, (sender, transaction)) in
+                    block.transactions_with_sender().enumerate()
+                {
+                    let Some((tx_hash, entry)) = pending.seek_exact(transaction.hash())? else {
+                        continue;
+                    };
+                    pending.delete_current()?;
+
+                    if pruned {
+                        debug!(
+                            target: "wvm::tag_index",
+                            %tx_hash,
+                            block = block.number,
+                            "Tags of a pruned block dropped"
+                        );
+                        continue;
+                    }
+
+                    let key = WvmTxKey::new(block.number, tx_index as u32);
+                    for (name, value) in &entry.tags.0 {
+                        tx.put::<tables::WvmTagIndex>(
+                            WvmTagIndexKey::new(wvm_tag_key(name, value), key),
+                            tx_hash,
+                        )?;
+                    }
+                    tx.put::<tables::WvmTxTags>(
+                        key,
+                        WvmTagEntry { tx_hash, sender: *sender, tags: entry.tags },
+                    )?;
+                }
+            }
+        }
+        provider.commit()?;
+
+        Ok(())
+    }
+
+    /// Removes the transactions of the reverted `chain` from the index. Their tags go back to
+    /// pending, in case the transactions are included again.
+    pub fn revert_chain(&self, chain: &Chain) -> eyre::Result<()> {
+        let created_at = now_millis()?;
+        let provider = self.provider.database_provider_rw()?;
+        {
+            let tx = provider.tx_ref();
+            let mut entries = tx.cursor_write::<tables::WvmTxTags>()?;
+            let range = chain.range();
+            let mut walker = entries.walk_range(
+                WvmTxKey::new(*range.start(), 0)..=WvmTxKey::new(*range.end(), u32::MAX),
+            )?;
+
+            while let Some(row) = walker.next() {
+                let (key, entry) = row?;
+                for (name, value) in &entry.tags.0 {
+                    tx.delete::<tables::WvmTagIndex>(
+                        WvmTagIndexKey::new(wvm_tag_key(name, value), key),
+                        None,
+                    )?;
+                }
+                tx.put::<tables::WvmPendingTags>(
+                    entry.tx_hash,
+                    WvmPendingTagEntry { created_at, tags: entry.tags },
+                )?;
+                walker.delete_current()?;
+            }
+        }
+        provider.commit()?;
+
+        Ok(())
+    }
+}
+
+fn now_millis() -> eyre::Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64)
+}
+
+impl<P> TagIndex for NodeTagIndex<P>
+where
+    P: DatabaseProviderFactory + 'static,
+{
+    fn save_pending_tags(&self, tx_hash: &str, tags: Vec<(String, String)>) -> eyre::Result<bool> {
+        let tx_hash = TxHash::from_str(tx_hash)?;
+
+        // Tags already pending for the transaction belong to an earlier submission.
+        let provider = self.provider.database_provider_ro()?;
+        if provider.tx_ref().get::<tables::WvmPendingTags>(tx_hash)?.is_some() {
+            return Ok(false)
+        }
+
+        let entry = WvmPendingTagEntry { created_at: now_millis()?, tags: WvmTags(tags) };
+        self.queue(PendingTagWrite::Save { tx_hash, entry })?;
+
+        Ok(true)
+    }
+
+    fn discard_pending_tags(&self, tx_hash: &str) -> eyre::Result<()> {
+        let tx_hash = TxHash::from_str(tx_hash)?;
+
+        self.queue(PendingTagWrite::Discard { tx_hash })
+    }
+
+    fn query_transactions(&self, query: &TagQuery) -> eyre::Result<TagQueryPage> {
+        let provider = self.provider.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let mut index = tx.cursor_read::<tables::WvmTagIndex>()?;
+        let mut entries = tx.cursor_read::<tables::WvmTxTags>()?;
+
+        let tags =
+            query.tags.iter().map(|(name, value)| wvm_tag_key(name, value)).collect::<Vec<_>>();
+        let sender = query.sender.as_deref().map(Address::from_str).transpose()?;
+        let to_block = query.to_block.unwrap_or(u64::MAX);
+
+        let start = WvmTxKey::new(query.from_block.unwrap_or_default(), 0);
+        let mut position = match &query.cursor {
+            Some(cursor) => {
+                next_position(cursor_position(&mut entries, cursor)?).map(|after| after.max(start))
+            }
+            None => Some(start),
+        };
+
+        // One extra row tells whether there's a next page.
+        let page_size = query.page_size();
+        let mut rows = Vec::new();
+        let mut scanned = 0;
+        while let Some(from) = position {
+            if rows.len() > page_size as usize {
+                break;
+            }
+
+            let Some(key) = next_match(&mut index, &tags, query.match_mode, from, to_block)? else {
+                break;
+            };
+            position = next_position(key);
+
+            let Some((_, entry)) = entries.seek_exact(key)? else {
+                error!(target: "wvm::tag_index", ?key, "Tag index entry without transaction tags");
+                continue;
+            };
+
+            scanned += 1;
+            if sender.is_some_and(|sender| sender != entry.sender) {
+                if scanned >= MAX_SCANNED_ENTRIES {
+                    // The page ends early, the caller continues after the last scanned entry.
+                    let next_cursor = TagCursor {
+                        block_number: key.block_number,
+                        hash: format!("{:#x}", entry.tx_hash),
+                    };
+                    return Ok(TagQueryPage { transactions: rows, next_cursor: Some(next_cursor) });
+                }
+                continue;
+            }
+
+            rows.push(TaggedTransaction {
+                hash: format!("{:#x}", entry.tx_hash),
+                block_number: key.block_number,
+                sender: Some(format!("{:#x}", entry.sender)),
+                tags: entry.tags.0,
+            });
+        }
+
+        Ok(TagQueryPage::from_rows(rows, page_size))
+    }
+}
+
+/// Handle to the [`NodeTagIndex`] for the RPC, which is built before the ExEx creates the index.
+/// Calls fail until the ExEx [`set`](Self::set) it.
+#[derive(Debug, Clone, Default)]
+pub struct DeferredTagIndex(Arc<OnceLock<Arc<dyn TagIndex>>>);
+
+impl DeferredTagIndex {
+    /// Sets the index calls are passed to. Returns `false` if it was already set.
+    pub fn set(&self, index: Arc<dyn TagIndex>) -> bool {
+        self.0.set(index).is_ok()
+    }
+
+    fn index(&self) -> eyre::Result<&Arc<dyn TagIndex>> {
+        self.0.get().ok_or_else(|| eyre::eyre!("Tag index is not running"))
+    }
+}
+
+impl TagIndex for DeferredTagIndex {
+    fn save_pending_tags(&self, tx_hash: &str, tags: Vec<(String, String)>) -> eyre::Result<bool> {
+        self.index()?.save_pending_tags(tx_hash, tags)
+    }
+
+    fn discard_pending_tags(&self, tx_hash: &str) -> eyre::Result<()> {
+        self.index()?.discard_pending_tags(tx_hash)
+    }
+
+    fn query_transactions(&self, query: &TagQuery) -> eyre::Result<TagQueryPage> {
+        self.index()?.query_transactions(query)
+    }
+}
+
+/// Position of the transaction a cursor points to.
+fn cursor_position<C: DbCursorRO<tables::WvmTxTags>>(
+    entries: &mut C,
+    cursor: &TagCursor,
+) -> eyre::Result<WvmTxKey> {
+    let hash = TxHash::from_str(&cursor.hash)?;
+    let block =
+        WvmTxKey::new(cursor.block_number, 0)..=WvmTxKey::new(cursor.block_number, u32::MAX);
+
+    for row in entries.walk_range(block)? {
+        let (key, entry) = row?;
+        if entry.tx_hash == hash {
+            return Ok(key);
+        }
+    }
+
+    Err(eyre::eyre!("Invalid cursor"))
+}
+
+const fn next_position(key: WvmTxKey) -> Option<WvmTxKey> {
+    if key.tx_index < u32::MAX {
+        Some(WvmTxKey::new(key.block_number, key.tx_index + 1))
+    } else if key.block_number < u64::MAX {
+        Some(WvmTxKey::new(key.block_number + 1, 0))
+    } else {
+        None
+    }
+}
+
+/// First transaction at or after `from`, up to `to_block`, matching `tags`.
+fn next_match<C: DbCursorRO<tables::WvmTagIndex>>(
+    index: &mut C,
+    tags: &[B256],
+    match_mode: TagMatch,
+    mut from: WvmTxKey,
+    to_block: u64,
+) -> Result<Option<WvmTxKey>, DatabaseError> {
+    let mut seek = |tag: B256, from: WvmTxKey| -> Result<Option<WvmTxKey>, DatabaseError> {
+        Ok(index
+            .seek(WvmTagIndexKey::new(tag, from))?
+            .map(|(key, _)| key)
+            .filter(|key| key.tag == tag && key.tx.block_number <= to_block)
+            .map(|key| key.tx))
+    };
+
+    match match_mode {
+        TagMatch::Any => {
+            let mut next: Option<WvmTxKey> = None;
+            for tag in tags {
+                if let Some(key) = seek(*tag, from)? {
+                    next = Some(next.map_or(key, |next| next.min(key)));
+                }
+            }
+            Ok(next)
+        }
+        // Every tag jumps to its first transaction at or after the candidate, until they all land
+        // on the same one.
+        TagMatch::All => loop {
+            let mut candidate = from;
+            for tag in tags {
+                let Some(key) = seek(*tag, candidate)? else { return Ok(None) };
+                candidate = key;
+            }
+            if candidate == from {
+                return Ok(Some(candidate));
+            }
+            from = candidate;
+        },
+    }
+}
+
+/// Keeps the native tag index in sync with the canonical chain, applies the pending tag writes
+/// of the RPC and expires the pending tags older than [`TagExpiryConfig::ttl`].
+///
+/// Failing to update the index stops the ExEx before it reports the height as finished, so the
+/// node doesn't prune past tags which weren't indexed.
+pub async fn exex_tag_index<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    index: NodeTagIndex<Node::Provider>,
+    mut writes: PendingTagWrites,
+    expiry: TagExpiryConfig,
+) -> eyre::Result<()> {
+    let mut expiry_interval = tokio::time::interval(expiry.interval);
+
+    loop {
+        tokio::select! {
+            // Writes queued before a transaction was submitted are applied before the block
+            // including it.
+            biased;
+
+            Some(write) = writes.recv() => {
+                let mut batch = vec![write];
+                while batch.len() < PENDING_TAG_WRITES_CAPACITY {
+                    let Ok(write) = writes.try_recv() else { break };
+                    batch.push(write);
+                }
+                index.apply_pending_writes(batch)?;
+            }
+
+            notification = ctx.notifications.next() => {
+                let Some(notification_result) = notification else { break };
+                let notification = match notification_result {
+                    Ok(notification) => notification,
+                    Err(e) => {
+                        error!(
+                            target: "wvm::tag_index",
+                            %e,
+                            "Failed to receive notification from exex stream",
+                        );
+                        continue;
+                    }
+                };
+
+                if let Some(reverted_chain) = notification.reverted_chain() {
+                    index.revert_chain(&reverted_chain).map_err(|err| {
+                        eyre::eyre!("Failed to revert tags of blocks {:?}: {err}", reverted_chain.range())
+                    })?;
+                }
+
+                if let Some(committed_chain) = notification.committed_chain() {
+                    index.commit_chain(&committed_chain).map_err(|err| {
+                        eyre::eyre!("Failed to index tags of blocks {:?}: {err}", committed_chain.range())
+                    })?;
+                    ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))?;
+                }
+            }
+
+            _ = expiry_interval.tick() => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+                match index.expire_pending_tags(expiry.cutoff(now) as u64)? {
+                    0 => {}
+                    expired => info!(target: "wvm::tag_index", expired, "Expired orphaned tags"),
+                }
+            }
+        }
+    }
+
+    info!(target: "wvm::tag_index", "Tag index shutting down");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tag_index_tests {
+    use crate::exex::tag_index::{next_match, next_position, DeferredTagIndex};
+    use load_db::query::{TagIndex, TagMatch, TagQuery, TagQueryPage};
+    use reth::primitives::revm_primitives::alloy_primitives::TxHash;
+    use reth_db::{
+        models::{wvm_tag_key, WvmTagIndexKey, WvmTxKey},
+        tables,
+        test_utils::create_test_rw_db,
+        transaction::{DbTx, DbTxMut},
+        Database, DatabaseError,
+    };
+    use std::sync::Arc;
+
+    struct EmptyTagIndex;
+
+    impl TagIndex for EmptyTagIndex {
+        fn save_pending_tags(
+            &self,
+            _tx_hash: &str,
+            _tags: Vec<(String, String)>,
+        ) -> eyre::Result<bool> {
+            Ok(true)
+        }
+
+        fn discard_pending_tags(&self, _tx_hash: &str) -> eyre::Result<()> {
+            Ok(())
+        }
+
+        fn query_transactions(&self, _query: &TagQuery) -> eyre::Result<TagQueryPage> {
+            Ok(TagQueryPage::default())
+        }
+    }
+
+    #[test]
+    pub fn test_next_match() -> eyre::Result<()> {
+        let db = create_test_rw_db();
+        let app = wvm_tag_key("App-Name", "wvm");
+        let kind = wvm_tag_key("Type", "image");
+        let other = wvm_tag_key("Type", "text");
+
+        let tx = db.tx_mut()?;
+        let index = [
+            (app, WvmTxKey::new(1, 0)),
+            (app, WvmTxKey::new(1, 3)),
+            (app, WvmTxKey::new(4, 1)),
+            (kind, WvmTxKey::new(1, 3)),
+            (kind, WvmTxKey::new(2, 0)),
+            (kind, WvmTxKey::new(4, 1)),
+            (other, WvmTxKey::new(3, 0)),
+        ];
+        for (tag, key) in index {
+            tx.put::<tables::WvmTagIndex>(WvmTagIndexKey::new(tag, key), TxHash::ZERO)?;
+        }
+        tx.commit()?;
+
+        let tx = db.tx()?;
+        let mut cursor = tx.cursor_read::<tables::WvmTagIndex>()?;
+        let mut matches = |tags: &[_], match_mode, to_block| {
+            let mut found = vec![];
+            let mut from = Some(WvmTxKey::default());
+            while let Some(position) = from {
+                let Some(key) = next_match(&mut cursor, tags, match_mode, position, to_block)?
+                else {
+                    break;
+                };
+                found.push(key);
+                from = next_position(key);
+            }
+            Ok::<_, DatabaseError>(found)
+        };
+
+        assert_eq!(
+            matches(&[app, kind], TagMatch::All, u64::MAX)?,
+            vec![WvmTxKey::new(1, 3), WvmTxKey::new(4, 1)]
+        );
+        assert_eq!(matches(&[app, kind], TagMatch::All, 3)?, vec![WvmTxKey::new(1, 3)]);
+        assert_eq!(matches(&[app, kind, other], TagMatch::All, u64::MAX)?, vec![]);
+        assert_eq!(
+            matches(&[kind, other], TagMatch::Any, u64::MAX)?,
+            vec![
+                WvmTxKey::new(1, 3),
+                WvmTxKey::new(2, 0),
+                WvmTxKey::new(3, 0),
+                WvmTxKey::new(4, 1)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_next_position() {
+        assert_eq!(next_position(WvmTxKey::new(1, 2)), Some(WvmTxKey::new(1, 3)));
+        assert_eq!(next_position(WvmTxKey::new(1, u32::MAX)), Some(WvmTxKey::new(2, 0)));
+        assert_eq!(next_position(WvmTxKey::new(u64::MAX, u32::MAX)), None);
+    }
+
+    #[test]
+    pub fn test_deferred_tag_index() {
+        let deferred = DeferredTagIndex::default();
+        let rpc = deferred.clone();
+        assert!(rpc.query_transactions(&TagQuery::default()).is_err());
+        assert!(rpc.save_pending_tags(&format!("{:#x}", TxHash::ZERO), vec![]).is_err());
+
+        assert!(deferred.set(Arc::new(EmptyTagIndex)));
+        assert!(!deferred.set(Arc::new(EmptyTagIndex)));
+        assert_eq!(rpc.query_transactions(&TagQuery::default()).unwrap(), TagQueryPage::default());
+        assert!(rpc.save_pending_tags(&format!("{:#x}", TxHash::ZERO), vec![]).unwrap());
+    }
+}
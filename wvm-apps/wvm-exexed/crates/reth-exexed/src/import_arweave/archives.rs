@@ -0,0 +1,372 @@
+//! Lookup, download and verification of archived blocks.
+
+use futures::{stream, StreamExt};
+use load_db::LoadDbConnection;
+use precompiles::{
+    inner::graphql_util::{build_transaction_query, send_graphql, Tag},
+    WVM_DATA_PUBLISHERS,
+};
+use rbrotli::codec::{
+    detect_encoding, CodecRegistry, ARCHIVE_CODECS, DICTIONARY_TAG, ENCODING_TAG, MAX_ARCHIVE_SIZE,
+};
+use reth::primitives::{revm_primitives::alloy_primitives::B256, SealedBlock};
+use std::{collections::BTreeMap, io::Read, ops::RangeInclusive, sync::Arc, time::Duration};
+use tracing::warn;
+use wvm_borsh::block::BorshSealedBlockWithSenders;
+
+/// Block numbers resolved by a single GraphQL query, blocks archived several times take more
+/// than one edge so the page is larger.
+const GRAPHQL_BLOCKS_PER_QUERY: usize = 50;
+const GRAPHQL_PAGE_SIZE: usize = 100;
+
+/// Arweave transaction holding an archived block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveRef {
+    pub arweave_id: String,
+    /// Hash of the block as tagged or indexed, checked against the downloaded block.
+    pub block_hash: Option<B256>,
+    /// `None` when the archive was resolved without its tags, the encoding is then detected.
+    pub encoding: Option<String>,
+    pub dictionary: Option<String>,
+}
+
+/// Where the archives of block numbers are looked up.
+#[derive(Clone)]
+pub enum ArchiveIndex {
+    LoadDb(Arc<dyn LoadDbConnection + Send + Sync + 'static>),
+    /// `Block-Number` tags of the transactions of [`WVM_DATA_PUBLISHERS`].
+    GraphQl {
+        gateway: String,
+    },
+}
+
+impl ArchiveIndex {
+    /// Archives of the blocks of `range`, a block may have been archived more than once.
+    pub async fn resolve(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> eyre::Result<BTreeMap<u64, Vec<ArchiveRef>>> {
+        let mut archives: BTreeMap<u64, Vec<ArchiveRef>> = BTreeMap::new();
+
+        match self {
+            ArchiveIndex::LoadDb(load_db) => {
+                for number in range {
+                    if let Some(state) = load_db.query_raw_state(number.to_string()).await? {
+                        archives.entry(number).or_default().push(ArchiveRef {
+                            arweave_id: state.arweave_id,
+                            block_hash: state.block_hash.parse().ok(),
+                            encoding: None,
+                            dictionary: None,
+                        });
+                    }
+                }
+            }
+            ArchiveIndex::GraphQl { gateway } => {
+                let numbers: Vec<String> = range.map(|number| number.to_string()).collect();
+                for chunk in numbers.chunks(GRAPHQL_BLOCKS_PER_QUERY) {
+                    let query = blocks_query(chunk);
+                    let gateway = gateway.clone();
+                    let response =
+                        tokio::task::spawn_blocking(move || send_graphql(&gateway, &query))
+                            .await??;
+
+                    for edge in response.data.transactions.edges {
+                        let tags = edge.node.tags.unwrap_or_default();
+                        if let Some((number, archive)) = archive_from_tags(edge.node.id, &tags) {
+                            archives.entry(number).or_default().push(archive);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(archives)
+    }
+}
+
+fn blocks_query(numbers: &[String]) -> String {
+    let query = build_transaction_query(
+        None,
+        Some(&[
+            ("Block-Number".to_string(), numbers.to_vec()),
+            ("Protocol".to_string(), vec!["WeaveVM-ExEx".to_string()]),
+        ]),
+        Some(&WVM_DATA_PUBLISHERS.map(|i| i.to_string())),
+        None,
+        false,
+        &[],
+    );
+
+    query.replacen("transactions(", &format!("transactions(first: {},\n", GRAPHQL_PAGE_SIZE), 1)
+}
+
+/// Block number and archive of a transaction, `None` if it isn't a block archive, such as the
+/// attestations uploaded along with archives.
+pub(crate) fn archive_from_tags(arweave_id: String, tags: &[Tag]) -> Option<(u64, ArchiveRef)> {
+    let tag = |name: &str| tags.iter().find(|tag| tag.name == name).map(|tag| tag.value.clone());
+
+    let encoding = tag(ENCODING_TAG).filter(|encoding| CodecRegistry::supports(encoding))?;
+    let number = tag("Block-Number")?.parse().ok()?;
+
+    Some((
+        number,
+        ArchiveRef {
+            arweave_id,
+            block_hash: tag("Block-Hash").and_then(|hash| hash.parse().ok()),
+            encoding: Some(encoding),
+            dictionary: tag(DICTIONARY_TAG),
+        },
+    ))
+}
+
+/// Downloads archives of an [`ArchiveIndex`] and checks they form a chain.
+#[derive(Clone)]
+pub struct ArchiveFetcher {
+    pub index: ArchiveIndex,
+    pub gateway: String,
+    /// Archives downloaded at once.
+    pub concurrency: usize,
+    /// Dictionary of the archives resolved without their tags.
+    pub dictionary: Option<String>,
+}
+
+impl ArchiveFetcher {
+    /// Blocks of `range`, the first one being the child of `parent_hash`.
+    ///
+    /// When a block was archived several times, the archive extending the chain is picked.
+    pub async fn fetch_chain(
+        &self,
+        range: RangeInclusive<u64>,
+        mut parent_hash: B256,
+    ) -> eyre::Result<Vec<SealedBlock>> {
+        let mut archives = self.resolve(range.clone()).await?;
+        for number in range.clone() {
+            if archives.get(&number).map_or(true, Vec::is_empty) {
+                eyre::bail!("Block {} was not found on Arweave", number);
+            }
+        }
+
+        // Blocks are usually archived once, the first archives are downloaded concurrently.
+        let first_archives: Vec<_> = stream::iter(range.clone())
+            .map(|number| self.download(archives[&number][0].clone()))
+            .buffered(self.concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut blocks = Vec::with_capacity(first_archives.len());
+        for (number, first_archive) in range.zip(first_archives) {
+            let candidates = archives.remove(&number).unwrap_or_default();
+            let mut downloaded = Some(first_archive);
+            let mut block = None;
+
+            for (i, archive) in candidates.iter().enumerate() {
+                let result = match downloaded.take() {
+                    Some(result) if i == 0 => result,
+                    _ => self.download(archive.clone()).await,
+                };
+                match result.and_then(|candidate| {
+                    verify_archived_block(&candidate, number, parent_hash, archive.block_hash)
+                        .map(|_| candidate)
+                }) {
+                    Ok(candidate) => {
+                        block = Some(candidate);
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(
+                            target: "wvm::import",
+                            arweave_id = archive.arweave_id,
+                            %e,
+                            "Skipping archive of block {}",
+                            number
+                        );
+                    }
+                }
+            }
+
+            let block = block.ok_or_else(|| {
+                eyre::eyre!("No archive of block {} extends the chain at {}", number, parent_hash)
+            })?;
+            parent_hash = block.hash();
+            blocks.push(block);
+        }
+
+        Ok(blocks)
+    }
+
+    async fn resolve(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> eyre::Result<BTreeMap<u64, Vec<ArchiveRef>>> {
+        let mut archives = self.index.resolve(range).await?;
+        for archive in archives.values_mut().flatten() {
+            if archive.encoding.is_none() && archive.dictionary.is_none() {
+                archive.dictionary = self.dictionary.clone();
+            }
+        }
+
+        Ok(archives)
+    }
+
+    async fn download(&self, archive: ArchiveRef) -> eyre::Result<SealedBlock> {
+        let gateway = self.gateway.clone();
+        tokio::task::spawn_blocking(move || {
+            let data = download(&gateway, &archive.arweave_id, MAX_ARCHIVE_SIZE)?;
+            decode_archive(&data, &archive, MAX_ARCHIVE_SIZE, |id| {
+                download(&gateway, id, MAX_ARCHIVE_SIZE).ok()
+            })
+        })
+        .await?
+    }
+}
+
+/// Data of an Arweave transaction, at most `limit` bytes.
+pub(crate) fn download(gateway: &str, arweave_id: &str, limit: usize) -> eyre::Result<Vec<u8>> {
+    let url = format!("{}/{}", gateway.trim_end_matches('/'), arweave_id);
+    let response = ureq::get(&url).timeout(Duration::from_secs(60)).call()?;
+
+    let mut data = Vec::new();
+    response.into_reader().take(limit as u64 + 1).read_to_end(&mut data)?;
+    if data.len() > limit {
+        eyre::bail!("Archive {} exceeds {} bytes", arweave_id, limit);
+    }
+
+    Ok(data)
+}
+
+/// Decodes the block archived in `data`, decompressed into at most `limit` bytes.
+pub fn decode_archive<F>(
+    data: &[u8],
+    archive: &ArchiveRef,
+    limit: usize,
+    fetch_dictionary: F,
+) -> eyre::Result<SealedBlock>
+where
+    F: FnOnce(&str) -> Option<Vec<u8>>,
+{
+    let encoding = match &archive.encoding {
+        Some(encoding) => encoding.as_str(),
+        None => detect_encoding(data, archive.dictionary.is_some()),
+    };
+    let borsh_data = ARCHIVE_CODECS.decompress(
+        encoding,
+        archive.dictionary.as_deref(),
+        data,
+        limit,
+        fetch_dictionary,
+    )?;
+    let block = borsh::from_slice::<BorshSealedBlockWithSenders>(&borsh_data)?;
+
+    Ok(block.0.into_sealed_block_with_senders().block)
+}
+
+/// Checks that `block` is block `number`, child of `parent_hash`.
+///
+/// The hash of the block is computed from its header rather than trusted from the archive, the
+/// body is checked against the header by the bodies stage.
+pub fn verify_archived_block(
+    block: &SealedBlock,
+    number: u64,
+    parent_hash: B256,
+    archived_hash: Option<B256>,
+) -> eyre::Result<()> {
+    let hash = block.header.hash_slow();
+    if hash != block.hash() || archived_hash.is_some_and(|archived_hash| archived_hash != hash) {
+        eyre::bail!("Hash of block {} doesn't match its header", number);
+    }
+    if block.number != number {
+        eyre::bail!("Archive holds block {} instead of {}", block.number, number);
+    }
+    if block.parent_hash != parent_hash {
+        eyre::bail!("Block {} isn't a child of {}", number, parent_hash);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod archives_tests {
+    use crate::import_arweave::archives::{
+        archive_from_tags, blocks_query, decode_archive, verify_archived_block, ArchiveRef,
+    };
+    use precompiles::inner::graphql_util::Tag;
+    use rbrotli::codec::ArchiveCodec;
+    use reth::primitives::{
+        revm_primitives::alloy_primitives::B256, Header, SealedBlock, SealedBlockWithSenders,
+        SealedHeader,
+    };
+    use wvm_borsh::block::BorshSealedBlockWithSenders;
+    use wvm_tx::wvm::{v1::V1WvmSealedBlockWithSenders, WvmSealedBlockWithSenders};
+
+    fn block(number: u64, parent_hash: B256) -> SealedBlock {
+        let header = Header { number, parent_hash, ..Default::default() };
+        let hash = header.hash_slow();
+        SealedBlock { header: SealedHeader::new(header, hash), ..Default::default() }
+    }
+
+    fn tag(name: &str, value: &str) -> Tag {
+        Tag { name: name.to_string(), value: value.to_string() }
+    }
+
+    #[test]
+    pub fn test_verify_archived_block() {
+        let parent = block(1, B256::ZERO);
+        let child = block(2, parent.hash());
+
+        assert!(verify_archived_block(&child, 2, parent.hash(), Some(child.hash())).is_ok());
+        assert!(verify_archived_block(&child, 2, parent.hash(), None).is_ok());
+        assert!(verify_archived_block(&child, 3, parent.hash(), None).is_err());
+        assert!(verify_archived_block(&child, 2, B256::ZERO, None).is_err());
+        assert!(verify_archived_block(&child, 2, parent.hash(), Some(parent.hash())).is_err());
+
+        // Header altered after being sealed.
+        let mut tampered = child.clone();
+        tampered.header = SealedHeader::new(
+            Header { gas_used: 1, ..tampered.header.clone().unseal() },
+            child.hash(),
+        );
+        assert!(verify_archived_block(&tampered, 2, parent.hash(), None).is_err());
+    }
+
+    #[test]
+    pub fn test_decode_archive() {
+        let sealed_block = block(7, B256::ZERO);
+        let borsh_data = borsh::to_vec(&BorshSealedBlockWithSenders(
+            WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(
+                SealedBlockWithSenders { block: sealed_block.clone(), senders: vec![] },
+            )),
+        ))
+        .unwrap();
+
+        for codec in [ArchiveCodec::default(), ArchiveCodec::Zstd { level: 3 }] {
+            let archive = ArchiveRef {
+                arweave_id: String::from("archive"),
+                block_hash: Some(sealed_block.hash()),
+                encoding: None,
+                dictionary: None,
+            };
+            let data = codec.compress(&borsh_data).unwrap();
+            let decoded = decode_archive(&data, &archive, MAX_ARCHIVE_SIZE, |_| None).unwrap();
+            assert_eq!(decoded, sealed_block);
+        }
+    }
+
+    #[test]
+    pub fn test_archive_from_tags() {
+        let tags = vec![
+            tag("WeaveVM:Encoding", "Borsh-Brotli"),
+            tag("Block-Number", "12"),
+            tag("Block-Hash", &B256::with_last_byte(1).to_string()),
+        ];
+        let (number, archive) = archive_from_tags(String::from("tx"), &tags).unwrap();
+        assert_eq!(number, 12);
+        assert_eq!(archive.block_hash, Some(B256::with_last_byte(1)));
+        assert_eq!(archive.encoding.as_deref(), Some("Borsh-Brotli"));
+
+        // Attestations of the archives carry the block number too.
+        let attestation = vec![tag("WeaveVM:Encoding", "JSON"), tag("Block-Number", "12")];
+        assert!(archive_from_tags(String::from("tx"), &attestation).is_none());
+
+        assert!(blocks_query(&[String::from("12")]).contains("transactions(first: 100,"));
+    }
+}
@@ -0,0 +1,180 @@
+//! `import-arweave` command, initializing the node from the blocks archived to Arweave.
+
+pub(crate) mod archives;
+
+use archives::{ArchiveFetcher, ArchiveIndex};
+use clap::{Parser, ValueEnum};
+use precompiles::{inner::util::DEFAULT_ARWEAVE_TX_ENDPOINT, node::wvm_block_executor};
+use reth::{
+    args::LogArgs,
+    beacon_consensus::EthBeaconConsensus,
+    chainspec::EthereumChainSpecParser,
+    providers::{BlockNumReader, HeaderProvider, ProviderError, StageCheckpointReader},
+    CliRunner,
+};
+use reth_cli_commands::{
+    common::{AccessRights, Environment, EnvironmentArgs},
+    import::build_import_pipeline,
+};
+use reth_db::tables;
+use reth_db_api::transaction::DbTx;
+use reth_downloaders::file_client::FileClient;
+use reth_node_ethereum::EthereumNode;
+use reth_primitives::SealedBlock;
+use reth_prune::PruneModes;
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use std::sync::Arc;
+use tracing::info;
+use wvm_static::{load_db_client, SUPERVISOR_RT};
+
+/// Name of the command, dispatched before the reth CLI is parsed.
+pub const COMMAND_NAME: &str = "import-arweave";
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum IndexName {
+    /// Block numbers indexed by LoadDb
+    #[value(name = "loaddb")]
+    LoadDb,
+    /// Tags of the archives, queried with the gateway GraphQL
+    #[value(name = "graphql")]
+    GraphQl,
+}
+
+/// Syncs the chain from the blocks archived to Arweave.
+///
+/// Blocks are imported from the local head up to `--to`, their hashes being checked to form a
+/// chain before they go through the regular import stages.
+#[derive(Debug, Parser)]
+#[command(name = COMMAND_NAME)]
+pub struct ImportArweaveCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs<EthereumChainSpecParser>,
+
+    #[command(flatten)]
+    logs: LogArgs,
+
+    /// Disables stages that require state.
+    #[arg(long)]
+    no_state: bool,
+
+    /// Last block to import.
+    #[arg(long)]
+    to: u64,
+
+    /// Blocks downloaded before running the pipeline.
+    #[arg(long, default_value_t = 1000)]
+    batch_size: u64,
+
+    /// Index the archives of block numbers are looked up in.
+    #[arg(long, value_enum, default_value = "graphql")]
+    index: IndexName,
+
+    #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = DEFAULT_ARWEAVE_TX_ENDPOINT)]
+    gateway: String,
+
+    /// Archives downloaded at once.
+    #[arg(long, default_value_t = 16)]
+    concurrency: usize,
+
+    /// Arweave id of the zstd dictionary of archives indexed without their encoding.
+    #[arg(long, env = "WVM_ARCHIVE_ZSTD_DICTIONARY")]
+    dictionary: Option<String>,
+}
+
+impl ImportArweaveCommand {
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.no_state {
+            info!(target: "wvm::import", "Disabled stages requiring state");
+        }
+
+        let Environment { provider_factory, config, .. } =
+            self.env.init::<EthereumNode>(AccessRights::RW)?;
+
+        let (_, executor) = wvm_block_executor(self.env.chain.clone(), provider_factory.clone());
+        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+
+        let fetcher = ArchiveFetcher {
+            index: match self.index {
+                IndexName::LoadDb => ArchiveIndex::LoadDb(load_db_client()?),
+                IndexName::GraphQl => ArchiveIndex::GraphQl { gateway: self.gateway.clone() },
+            },
+            gateway: self.gateway.clone(),
+            concurrency: self.concurrency,
+            dictionary: self.dictionary.clone(),
+        };
+
+        let mut total_fetched_blocks = 0;
+        let mut total_fetched_txns = 0;
+
+        loop {
+            let local_head = provider_factory.last_block_number()?;
+            if local_head >= self.to {
+                break;
+            }
+
+            let parent = provider_factory
+                .sealed_header(local_head)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(local_head.into()))?;
+            let range = local_head + 1..=self.to.min(local_head + self.batch_size.max(1));
+            info!(target: "wvm::import", ?range, "Fetching archived blocks");
+
+            let blocks = fetcher.fetch_chain(range, parent.hash()).await?;
+            let tip = blocks.last().map(SealedBlock::hash).expect("range is not empty");
+            total_fetched_blocks += blocks.len();
+            total_fetched_txns +=
+                blocks.iter().map(|block| block.body.transactions.len()).sum::<usize>();
+
+            let file_client = FileClient::from_blocks(blocks.into_iter().map(SealedBlock::unseal));
+            let (mut pipeline, events) = build_import_pipeline(
+                &config,
+                provider_factory.clone(),
+                &consensus,
+                Arc::new(file_client),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                self.no_state,
+                executor.clone(),
+            )?;
+            pipeline.set_tip(tip);
+
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|checkpoint| checkpoint.block_number);
+            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+
+            pipeline.run().await?;
+
+            if provider_factory.last_block_number()? <= local_head {
+                eyre::bail!("Pipeline made no progress past block {}", local_head);
+            }
+        }
+
+        let provider = provider_factory.provider()?;
+        let total_imported_blocks = provider.tx_ref().entries::<tables::HeaderNumbers>()?;
+        let total_imported_txns = provider.tx_ref().entries::<tables::TransactionHashNumbers>()?;
+
+        info!(target: "wvm::import",
+            total_fetched_blocks,
+            total_fetched_txns,
+            total_imported_blocks,
+            total_imported_txns,
+            "Archived chain imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Runs `import-arweave`, the arguments following the command name.
+pub fn run() -> eyre::Result<()> {
+    let command = ImportArweaveCommand::parse_from(
+        std::iter::once(COMMAND_NAME.to_string()).chain(std::env::args().skip(2)),
+    );
+    let _guard = command.logs.init_tracing()?;
+
+    // LoadDb client is driven by the supervisor runtime
+    let _rt = &*SUPERVISOR_RT;
+
+    CliRunner::default().run_blocking_until_ctrl_c(command.execute())
+}
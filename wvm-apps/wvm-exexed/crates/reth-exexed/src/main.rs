@@ -2,29 +2,53 @@
 
 #![doc(issue_tracker_base_url = "https://github.com/weaveVM/wvm-reth/issues/")]
 
+mod archive_gateway;
 mod constant;
 mod exex;
+mod import_arweave;
 mod network_tag;
 mod util;
 
 use futures::StreamExt;
 use lambda::lambda::exex_lambda_processor;
-use load_db::drivers::planetscale::PlanetScaleDriver;
-use precompiles::node::WvmEthExecutorBuilder;
-use reth::{api::FullNodeComponents, args::PruningArgs, builder::NodeBuilder};
+use load_db::{
+    migrations::ensure_schema,
+    reconcile::{expire_orphaned_tags, TagExpiryConfig},
+};
+use precompiles::{
+    archive_registry::WvmPayloadServiceBuilder,
+    inner::util::{clean_gateway_url, download_tx, ARWEAVE_GATEWAY},
+    node::WvmEthExecutorBuilder,
+};
+use rbrotli::codec::{ArchiveCodec, ZstdDictionary, ARCHIVE_CODECS};
+use reth::{
+    api::FullNodeComponents,
+    args::PruningArgs,
+    builder::NodeBuilder,
+    providers::CanonStateSubscriptions,
+    rpc::server_types::eth::tag_reconciler::{reconcile_reorged_tags, PendingTagStore},
+};
 use reth_exex::{ExExContext, ExExEvent, ExExNotification};
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_primitives::constants::SLOT_DURATION;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use exex::ar_actor::ArweaveActorHandle;
-use wvm_static::{PRECOMPILE_LOADDB_CLIENT, SUPERVISOR_RT};
+use archive_gateway::GraphQlArchiveGateway;
+use exex::{
+    ar_actor::ArweaveActorHandle,
+    archive::{archive_state_diff_enabled, tip_archive},
+    state_snapshot::{exex_state_snapshot, StateSnapshotConfig},
+    tag_index::{exex_tag_index, DeferredTagIndex, NodeTagIndex},
+};
+use wvm_static::{load_db_client, SUPERVISOR_RT};
 
 async fn exex_etl_processor<Node: FullNodeComponents>(
     mut ctx: ExExContext<Node>,
     ar_actor_handle: Arc<ArweaveActorHandle>,
 ) -> eyre::Result<()> {
+    let with_state_diff = archive_state_diff_enabled();
+
     while let Some(notification_result) = ctx.notifications.next().await {
         let notification = match notification_result {
             Ok(notification) => notification,
@@ -54,8 +78,8 @@ async fn exex_etl_processor<Node: FullNodeComponents>(
         };
 
         if let Some(committed_chain) = notification.committed_chain() {
-            let block = committed_chain.tip().clone();
-            let block_number = block.number;
+            let block = tip_archive(&committed_chain, with_state_diff);
+            let block_number = block.block.header.header.number;
 
             if let Err(err) =
                 ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().num_hash()))
@@ -94,18 +118,49 @@ async fn exex_etl_processor<Node: FullNodeComponents>(
 
 /// Main loop of the exexed WVM node
 fn main() -> eyre::Result<()> {
+    if std::env::args().nth(1).as_deref() == Some(import_arweave::COMMAND_NAME) {
+        return import_arweave::run();
+    }
+
     let _rt = &*SUPERVISOR_RT;
-    let _bc = &*PRECOMPILE_LOADDB_CLIENT;
 
     reth::cli::Cli::parse_args().run(|builder, _| async move {
         // Initializations
-        let load_db_repo = init_planetscale_client().await;
+        let load_db_repo = load_db_client()?;
+        let auto_migrate = std::env::var("LOADDB_AUTO_MIGRATE").unwrap_or_default() == "true";
+        ensure_schema(load_db_repo.as_ref(), auto_migrate).await?;
+
+        // Tags of transactions which were never mined
+        let tag_expiry = TagExpiryConfig::from_env();
+        builder
+            .task_executor()
+            .spawn(Box::pin(expire_orphaned_tags(load_db_repo.clone(), tag_expiry)));
 
         let arweave_actor_buffer_size = std::env::var("ARWEAVE_ACTOR_BUFFER_SIZE")
             .unwrap_or_else(|_| "1024".to_string())
             .parse::<usize>()
             .unwrap_or(1024);
 
+        // Dictionary of `zstd-dict` is stored on Arweave
+        let fetch_dictionary = |id: &str| {
+            download_tx(0, clean_gateway_url(ARWEAVE_GATEWAY.as_str()), id.to_string())
+                .ok()
+                .map(|output| output.bytes.to_vec())
+        };
+        let archive_codec = ArchiveCodec::from_env(fetch_dictionary)?;
+        if let ArchiveCodec::ZstdDict { dictionary, .. } = &archive_codec {
+            ARCHIVE_CODECS.register_dictionary(dictionary.clone());
+        }
+
+        // The block precompile only reads archives whose dictionary was loaded here, downloading
+        // it while executing a block would neither be metered nor the same on every node.
+        for id in archive_dictionary_ids() {
+            let data = fetch_dictionary(&id)
+                .ok_or_else(|| eyre::eyre!("Archive dictionary {} could not be loaded", id))?;
+            ARCHIVE_CODECS.register_dictionary(ZstdDictionary { id, data: Arc::new(data) });
+        }
+        info!(target: "wvm::exex", encoding = archive_codec.encoding(), "Archive codec configured");
+
         let ar_actor_handle = Arc::new(
             ArweaveActorHandle::new_parallel(
                 std::env::var("ARWEAVE_ACTOR_BUFFER_SIZE")
@@ -116,12 +171,12 @@ fn main() -> eyre::Result<()> {
                     .unwrap_or_else(|_| "10".to_string())
                     .parse()
                     .unwrap_or(10),
-                Arc::new(load_db_repo),
+                load_db_repo.clone(),
+                archive_codec,
             )
             .await,
         );
 
-        let _init_fee_manager = &*reth_primitives::constants::WVM_FEE_MANAGER;
         // Original config
         let mut config = builder.config().clone();
         let pruning_args = config.pruning.clone();
@@ -134,12 +189,32 @@ fn main() -> eyre::Result<()> {
             });
         }
 
+        // Tag RPCs use the index kept in the node database instead of LoadDb, it's only created
+        // once the ExEx is launched.
+        let tag_index =
+            (std::env::var("WVM_TAG_INDEX").unwrap_or(String::from("false"))).to_lowercase();
+        let native_tag_index = (tag_index == "true").then(DeferredTagIndex::default);
+
+        let mut add_ons = EthereumAddOns::default()
+            .with_load_db(load_db_repo.clone())
+            // Storage proofs of archived blocks are described by the gateway
+            .with_archive_gateway(Arc::new(GraphQlArchiveGateway::from_env(
+                ARWEAVE_GATEWAY.as_str(),
+            )));
+        if let Some(native_tag_index) = native_tag_index.clone() {
+            add_ons = add_ons.with_tag_index(Arc::new(native_tag_index));
+        }
+
         let mut handle = NodeBuilder::new(config)
             .with_database(builder.db().clone())
             .with_launch_context(builder.task_executor().clone())
             .with_types::<EthereumNode>()
-            .with_components(EthereumNode::components().executor(WvmEthExecutorBuilder::default()))
-            .with_add_ons(EthereumAddOns::default());
+            .with_components(
+                EthereumNode::components()
+                    .payload(WvmPayloadServiceBuilder::default())
+                    .executor(WvmEthExecutorBuilder::default()),
+            )
+            .with_add_ons(add_ons);
 
         let run_exex = (std::env::var("RUN_EXEX").unwrap_or(String::from("false"))).to_lowercase();
         if run_exex == "true" {
@@ -149,12 +224,52 @@ fn main() -> eyre::Result<()> {
                 })
                 .install_exex("exex-lambda", |ctx| async move { Ok(exex_lambda_processor(ctx)) });
         }
+
+        if let Some(native_tag_index) = native_tag_index.clone() {
+            handle = handle.install_exex("exex-tag-index", |ctx| async move {
+                let (index, writes) = NodeTagIndex::new(ctx.provider().clone());
+                if !native_tag_index.set(Arc::new(index.clone())) {
+                    warn!(target: "wvm::tag_index", "Native tag index was already set");
+                }
+                Ok(exex_tag_index(ctx, index, writes, tag_expiry))
+            });
+        }
+        if let Some(snapshot_config) = StateSnapshotConfig::from_env() {
+            info!(
+                target: "wvm::state_snapshot",
+                interval = snapshot_config.interval,
+                "State snapshots enabled"
+            );
+            handle = handle.install_exex("exex-state-snapshot", |ctx| async move {
+                Ok(exex_state_snapshot(ctx, snapshot_config))
+            });
+        }
         let handle = handle.launch().await?;
 
+        // The native tag index reverts the tags of reorged blocks itself
+        if native_tag_index.is_none() {
+            handle.node.task_executor.spawn(Box::pin(reconcile_reorged_tags(
+                PendingTagStore::LoadDb(load_db_repo),
+                handle.node.provider.canonical_state_stream(),
+                tag_expiry.ttl,
+            )));
+        }
+
         handle.wait_for_node_exit().await
     })
 }
 
+/// Arweave ids of the dictionaries listed in `WVM_ARCHIVE_DICTIONARIES`, comma separated.
+fn archive_dictionary_ids() -> Vec<String> {
+    std::env::var("WVM_ARCHIVE_DICTIONARIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 fn parse_prune_config(prune_conf: &str) -> u64 {
     let mut d = "";
     if prune_conf == "true" {
@@ -168,15 +283,6 @@ fn parse_prune_config(prune_conf: &str) -> u64 {
     SLOT_DURATION.as_secs() * secs
 }
 
-async fn init_planetscale_client() -> PlanetScaleDriver {
-    let host = std::env::var("PS_HOST").unwrap_or_default();
-    let username = std::env::var("PS_USERNAME").unwrap_or_default();
-    let password = std::env::var("PS_PASSWORD").unwrap_or_default();
-
-    let planet_scale_driver = PlanetScaleDriver::new(host, username, password);
-    planet_scale_driver
-}
-
 #[cfg(test)]
 mod tests {
     use crate::parse_prune_config;
@@ -15,6 +15,7 @@ pub(crate) fn check_block_existence(block_hash: &str, irys: bool) -> bool {
         Some(&WVM_DATA_PUBLISHERS.map(|i| i.to_string())),
         Some("DESC".to_string()),
         false,
+        &[],
     );
 
     let gateway = {
@@ -1,11 +1,16 @@
-use crate::wvm::v1::{
-    header::V1WvmHeader, transaction::V1WvmTransactionSigned, V1WvmSealedBlock,
-    V1WvmSealedBlockWithSenders, V1WvmSealedHeader,
+use crate::wvm::{
+    v1::{
+        header::V1WvmHeader, transaction::V1WvmTransactionSigned, V1WvmSealedBlock,
+        V1WvmSealedBlockWithSenders, V1WvmSealedHeader,
+    },
+    v2::{transaction::V2WvmTransactionSigned, V2WvmSealedBlockWithSenders},
 };
+use reth_primitives::SealedBlockWithSenders;
 
 use paste::paste;
 
 pub mod v1;
+pub mod v2;
 
 // Define a trait for `magic_identifier`
 pub trait MagicIdentifier {
@@ -47,10 +52,28 @@ define_wvm_enum!(WvmSealedBlock, {
     V1(V1WvmSealedBlock) => 0u8,
 });
 
+// V2 adds the receipts of the block and optionally its state changes.
 define_wvm_enum!(WvmSealedBlockWithSenders, {
     V1(V1WvmSealedBlockWithSenders) => 0u8,
+    V2(V2WvmSealedBlockWithSenders) => 1u8,
 });
 
+impl WvmSealedBlockWithSenders {
+    pub fn block(&self) -> &V1WvmSealedBlock {
+        match self {
+            Self::V1(sealed_block_with_senders) => &sealed_block_with_senders.block,
+            Self::V2(sealed_block_with_senders) => &sealed_block_with_senders.block,
+        }
+    }
+
+    pub fn into_sealed_block_with_senders(self) -> SealedBlockWithSenders {
+        match self {
+            Self::V1(sealed_block_with_senders) => sealed_block_with_senders.into(),
+            Self::V2(sealed_block_with_senders) => sealed_block_with_senders.into(),
+        }
+    }
+}
+
 define_wvm_enum!(WvmHeader, {
     V1(V1WvmHeader) => 0u8,
 });
@@ -59,6 +82,18 @@ define_wvm_enum!(WvmSealedHeader, {
     V1(V1WvmSealedHeader) => 0u8,
 });
 
+// V2 only changes how the transaction itself is encoded, JSON in V1 and field level Borsh in V2.
 define_wvm_enum!(WvmTransactionSigned, {
     V1(V1WvmTransactionSigned) => 0u8,
+    V2(V2WvmTransactionSigned) => 1u8,
 });
+
+impl WvmTransactionSigned {
+    /// Blocks hold their transactions as [`V1WvmTransactionSigned`], whatever their encoding.
+    pub fn into_v1(self) -> V1WvmTransactionSigned {
+        match self {
+            Self::V1(transaction) => transaction,
+            Self::V2(transaction) => transaction.into(),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::wvm::{
+    v1::{V1WvmSealedBlock, V1WvmSealedBlockWithSenders},
+    v2::{receipt::V2WvmReceipt, state_diff::WvmStateDiff},
+};
+use alloy_primitives::{Address, B256};
+use reth_primitives::{proofs::calculate_receipt_root_no_memo, Receipt, SealedBlockWithSenders};
+use serde::{Deserialize, Serialize};
+
+pub mod receipt;
+pub mod state_diff;
+pub mod transaction;
+
+/// Archived block along with the outcome of its execution, which can be checked against the
+/// `receipts_root` of its header.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct V2WvmSealedBlockWithSenders {
+    pub block: V1WvmSealedBlock,
+    pub senders: Vec<Address>,
+    /// One receipt per transaction, in block order. `None` when the node archiving the block had
+    /// already pruned the receipt.
+    pub receipts: Vec<Option<V2WvmReceipt>>,
+    pub state_diff: Option<WvmStateDiff>,
+}
+
+impl V2WvmSealedBlockWithSenders {
+    pub fn new(
+        block: SealedBlockWithSenders,
+        receipts: Vec<Option<Receipt>>,
+        state_diff: Option<WvmStateDiff>,
+    ) -> Self {
+        V2WvmSealedBlockWithSenders {
+            block: block.block.into(),
+            senders: block.senders,
+            receipts: receipts.into_iter().map(|receipt| receipt.map(V2WvmReceipt::from)).collect(),
+            state_diff,
+        }
+    }
+
+    /// Whether some receipts were pruned before the block was archived.
+    pub fn has_pruned_receipts(&self) -> bool {
+        self.receipts.iter().any(Option::is_none)
+    }
+
+    /// Root of the receipts, `None` if some of them were pruned.
+    pub fn receipts_root(&self) -> Option<B256> {
+        let receipts = self
+            .receipts
+            .iter()
+            .cloned()
+            .map(|receipt| receipt.map(Into::into))
+            .collect::<Option<Vec<Receipt>>>()?;
+        Some(calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>()))
+    }
+
+    /// Whether the receipts are the ones committed to by the block header, which can't be told
+    /// when some of them were pruned.
+    pub fn verify_receipts_root(&self) -> bool {
+        self.receipts.len() == self.block.body.transactions.len() &&
+            self.receipts_root() == Some(self.block.header.header.receipts_root)
+    }
+}
+
+impl Into<SealedBlockWithSenders> for V2WvmSealedBlockWithSenders {
+    fn into(self) -> SealedBlockWithSenders {
+        SealedBlockWithSenders { block: self.block.into(), senders: self.senders }
+    }
+}
+
+impl Into<V1WvmSealedBlockWithSenders> for V2WvmSealedBlockWithSenders {
+    fn into(self) -> V1WvmSealedBlockWithSenders {
+        V1WvmSealedBlockWithSenders { block: self.block, senders: self.senders }
+    }
+}
@@ -0,0 +1,51 @@
+use alloy_primitives::Log;
+use reth_primitives::{Receipt, TxType};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a transaction, as found in the block receipts.
+///
+/// The deposit fields only exist on Optimism receipts. They're kept whatever the features of the
+/// build, so archives decode the same way everywhere, and are `None` on WVM.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct V2WvmReceipt {
+    pub tx_type: TxType,
+    pub success: bool,
+    pub cumulative_gas_used: u64,
+    pub logs: Vec<Log>,
+    pub deposit_nonce: Option<u64>,
+    pub deposit_receipt_version: Option<u64>,
+}
+
+impl From<Receipt> for V2WvmReceipt {
+    fn from(value: Receipt) -> Self {
+        V2WvmReceipt {
+            tx_type: value.tx_type,
+            success: value.success,
+            cumulative_gas_used: value.cumulative_gas_used,
+            logs: value.logs,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: value.deposit_nonce,
+            #[cfg(not(feature = "optimism"))]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: value.deposit_receipt_version,
+            #[cfg(not(feature = "optimism"))]
+            deposit_receipt_version: None,
+        }
+    }
+}
+
+impl Into<Receipt> for V2WvmReceipt {
+    fn into(self) -> Receipt {
+        Receipt {
+            tx_type: self.tx_type,
+            success: self.success,
+            cumulative_gas_used: self.cumulative_gas_used,
+            logs: self.logs,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: self.deposit_nonce,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: self.deposit_receipt_version,
+        }
+    }
+}
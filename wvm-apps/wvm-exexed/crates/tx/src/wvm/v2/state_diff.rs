@@ -0,0 +1,30 @@
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// State changes of a single block, enough to move the state of the previous block to the one of
+/// this block without executing it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmStateDiff {
+    /// Changed accounts, sorted by address.
+    pub accounts: Vec<WvmAccountDiff>,
+    /// Bytecode deployed in the block, by code hash.
+    pub contracts: Vec<(B256, Bytes)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmAccountDiff {
+    pub address: Address,
+    /// Account after the block, `None` if it was destroyed.
+    pub info: Option<WvmAccountInfo>,
+    /// Storage of the account was cleared before `storage` was applied.
+    pub wiped_storage: bool,
+    /// Changed slots and their value after the block, sorted by slot.
+    pub storage: Vec<(U256, U256)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WvmAccountInfo {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code_hash: B256,
+}
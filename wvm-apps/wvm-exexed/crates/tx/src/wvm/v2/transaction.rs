@@ -0,0 +1,53 @@
+use crate::{wvm::v1::transaction::V1WvmTransactionSigned, WvmTransaction};
+use alloy_primitives::{Signature, TxHash};
+use reth_primitives::TransactionSigned;
+use serde::{Deserialize, Serialize};
+
+/// Same content as [`V1WvmTransactionSigned`], archived with a field level Borsh encoding of the
+/// transaction instead of JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct V2WvmTransactionSigned {
+    pub hash: TxHash,
+    pub signature: Signature,
+    pub transaction: WvmTransaction,
+}
+
+impl Into<TransactionSigned> for V2WvmTransactionSigned {
+    fn into(self) -> TransactionSigned {
+        TransactionSigned {
+            hash: self.hash,
+            signature: self.signature,
+            transaction: self.transaction.into(),
+        }
+    }
+}
+
+impl From<TransactionSigned> for V2WvmTransactionSigned {
+    fn from(value: TransactionSigned) -> Self {
+        V2WvmTransactionSigned {
+            hash: value.hash,
+            signature: value.signature,
+            transaction: value.transaction.into(),
+        }
+    }
+}
+
+impl From<V1WvmTransactionSigned> for V2WvmTransactionSigned {
+    fn from(value: V1WvmTransactionSigned) -> Self {
+        V2WvmTransactionSigned {
+            hash: value.hash,
+            signature: value.signature,
+            transaction: value.transaction,
+        }
+    }
+}
+
+impl Into<V1WvmTransactionSigned> for V2WvmTransactionSigned {
+    fn into(self) -> V1WvmTransactionSigned {
+        V1WvmTransactionSigned {
+            hash: self.hash,
+            signature: self.signature,
+            transaction: self.transaction,
+        }
+    }
+}
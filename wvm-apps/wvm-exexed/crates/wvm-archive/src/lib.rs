@@ -0,0 +1,339 @@
+//! Decoding, encoding and conversion of the blocks archived to Arweave by the WVM ExEx.
+//!
+//! Archives are compressed Borsh encodings of [`WvmSealedBlockWithSenders`], the first byte of the
+//! Borsh payload being the magic identifier of the format version. Brotli and zstd archives are
+//! told apart by their content, archives compressed with a zstd dictionary need the dictionary.
+
+use clap::ValueEnum;
+use eyre::{bail, eyre};
+use rbrotli::codec::{detect_encoding, ArchiveCodec, CodecRegistry, ZstdDictionary};
+use serde::{Deserialize, Serialize};
+use std::{fmt, io::Read, time::Duration};
+use wvm_borsh::block::BorshSealedBlockWithSenders;
+use wvm_tx::wvm::{
+    v1::V1WvmSealedBlockWithSenders,
+    v2::{receipt::V2WvmReceipt, V2WvmSealedBlockWithSenders},
+    MagicIdentifier, WvmSealedBlockWithSenders,
+};
+
+pub use rbrotli::codec::MAX_ARCHIVE_SIZE;
+
+pub const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net";
+
+/// Format version of an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveVersion {
+    /// Header, transactions, ommers, withdrawals and senders
+    V1,
+    /// V1 content along with the receipts and optionally the state diff of the block
+    V2,
+}
+
+impl ArchiveVersion {
+    pub fn from_magic(magic: u8) -> eyre::Result<Self> {
+        match magic {
+            0u8 => Ok(Self::V1),
+            1u8 => Ok(Self::V2),
+            _ => bail!("Unknown archive magic identifier {}", magic),
+        }
+    }
+
+    pub fn of(block: &WvmSealedBlockWithSenders) -> Self {
+        match block {
+            WvmSealedBlockWithSenders::V1(_) => Self::V1,
+            WvmSealedBlockWithSenders::V2(_) => Self::V2,
+        }
+    }
+}
+
+impl fmt::Display for ArchiveVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 => f.write_str("v1"),
+            Self::V2 => f.write_str("v2"),
+        }
+    }
+}
+
+/// JSON representation of an archive, tagged with its format version.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum ArchiveJson {
+    V1(V1WvmSealedBlockWithSenders),
+    V2(V2WvmSealedBlockWithSenders),
+}
+
+impl From<WvmSealedBlockWithSenders> for ArchiveJson {
+    fn from(value: WvmSealedBlockWithSenders) -> Self {
+        match value {
+            WvmSealedBlockWithSenders::V1(block) => Self::V1(block),
+            WvmSealedBlockWithSenders::V2(block) => Self::V2(block),
+        }
+    }
+}
+
+impl Into<WvmSealedBlockWithSenders> for ArchiveJson {
+    fn into(self) -> WvmSealedBlockWithSenders {
+        match self {
+            Self::V1(block) => WvmSealedBlockWithSenders::V1(block),
+            Self::V2(block) => WvmSealedBlockWithSenders::V2(block),
+        }
+    }
+}
+
+/// Borsh payload of an archive.
+pub fn decompress_archive(
+    data: &[u8],
+    dictionary: Option<&ZstdDictionary>,
+) -> eyre::Result<Vec<u8>> {
+    let registry = CodecRegistry::default();
+    if let Some(dictionary) = dictionary {
+        registry.register_dictionary(dictionary.clone());
+    }
+
+    let encoding = detect_encoding(data, dictionary.is_some());
+    registry
+        .decompress(encoding, dictionary.map(|d| d.id.as_str()), data, MAX_ARCHIVE_SIZE, |_| None)
+        .map_err(|e| eyre!("Invalid {} archive: {}", encoding, e))
+}
+
+/// Decodes an archive, checking its magic identifier and that it holds nothing but the block.
+pub fn decode_archive(
+    data: &[u8],
+    dictionary: Option<&ZstdDictionary>,
+) -> eyre::Result<WvmSealedBlockWithSenders> {
+    let borsh_data = decompress_archive(data, dictionary)?;
+    let magic = *borsh_data.first().ok_or_else(|| eyre!("Archive is empty"))?;
+    let version = ArchiveVersion::from_magic(magic)?;
+
+    let block = borsh::from_slice::<BorshSealedBlockWithSenders>(&borsh_data)
+        .map_err(|e| eyre!("Invalid {} archive: {}", version, e))?;
+
+    Ok(block.0)
+}
+
+pub fn encode_archive(
+    block: WvmSealedBlockWithSenders,
+    codec: &ArchiveCodec,
+) -> eyre::Result<Vec<u8>> {
+    let borsh_data = borsh::to_vec(&BorshSealedBlockWithSenders(block))?;
+    Ok(codec.compress(&borsh_data)?)
+}
+
+/// Converts `block` to the `to` format version.
+///
+/// V1 archives don't hold receipts, `receipts` must then be given to convert to V2 and match the
+/// `receipts_root` of the block.
+pub fn convert_archive(
+    block: WvmSealedBlockWithSenders,
+    to: ArchiveVersion,
+    receipts: Option<Vec<V2WvmReceipt>>,
+) -> eyre::Result<WvmSealedBlockWithSenders> {
+    match (block, to) {
+        (WvmSealedBlockWithSenders::V1(block), ArchiveVersion::V1) => {
+            Ok(WvmSealedBlockWithSenders::V1(block))
+        }
+        (WvmSealedBlockWithSenders::V2(block), ArchiveVersion::V1) => {
+            Ok(WvmSealedBlockWithSenders::V1(block.into()))
+        }
+        (WvmSealedBlockWithSenders::V2(block), ArchiveVersion::V2) => {
+            Ok(WvmSealedBlockWithSenders::V2(block))
+        }
+        (WvmSealedBlockWithSenders::V1(block), ArchiveVersion::V2) => {
+            let Some(receipts) = receipts else {
+                bail!("Receipts of the block are required to convert a v1 archive to v2");
+            };
+
+            let block = V2WvmSealedBlockWithSenders {
+                block: block.block,
+                senders: block.senders,
+                receipts: receipts.into_iter().map(Some).collect(),
+                state_diff: None,
+            };
+            if !block.verify_receipts_root() {
+                bail!("Receipts don't match the receipts root of the block");
+            }
+
+            Ok(WvmSealedBlockWithSenders::V2(block))
+        }
+    }
+}
+
+/// Arweave transaction ids are 43 characters of base64url.
+pub fn is_arweave_tx_id(value: &str) -> bool {
+    value.len() == 43 && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Downloads the data of an Arweave transaction.
+pub fn fetch_archive(gateway: &str, tx_id: &str) -> eyre::Result<Vec<u8>> {
+    let url = format!("{}/{}", gateway.trim_end_matches('/'), tx_id);
+    let response = ureq::get(&url)
+        .timeout(Duration::from_secs(60))
+        .call()
+        .map_err(|e| eyre!("Failed to download {}: {}", url, e))?;
+
+    let mut data = Vec::new();
+    response.into_reader().take(MAX_ARCHIVE_SIZE as u64 + 1).read_to_end(&mut data)?;
+    if data.len() > MAX_ARCHIVE_SIZE {
+        bail!("Archive {} exceeds {} bytes", tx_id, MAX_ARCHIVE_SIZE);
+    }
+
+    Ok(data)
+}
+
+/// One line description of an archive.
+pub fn summary(block: &WvmSealedBlockWithSenders) -> String {
+    let sealed_block = block.block();
+    let mut summary = format!(
+        "format {} (magic {}), block {} {}, {} transactions",
+        ArchiveVersion::of(block),
+        block.magic_identifier(),
+        sealed_block.header.header.number,
+        sealed_block.header.hash,
+        sealed_block.body.transactions.len()
+    );
+
+    if let WvmSealedBlockWithSenders::V2(block) = block {
+        let receipts_root = if block.has_pruned_receipts() {
+            "pruned"
+        } else if block.verify_receipts_root() {
+            "valid"
+        } else {
+            "INVALID"
+        };
+        summary.push_str(&format!(
+            ", {} receipts ({} receipts root), state diff {}",
+            block.receipts.len(),
+            receipts_root,
+            if block.state_diff.is_some() { "included" } else { "not included" }
+        ));
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use crate::{
+        convert_archive, decode_archive, encode_archive, is_arweave_tx_id, ArchiveJson,
+        ArchiveVersion,
+    };
+    use rbrotli::{
+        codec::{ArchiveCodec, ZstdDictionary},
+        to_brotli,
+    };
+    use reth_primitives::{
+        Header, Receipt, SealedBlockWithSenders, SealedHeader, TransactionSigned, TxType,
+    };
+    use std::sync::Arc;
+    use wvm_tx::wvm::{
+        v1::V1WvmSealedBlockWithSenders,
+        v2::{receipt::V2WvmReceipt, V2WvmSealedBlockWithSenders},
+        WvmSealedBlockWithSenders,
+    };
+
+    fn block_with_receipts() -> (SealedBlockWithSenders, Vec<V2WvmReceipt>) {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.transactions = vec![TransactionSigned::default()];
+        let receipts = vec![Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: 21_000,
+            ..Default::default()
+        }];
+
+        let receipts_root = V2WvmSealedBlockWithSenders::new(
+            block.clone(),
+            receipts.iter().cloned().map(Some).collect(),
+            None,
+        )
+        .receipts_root()
+        .unwrap();
+        let header = Header { receipts_root, ..Default::default() };
+        block.block.header = SealedHeader::new(header, Default::default());
+
+        (block, receipts.into_iter().map(V2WvmReceipt::from).collect())
+    }
+
+    #[test]
+    pub fn test_json_roundtrip() {
+        let (block, _) = block_with_receipts();
+        let archive = encode_archive(
+            WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(block.clone())),
+            &ArchiveCodec::default(),
+        )
+        .unwrap();
+
+        let json =
+            serde_json::to_string(&ArchiveJson::from(decode_archive(&archive, None).unwrap()))
+                .unwrap();
+        assert!(json.starts_with(r#"{"format":"v1""#));
+
+        let from_json: ArchiveJson = serde_json::from_str(&json).unwrap();
+        let archive = encode_archive(from_json.into(), &ArchiveCodec::default()).unwrap();
+        let decoded = decode_archive(&archive, None).unwrap();
+        assert_eq!(block, decoded.into_sealed_block_with_senders());
+    }
+
+    #[test]
+    pub fn test_convert() {
+        let (block, receipts) = block_with_receipts();
+        let v1 = WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(block.clone()));
+
+        assert!(convert_archive(v1, ArchiveVersion::V2, None).is_err());
+
+        let v1 = WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(block.clone()));
+        let v2 = convert_archive(v1, ArchiveVersion::V2, Some(receipts)).unwrap();
+        let archive = encode_archive(v2, &ArchiveCodec::Zstd { level: 3 }).unwrap();
+        let decoded = decode_archive(&archive, None).unwrap();
+        assert_eq!(ArchiveVersion::of(&decoded), ArchiveVersion::V2);
+
+        let v1 = convert_archive(decoded, ArchiveVersion::V1, None).unwrap();
+        assert_eq!(ArchiveVersion::of(&v1), ArchiveVersion::V1);
+        assert_eq!(block, v1.into_sealed_block_with_senders());
+    }
+
+    #[test]
+    pub fn test_invalid_archive() {
+        assert!(decode_archive(b"not brotli", None).is_err());
+        assert!(decode_archive(&to_brotli(&[]).unwrap(), None).is_err());
+        assert!(decode_archive(&to_brotli(&[7u8, 0, 0]).unwrap(), None).is_err());
+
+        let (block, _) = block_with_receipts();
+        let mut borsh_data = borsh::to_vec(&wvm_borsh::block::BorshSealedBlockWithSenders(
+            WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(block)),
+        ))
+        .unwrap();
+        borsh_data.push(0);
+        assert!(decode_archive(&to_brotli(&borsh_data).unwrap(), None).is_err());
+    }
+
+    #[test]
+    pub fn test_dictionary_archive() {
+        let (block, _) = block_with_receipts();
+        let wvm_block =
+            || WvmSealedBlockWithSenders::V1(V1WvmSealedBlockWithSenders::from(block.clone()));
+        // Any content can be used as a zstd dictionary, the block itself does fine here.
+        let dictionary = ZstdDictionary {
+            id: String::from("dictionary"),
+            data: Arc::new(
+                borsh::to_vec(&wvm_borsh::block::BorshSealedBlockWithSenders(wvm_block())).unwrap(),
+            ),
+        };
+        let codec = ArchiveCodec::ZstdDict { level: 3, dictionary: dictionary.clone() };
+
+        let archive = encode_archive(wvm_block(), &codec).unwrap();
+        assert!(decode_archive(&archive, None).is_err());
+        assert_eq!(
+            ArchiveVersion::of(&decode_archive(&archive, Some(&dictionary)).unwrap()),
+            ArchiveVersion::V1
+        );
+    }
+
+    #[test]
+    pub fn test_is_arweave_tx_id() {
+        assert!(is_arweave_tx_id("bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJDI"));
+        assert!(!is_arweave_tx_id("block.data"));
+        assert!(!is_arweave_tx_id("bs318IdjLWQK7pF_bNIbJnpade8feD7yGAS8xIffJD/"));
+    }
+}
@@ -0,0 +1,247 @@
+use arweave_upload::{ArweaveRequest, UploaderProvider};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use eyre::bail;
+use rbrotli::codec::{
+    train_dictionary, ArchiveCodec, ZstdDictionary, DEFAULT_BROTLI_QUALITY, DEFAULT_BROTLI_WINDOW,
+    DEFAULT_DICTIONARY_SIZE, DEFAULT_ZSTD_LEVEL, DICTIONARY_TAG,
+};
+use std::{fs, path::PathBuf, sync::Arc};
+use wvm_archive::{
+    convert_archive, decode_archive, decompress_archive, encode_archive, fetch_archive,
+    is_arweave_tx_id, summary, ArchiveJson, ArchiveVersion, DEFAULT_ARWEAVE_GATEWAY,
+};
+use wvm_tx::wvm::{v2::receipt::V2WvmReceipt, WvmSealedBlockWithSenders};
+
+/// Inspects and converts the blocks archived to Arweave by WVM nodes
+#[derive(Debug, Parser)]
+#[command(name = "wvm-archive", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Decodes an archive into JSON
+    Decode {
+        /// Path of the archive or id of its Arweave transaction
+        source: String,
+        #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = DEFAULT_ARWEAVE_GATEWAY)]
+        gateway: String,
+        /// Path or Arweave id of the zstd dictionary the archive was compressed with
+        #[arg(long)]
+        dictionary: Option<String>,
+        /// Writes the JSON to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Encodes a JSON block, as output by `decode`, into an archive
+    Encode {
+        input: PathBuf,
+        #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = DEFAULT_ARWEAVE_GATEWAY)]
+        gateway: String,
+        #[command(flatten)]
+        codec: CodecArgs,
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Converts an archive to another format version or codec
+    Convert {
+        /// Path of the archive or id of its Arweave transaction
+        source: String,
+        #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = DEFAULT_ARWEAVE_GATEWAY)]
+        gateway: String,
+        #[arg(long, value_enum)]
+        to: ArchiveVersion,
+        /// JSON list of the block receipts, required to convert a v1 archive to v2
+        #[arg(long)]
+        receipts: Option<PathBuf>,
+        /// Path or Arweave id of the zstd dictionary the source archive was compressed with
+        #[arg(long)]
+        source_dictionary: Option<String>,
+        #[command(flatten)]
+        codec: CodecArgs,
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Trains a zstd dictionary on archived blocks
+    TrainDictionary {
+        /// Paths of archives, directories of archives or ids of Arweave transactions
+        #[arg(required = true)]
+        sources: Vec<String>,
+        #[arg(long, env = "ARWEAVE_GATEWAY_URL", default_value = DEFAULT_ARWEAVE_GATEWAY)]
+        gateway: String,
+        /// Maximum size of the dictionary in bytes
+        #[arg(long, default_value_t = DEFAULT_DICTIONARY_SIZE)]
+        size: usize,
+        #[arg(long, short)]
+        output: PathBuf,
+        /// Uploads the dictionary to Arweave, with the key of `irys_pk`
+        #[arg(long)]
+        upload: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CodecName {
+    Brotli,
+    Zstd,
+    ZstdDict,
+}
+
+#[derive(Debug, Args)]
+struct CodecArgs {
+    /// Codec of the written archive
+    #[arg(long, value_enum, default_value = "brotli")]
+    codec: CodecName,
+    #[arg(long, default_value_t = DEFAULT_BROTLI_QUALITY)]
+    brotli_quality: u8,
+    #[arg(long, default_value_t = DEFAULT_BROTLI_WINDOW)]
+    brotli_window: u8,
+    #[arg(long, default_value_t = DEFAULT_ZSTD_LEVEL)]
+    zstd_level: i32,
+    /// Path or Arweave id of the dictionary of `zstd-dict`
+    #[arg(long)]
+    dictionary: Option<String>,
+}
+
+impl CodecArgs {
+    fn codec(&self, gateway: &str) -> eyre::Result<ArchiveCodec> {
+        Ok(match self.codec {
+            CodecName::Brotli => {
+                ArchiveCodec::Brotli { quality: self.brotli_quality, window: self.brotli_window }
+            }
+            CodecName::Zstd => ArchiveCodec::Zstd { level: self.zstd_level },
+            CodecName::ZstdDict => {
+                let Some(dictionary) = &self.dictionary else {
+                    bail!("--dictionary is required by zstd-dict");
+                };
+                ArchiveCodec::ZstdDict {
+                    level: self.zstd_level,
+                    dictionary: read_dictionary(dictionary, gateway)?,
+                }
+            }
+        })
+    }
+}
+
+fn read_source(source: &str, gateway: &str) -> eyre::Result<Vec<u8>> {
+    if PathBuf::from(source).is_file() {
+        Ok(fs::read(source)?)
+    } else if is_arweave_tx_id(source) {
+        fetch_archive(gateway, source)
+    } else {
+        bail!("{} is neither a file nor an Arweave transaction id", source);
+    }
+}
+
+/// Dictionaries are identified by their Arweave id, or their path when not uploaded yet.
+fn read_dictionary(source: &str, gateway: &str) -> eyre::Result<ZstdDictionary> {
+    Ok(ZstdDictionary { id: source.to_string(), data: Arc::new(read_source(source, gateway)?) })
+}
+
+fn read_archive(
+    source: &str,
+    gateway: &str,
+    dictionary: Option<&str>,
+) -> eyre::Result<WvmSealedBlockWithSenders> {
+    let dictionary = dictionary.map(|source| read_dictionary(source, gateway)).transpose()?;
+    let block = decode_archive(&read_source(source, gateway)?, dictionary.as_ref())?;
+    eprintln!("{}", summary(&block));
+
+    Ok(block)
+}
+
+fn write_archive(
+    block: WvmSealedBlockWithSenders,
+    codec: &ArchiveCodec,
+    output: &PathBuf,
+) -> eyre::Result<()> {
+    let block_summary = summary(&block);
+    fs::write(output, encode_archive(block, codec)?)?;
+    eprintln!("{} written to {} ({})", block_summary, output.display(), codec.encoding());
+
+    Ok(())
+}
+
+/// Borsh payloads of the archives of `sources`, directories being read non recursively.
+fn read_samples(sources: &[String], gateway: &str) -> eyre::Result<Vec<Vec<u8>>> {
+    let mut samples = Vec::new();
+    for source in sources {
+        let path = PathBuf::from(source);
+        let archives = if path.is_dir() {
+            let mut archives = Vec::new();
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    archives.push(fs::read(entry.path())?);
+                }
+            }
+            archives
+        } else {
+            vec![read_source(source, gateway)?]
+        };
+
+        for archive in archives {
+            samples.push(decompress_archive(&archive, None)?);
+        }
+    }
+
+    Ok(samples)
+}
+
+fn upload_dictionary(dictionary: Vec<u8>) -> eyre::Result<String> {
+    let mut request = ArweaveRequest::new();
+    request
+        .set_tag("Content-Type", "application/octet-stream")
+        .set_tag("Type", DICTIONARY_TAG)
+        .set_data(dictionary);
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(request.send_with_provider(&UploaderProvider::new(None)))
+}
+
+fn main() -> eyre::Result<()> {
+    match Cli::parse().command {
+        Commands::Decode { source, gateway, dictionary, output } => {
+            let block = read_archive(&source, &gateway, dictionary.as_deref())?;
+            let json = serde_json::to_string_pretty(&ArchiveJson::from(block))?;
+
+            match output {
+                Some(output) => fs::write(output, json)?,
+                None => println!("{}", json),
+            }
+        }
+        Commands::Encode { input, gateway, codec, output } => {
+            let json: ArchiveJson = serde_json::from_slice(&fs::read(input)?)?;
+            write_archive(json.into(), &codec.codec(&gateway)?, &output)?;
+        }
+        Commands::Convert { source, gateway, to, receipts, source_dictionary, codec, output } => {
+            let block = read_archive(&source, &gateway, source_dictionary.as_deref())?;
+            let receipts = receipts
+                .map(|path| -> eyre::Result<Vec<V2WvmReceipt>> {
+                    Ok(serde_json::from_slice(&fs::read(path)?)?)
+                })
+                .transpose()?;
+
+            write_archive(convert_archive(block, to, receipts)?, &codec.codec(&gateway)?, &output)?;
+        }
+        Commands::TrainDictionary { sources, gateway, size, output, upload } => {
+            let samples = read_samples(&sources, &gateway)?;
+            let dictionary = train_dictionary(&samples, size)?;
+            fs::write(&output, &dictionary)?;
+            eprintln!(
+                "Dictionary of {} bytes trained on {} blocks written to {}",
+                dictionary.len(),
+                samples.len(),
+                output.display()
+            );
+
+            if upload {
+                println!("{}", upload_dictionary(dictionary)?);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::{address::BorshAddress, b256::BorshB256};
+use alloy_eips::eip2930::{AccessList, AccessListItem};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::io::{Read, Write};
+
+pub struct BorshAccessList(pub AccessList);
+
+impl BorshSerialize for BorshAccessList {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let AccessList(items) = &self.0;
+
+        (items.len() as u32).serialize(writer)?;
+        for item in items {
+            BorshAddress(item.address).serialize(writer)?;
+            let storage_keys: Vec<BorshB256> =
+                item.storage_keys.iter().copied().map(BorshB256).collect();
+            storage_keys.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshAccessList {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let len = u32::deserialize_reader(reader)?;
+        let mut items = Vec::new();
+        for _ in 0..len {
+            let address = BorshAddress::deserialize_reader(reader)?;
+            let storage_keys = Vec::<BorshB256>::deserialize_reader(reader)?;
+            items.push(AccessListItem {
+                address: address.0,
+                storage_keys: storage_keys.into_iter().map(|i| i.0).collect(),
+            });
+        }
+
+        Ok(BorshAccessList(AccessList(items)))
+    }
+}
+
+#[cfg(test)]
+mod access_list_tests {
+    use crate::access_list::BorshAccessList;
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{Address, B256};
+
+    #[test]
+    pub fn test_access_list() {
+        let data = AccessList(vec![
+            AccessListItem {
+                address: Address::random(),
+                storage_keys: vec![B256::random(), B256::random()],
+            },
+            AccessListItem { address: Address::random(), storage_keys: vec![] },
+        ]);
+        let to_borsh = borsh::to_vec(&BorshAccessList(data.clone())).unwrap();
+        let from_borsh: BorshAccessList = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        assert_eq!(data, from_borsh.0);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::b256::BorshB256;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxKind};
 use borsh::{BorshDeserialize, BorshSerialize};
-use std::io::{Read, Write};
+use std::io::{Error, ErrorKind, Read, Write};
 
 pub struct BorshAddress(pub Address);
+pub struct BorshTxKind(pub TxKind);
 
 impl BorshSerialize for BorshAddress {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
@@ -18,10 +19,32 @@ impl BorshDeserialize for BorshAddress {
     }
 }
 
+impl BorshSerialize for BorshTxKind {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            TxKind::Create => 0u8.serialize(writer),
+            TxKind::Call(address) => {
+                1u8.serialize(writer)?;
+                BorshAddress(address).serialize(writer)
+            }
+        }
+    }
+}
+
+impl BorshDeserialize for BorshTxKind {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            0u8 => Ok(BorshTxKind(TxKind::Create)),
+            1u8 => Ok(BorshTxKind(TxKind::Call(BorshAddress::deserialize_reader(reader)?.0))),
+            _ => Err(Error::new(ErrorKind::InvalidData, "Invalid transaction kind")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod address_tests {
-    use crate::address::BorshAddress;
-    use alloy_primitives::Address;
+    use crate::address::{BorshAddress, BorshTxKind};
+    use alloy_primitives::{Address, TxKind};
 
     #[test]
     pub fn test_sealed_header() {
@@ -31,4 +54,13 @@ mod address_tests {
         let from_borsh: BorshAddress = borsh::from_slice(to_borsh.as_slice()).unwrap();
         assert_eq!(data, from_borsh.0);
     }
+
+    #[test]
+    pub fn test_tx_kind() {
+        for data in [TxKind::Create, TxKind::Call(Address::random())] {
+            let to_borsh = borsh::to_vec(&BorshTxKind(data)).unwrap();
+            let from_borsh: BorshTxKind = borsh::from_slice(to_borsh.as_slice()).unwrap();
+            assert_eq!(data, from_borsh.0);
+        }
+    }
 }
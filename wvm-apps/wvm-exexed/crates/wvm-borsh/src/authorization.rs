@@ -0,0 +1,56 @@
+use crate::{address::BorshAddress, b256::BorshU256};
+use alloy_eips::eip7702::{Authorization, SignedAuthorization};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::io::{Read, Write};
+
+/// EIP-7702 authorization, kept with its signature as is. Authorizations are only validated
+/// during execution, an archive has to preserve invalid ones too.
+pub struct BorshSignedAuthorization(pub SignedAuthorization);
+
+impl BorshSerialize for BorshSignedAuthorization {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.0.chain_id.serialize(writer)?;
+        BorshAddress(self.0.address).serialize(writer)?;
+        self.0.nonce().serialize(writer)?;
+        self.0.y_parity().serialize(writer)?;
+        BorshU256(self.0.r()).serialize(writer)?;
+        BorshU256(self.0.s()).serialize(writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshSignedAuthorization {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let chain_id: u64 = BorshDeserialize::deserialize_reader(reader)?;
+        let address = BorshAddress::deserialize_reader(reader)?;
+        let nonce: u64 = BorshDeserialize::deserialize_reader(reader)?;
+        let y_parity: u8 = BorshDeserialize::deserialize_reader(reader)?;
+        let r = BorshU256::deserialize_reader(reader)?;
+        let s = BorshU256::deserialize_reader(reader)?;
+
+        let authorization = Authorization { chain_id, address: address.0, nonce };
+
+        Ok(BorshSignedAuthorization(SignedAuthorization::new_unchecked(
+            authorization,
+            y_parity,
+            r.0,
+            s.0,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod authorization_tests {
+    use crate::authorization::BorshSignedAuthorization;
+    use alloy_eips::eip7702::{Authorization, SignedAuthorization};
+    use alloy_primitives::{Address, U256};
+
+    #[test]
+    pub fn test_signed_authorization() {
+        let authorization = Authorization { chain_id: 9496, address: Address::random(), nonce: 7 };
+        let data = SignedAuthorization::new_unchecked(authorization, 1, U256::from(42), U256::MAX);
+        let to_borsh = borsh::to_vec(&BorshSignedAuthorization(data.clone())).unwrap();
+        let from_borsh: BorshSignedAuthorization = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        assert_eq!(data, from_borsh.0);
+    }
+}
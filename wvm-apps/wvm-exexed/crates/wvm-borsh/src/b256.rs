@@ -1,6 +1,6 @@
 use alloy_primitives::{B256, U256};
 use borsh::{BorshDeserialize, BorshSerialize};
-use std::io::{Read, Write};
+use std::io::{Error, ErrorKind, Read, Write};
 
 pub struct BorshB256(pub B256);
 pub struct BorshU256(pub U256);
@@ -15,7 +15,8 @@ impl BorshSerialize for BorshB256 {
 impl BorshDeserialize for BorshB256 {
     fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
         let reader = Vec::<u8>::deserialize_reader(reader)?;
-        let val: B256 = B256::from_slice(reader.as_slice());
+        let val = B256::try_from(reader.as_slice())
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid B256 length"))?;
         Ok(BorshB256(val))
     }
 }
@@ -29,6 +30,9 @@ impl BorshSerialize for BorshU256 {
 impl BorshDeserialize for BorshU256 {
     fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
         let bu256_le_bytes = Vec::<u8>::deserialize_reader(reader)?;
+        if bu256_le_bytes.len() > 32 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid U256 length"));
+        }
         let u256 = U256::from_le_slice(bu256_le_bytes.as_slice());
         Ok(BorshU256(u256))
     }
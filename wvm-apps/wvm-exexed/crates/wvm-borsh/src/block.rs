@@ -1,6 +1,8 @@
 use crate::{
     address::BorshAddress,
     header::{BorshHeader, BorshSealedHeader},
+    receipt::BorshReceipt,
+    state_diff::BorshStateDiff,
     transaction::BorshTransactionSigned,
     withdrawal::BorshWithdrawal,
 };
@@ -11,6 +13,7 @@ use alloy_eips::eip4895::Withdrawals;
 use std::io::{Read, Write};
 use wvm_tx::wvm::{
     v1::{V1WvmBlockBody, V1WvmSealedBlock, V1WvmSealedBlockWithSenders},
+    v2::V2WvmSealedBlockWithSenders,
     MagicIdentifier, WvmHeader, WvmSealedBlock, WvmSealedBlockWithSenders, WvmSealedHeader,
     WvmTransactionSigned,
 };
@@ -31,7 +34,7 @@ impl BorshSerialize for BorshSealedBlock {
                     .clone()
                     .transactions
                     .into_iter()
-                    .map(|e| BorshTransactionSigned(WvmTransactionSigned::V1(e)))
+                    .map(|e| BorshTransactionSigned(WvmTransactionSigned::V2(e.into())))
                     .collect();
                 let borsh_ommers: Vec<BorshHeader> = sealed_block
                     .body
@@ -70,16 +73,22 @@ impl BorshDeserialize for BorshSealedBlock {
                 let borsh_ommers = Vec::<BorshHeader>::deserialize_reader(reader)?;
                 let withdrawal = Option::<Vec<BorshWithdrawal>>::deserialize_reader(reader)?;
 
+                let WvmSealedHeader::V1(header) = sealed_header.0;
+
                 Ok(BorshSealedBlock(WvmSealedBlock::V1(V1WvmSealedBlock {
-                    header: sealed_header.0.as_v1().unwrap().clone(),
+                    header,
                     body: V1WvmBlockBody {
+                        // Transactions may be V1 or V2 encoded, depending on when they were
+                        // archived.
                         transactions: borsh_transactions
                             .into_iter()
-                            .map(|i| i.0.as_v1().unwrap().clone())
+                            .map(|i| i.0.into_v1())
                             .collect(),
                         ommers: borsh_ommers
                             .into_iter()
-                            .map(|i| i.0.as_v1().unwrap().clone())
+                            .map(|i| match i.0 {
+                                WvmHeader::V1(header) => header,
+                            })
                             .collect(),
                         withdrawals: withdrawal
                             .map(|i| {
@@ -114,6 +123,28 @@ impl BorshSerialize for BorshSealedBlockWithSenders {
                 borsh_sealed_block.serialize(writer)?;
                 senders.serialize(writer)?;
             }
+            WvmSealedBlockWithSenders::V2(sealed_block_with_senders) => {
+                let borsh_sealed_block =
+                    BorshSealedBlock(WvmSealedBlock::V1(sealed_block_with_senders.block.clone()));
+                let senders: Vec<BorshAddress> = sealed_block_with_senders
+                    .senders
+                    .clone()
+                    .into_iter()
+                    .map(BorshAddress)
+                    .collect();
+                let receipts: Vec<Option<BorshReceipt>> = sealed_block_with_senders
+                    .receipts
+                    .clone()
+                    .into_iter()
+                    .map(|receipt| receipt.map(BorshReceipt))
+                    .collect();
+                let state_diff = sealed_block_with_senders.state_diff.clone().map(BorshStateDiff);
+
+                borsh_sealed_block.serialize(writer)?;
+                senders.serialize(writer)?;
+                receipts.serialize(writer)?;
+                state_diff.serialize(writer)?;
+            }
         }
 
         Ok(())
@@ -127,7 +158,7 @@ impl BorshDeserialize for BorshSealedBlockWithSenders {
         match magic_identifier {
             0u8 => {
                 let sealed_block: BorshSealedBlock = BorshDeserialize::deserialize_reader(reader)?;
-                let block: V1WvmSealedBlock = sealed_block.0.as_v1().unwrap().clone();
+                let WvmSealedBlock::V1(block) = sealed_block.0;
                 let senders: Vec<BorshAddress> = BorshDeserialize::deserialize_reader(reader)?;
 
                 Ok(BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V1(
@@ -137,6 +168,24 @@ impl BorshDeserialize for BorshSealedBlockWithSenders {
                     },
                 )))
             }
+            1u8 => {
+                let sealed_block: BorshSealedBlock = BorshDeserialize::deserialize_reader(reader)?;
+                let WvmSealedBlock::V1(block) = sealed_block.0;
+                let senders: Vec<BorshAddress> = BorshDeserialize::deserialize_reader(reader)?;
+                let receipts: Vec<Option<BorshReceipt>> =
+                    BorshDeserialize::deserialize_reader(reader)?;
+                let state_diff: Option<BorshStateDiff> =
+                    BorshDeserialize::deserialize_reader(reader)?;
+
+                Ok(BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V2(
+                    V2WvmSealedBlockWithSenders {
+                        block,
+                        senders: senders.into_iter().map(|i| i.0).collect(),
+                        receipts: receipts.into_iter().map(|i| i.map(|i| i.0)).collect(),
+                        state_diff: state_diff.map(|i| i.0),
+                    },
+                )))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::Other, "Invalid Magic Identifier")),
         }
     }
@@ -145,9 +194,24 @@ impl BorshDeserialize for BorshSealedBlockWithSenders {
 #[cfg(test)]
 mod block_tests {
     use crate::block::{BorshSealedBlock, BorshSealedBlockWithSenders};
-    use reth::primitives::{SealedBlock, SealedBlockWithSenders};
+    use alloy_eips::{
+        eip2718::Decodable2718,
+        eip4895::{Withdrawal, Withdrawals},
+    };
+    use alloy_primitives::{hex, Address, Bytes, Log, B256, B64, U256};
+    use reth::primitives::{
+        proofs::{
+            calculate_receipt_root_no_memo, calculate_transaction_root, calculate_withdrawals_root,
+        },
+        BlockBody, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+        TransactionSigned, TxType,
+    };
     use wvm_tx::wvm::{
         v1::{V1WvmSealedBlock, V1WvmSealedBlockWithSenders},
+        v2::{
+            state_diff::{WvmAccountDiff, WvmAccountInfo, WvmStateDiff},
+            V2WvmSealedBlockWithSenders,
+        },
         WvmSealedBlockWithSenders,
     };
 
@@ -161,6 +225,20 @@ mod block_tests {
         assert_eq!(block, from_borsh.0.as_v1().unwrap().clone().into());
     }
 
+    #[test]
+    pub fn test_sealed_block_with_transactions() {
+        let raw_tx = hex!("02f872018307910d808507204d2cb1827d0094388c818ca8b9251b393131c08a736a67ccb19297880320d04823e2701c80c001a0cf024f4815304df2867a1a74e9d2707b6abda0337d2d54a4438d453f4160f190a07ac0e6b3bc9395b5b9c8b9e6d77204a236577a5b18467b9175c01de4faa208d9");
+        let tx = TransactionSigned::decode_2718(&mut raw_tx.as_slice()).unwrap();
+        let mut block = SealedBlock::default();
+        block.body.transactions = vec![TransactionSigned::default(), tx];
+
+        let wvm_block = V1WvmSealedBlock::from(block.clone());
+        let borsh_block = BorshSealedBlock(wvm_tx::wvm::WvmSealedBlock::V1(wvm_block));
+        let to_borsh = borsh::to_vec(&borsh_block).unwrap();
+        let from_borsh: BorshSealedBlock = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        assert_eq!(block, from_borsh.0.as_v1().unwrap().clone().into());
+    }
+
     #[test]
     pub fn test_sealed_block_w_senders() {
         let block = SealedBlockWithSenders::default();
@@ -171,4 +249,136 @@ mod block_tests {
             borsh::from_slice(to_borsh.as_slice()).unwrap();
         assert_eq!(block, from_borsh.0.as_v1().unwrap().clone().into());
     }
+
+    #[test]
+    pub fn test_sealed_block_w_receipts() {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.transactions = vec![TransactionSigned::default()];
+        let receipt = Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(Address::random(), vec![B256::random()], Bytes::new())],
+            ..Default::default()
+        };
+        let state_diff = WvmStateDiff {
+            accounts: vec![WvmAccountDiff {
+                address: Address::random(),
+                info: Some(WvmAccountInfo {
+                    balance: U256::from(1),
+                    nonce: 1,
+                    code_hash: B256::random(),
+                }),
+                wiped_storage: false,
+                storage: vec![(U256::from(1), U256::from(2))],
+            }],
+            contracts: vec![],
+        };
+
+        let mut wvm_block =
+            V2WvmSealedBlockWithSenders::new(block, vec![Some(receipt)], Some(state_diff.clone()));
+        wvm_block.block.header.header.receipts_root = wvm_block.receipts_root().unwrap();
+        let borsh_block =
+            BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V2(wvm_block.clone()));
+        let to_borsh = borsh::to_vec(&borsh_block).unwrap();
+        assert_eq!(to_borsh[0], 1u8);
+
+        let from_borsh: BorshSealedBlockWithSenders =
+            borsh::from_slice(to_borsh.as_slice()).unwrap();
+        let decoded = from_borsh.0.as_v2().unwrap().clone();
+        assert!(decoded.verify_receipts_root());
+        assert_eq!(decoded.receipts, wvm_block.receipts);
+        assert_eq!(decoded.state_diff, Some(state_diff));
+        let block: SealedBlockWithSenders = wvm_block.into();
+        assert_eq!(block, decoded.into());
+    }
+
+    #[test]
+    pub fn test_sealed_block_w_pruned_receipts() {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.transactions = vec![TransactionSigned::default(); 2];
+        let receipt = Receipt { tx_type: TxType::Legacy, success: true, ..Default::default() };
+
+        let wvm_block = V2WvmSealedBlockWithSenders::new(block, vec![Some(receipt), None], None);
+        let borsh_block =
+            BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V2(wvm_block.clone()));
+        let to_borsh = borsh::to_vec(&borsh_block).unwrap();
+        let from_borsh: BorshSealedBlockWithSenders =
+            borsh::from_slice(to_borsh.as_slice()).unwrap();
+        let decoded = from_borsh.0.as_v2().unwrap().clone();
+
+        assert_eq!(decoded.receipts, wvm_block.receipts);
+        assert!(decoded.has_pruned_receipts());
+        assert_eq!(decoded.receipts_root(), None);
+        assert!(!decoded.verify_receipts_root());
+    }
+
+    #[test]
+    pub fn test_sealed_block_fixture_roundtrip() {
+        // Mainnet EIP-1559 transfer, archived with every optional header field set.
+        let raw_tx = hex!("02f872018307910d808507204d2cb1827d0094388c818ca8b9251b393131c08a736a67ccb19297880320d04823e2701c80c001a0cf024f4815304df2867a1a74e9d2707b6abda0337d2d54a4438d453f4160f190a07ac0e6b3bc9395b5b9c8b9e6d77204a236577a5b18467b9175c01de4faa208d9");
+        let tx = TransactionSigned::decode_2718(&mut raw_tx.as_slice()).unwrap();
+        let sender = tx.recover_signer().unwrap();
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                sender,
+                vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+                Bytes::from_static(b"wvm"),
+            )],
+            ..Default::default()
+        };
+        let withdrawals =
+            vec![Withdrawal { index: 7, validator_index: 11, address: sender, amount: 1_000_000 }];
+
+        let header = Header {
+            parent_hash: B256::with_last_byte(1),
+            beneficiary: sender,
+            state_root: B256::with_last_byte(2),
+            transactions_root: calculate_transaction_root(&[tx.clone()]),
+            receipts_root: calculate_receipt_root_no_memo(&[&receipt]),
+            withdrawals_root: Some(calculate_withdrawals_root(&withdrawals)),
+            logs_bloom: receipt.bloom_slow(),
+            difficulty: U256::ZERO,
+            number: 1_234_567,
+            gas_limit: 300_000_000,
+            gas_used: 21_000,
+            timestamp: 1_730_000_000,
+            mix_hash: B256::with_last_byte(3),
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(500_000_000),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::with_last_byte(4)),
+            extra_data: Bytes::from_static(b"wvm"),
+            ..Default::default()
+        };
+        let hash = header.hash_slow();
+        let block = SealedBlockWithSenders {
+            block: SealedBlock::new(
+                SealedHeader::new(header, hash),
+                BlockBody {
+                    transactions: vec![tx],
+                    ommers: vec![],
+                    withdrawals: Some(Withdrawals::new(withdrawals)),
+                },
+            ),
+            senders: vec![sender],
+        };
+
+        let wvm_block = V2WvmSealedBlockWithSenders::new(block.clone(), vec![Some(receipt)], None);
+        let borsh_block = BorshSealedBlockWithSenders(WvmSealedBlockWithSenders::V2(wvm_block));
+        let to_borsh = borsh::to_vec(&borsh_block).unwrap();
+        let from_borsh: BorshSealedBlockWithSenders =
+            borsh::from_slice(to_borsh.as_slice()).unwrap();
+        let decoded = from_borsh.0.as_v2().unwrap().clone();
+        assert!(decoded.verify_receipts_root());
+
+        let decoded: SealedBlockWithSenders = decoded.into();
+        assert_eq!(decoded, block);
+        assert_eq!(decoded.header.hash_slow(), hash);
+        assert_eq!(decoded.senders, vec![decoded.body.transactions[0].recover_signer().unwrap()]);
+    }
 }
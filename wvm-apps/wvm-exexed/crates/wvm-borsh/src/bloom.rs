@@ -1,6 +1,6 @@
-use alloy_primitives::Bloom;
+use alloy_primitives::{Bloom, BLOOM_SIZE_BYTES};
 use borsh::{BorshDeserialize, BorshSerialize};
-use std::io::{Read, Write};
+use std::io::{Error, ErrorKind, Read, Write};
 
 pub struct BorshBloom(pub Bloom);
 
@@ -14,6 +14,9 @@ impl BorshSerialize for BorshBloom {
 impl BorshDeserialize for BorshBloom {
     fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
         let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        if bytes.len() != BLOOM_SIZE_BYTES {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid Bloom length"));
+        }
         let bloom = Bloom::from_slice(bytes.as_slice());
         Ok(BorshBloom(bloom))
     }
@@ -131,7 +131,7 @@ impl BorshDeserialize for BorshSealedHeader {
             0u8 => {
                 let hash = BorshB256::deserialize_reader(reader)?;
                 let header = BorshHeader::deserialize_reader(reader)?;
-                let v1_header: V1WvmHeader = header.0.as_v1().unwrap().clone();
+                let WvmHeader::V1(v1_header) = header.0;
 
                 Ok(BorshSealedHeader(WvmSealedHeader::V1(V1WvmSealedHeader {
                     hash: hash.0,
@@ -1,10 +1,13 @@
+pub mod access_list;
 pub mod address;
+pub mod authorization;
 pub mod b256;
 pub mod block;
 pub mod bloom;
 pub mod header;
-
+pub mod receipt;
 pub mod signature;
+pub mod state_diff;
 pub mod transaction;
 pub mod withdrawal;
 
@@ -37,7 +40,7 @@ mod tests {
         let borsh_serialize = borsh::to_vec(&borsh_block).unwrap();
 
         assert_eq!(serde_json_serialize.len(), 1828);
-        assert_eq!(borsh_serialize.len(), 907);
+        assert_eq!(borsh_serialize.len(), 903);
     }
 
     #[test]
@@ -0,0 +1,124 @@
+use crate::{address::BorshAddress, b256::BorshB256};
+use alloy_primitives::{Log, LogData};
+use borsh::{BorshDeserialize, BorshSerialize};
+use reth_primitives::TxType;
+use std::io::{Error, ErrorKind, Read, Write};
+use wvm_tx::wvm::v2::receipt::V2WvmReceipt;
+
+/// Version of the receipt encoding, written before each receipt.
+pub const RECEIPT_ENCODING_VERSION: u8 = 0;
+
+pub struct BorshLog(pub Log);
+pub struct BorshReceipt(pub V2WvmReceipt);
+
+impl BorshSerialize for BorshLog {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshAddress(self.0.address).serialize(writer)?;
+        let topics: Vec<BorshB256> = self.0.data.topics().iter().copied().map(BorshB256).collect();
+        topics.serialize(writer)?;
+        self.0.data.data.0.serialize(writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshLog {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let address = BorshAddress::deserialize_reader(reader)?;
+        let topics = Vec::<BorshB256>::deserialize_reader(reader)?;
+        let data = Vec::<u8>::deserialize_reader(reader)?;
+
+        let data = LogData::new(topics.into_iter().map(|i| i.0).collect(), data.into())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Too many log topics"))?;
+
+        Ok(BorshLog(Log { address: address.0, data }))
+    }
+}
+
+impl BorshSerialize for BorshReceipt {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        RECEIPT_ENCODING_VERSION.serialize(writer)?;
+        u8::from(self.0.tx_type).serialize(writer)?;
+        self.0.success.serialize(writer)?;
+        self.0.cumulative_gas_used.serialize(writer)?;
+        let logs: Vec<BorshLog> = self.0.logs.iter().cloned().map(BorshLog).collect();
+        logs.serialize(writer)?;
+        self.0.deposit_nonce.serialize(writer)?;
+        self.0.deposit_receipt_version.serialize(writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshReceipt {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = u8::deserialize_reader(reader)?;
+        if version != RECEIPT_ENCODING_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, "Unknown receipt encoding version"));
+        }
+
+        let tx_type = TxType::try_from(u8::deserialize_reader(reader)?)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid receipt type"))?;
+        let success = bool::deserialize_reader(reader)?;
+        let cumulative_gas_used = u64::deserialize_reader(reader)?;
+        let logs = Vec::<BorshLog>::deserialize_reader(reader)?;
+
+        Ok(BorshReceipt(V2WvmReceipt {
+            tx_type,
+            success,
+            cumulative_gas_used,
+            logs: logs.into_iter().map(|i| i.0).collect(),
+            deposit_nonce: BorshDeserialize::deserialize_reader(reader)?,
+            deposit_receipt_version: BorshDeserialize::deserialize_reader(reader)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod receipt_tests {
+    use crate::receipt::{BorshReceipt, RECEIPT_ENCODING_VERSION};
+    use alloy_primitives::{Address, Bytes, Log, B256};
+    use reth_primitives::{Receipt, TxType};
+    use wvm_tx::wvm::v2::receipt::V2WvmReceipt;
+
+    #[test]
+    pub fn test_receipt() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 46_913,
+            logs: vec![
+                Log::new_unchecked(
+                    Address::random(),
+                    vec![B256::random(), B256::random()],
+                    Bytes::from(vec![1, 2, 3]),
+                ),
+                Log::new_unchecked(Address::random(), vec![], Bytes::new()),
+            ],
+            ..Default::default()
+        };
+        let to_borsh = borsh::to_vec(&BorshReceipt(V2WvmReceipt::from(receipt.clone()))).unwrap();
+        let from_borsh: BorshReceipt = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        let decoded: Receipt = from_borsh.0.into();
+        assert_eq!(receipt, decoded);
+    }
+
+    #[test]
+    pub fn test_receipt_deposit_fields() {
+        // Deposit fields are encoded whatever the features of the build.
+        let receipt = V2WvmReceipt {
+            tx_type: TxType::Legacy,
+            success: false,
+            cumulative_gas_used: 21_000,
+            logs: vec![],
+            deposit_nonce: Some(7),
+            deposit_receipt_version: Some(1),
+        };
+        let to_borsh = borsh::to_vec(&BorshReceipt(receipt.clone())).unwrap();
+        assert_eq!(to_borsh[0], RECEIPT_ENCODING_VERSION);
+        let from_borsh: BorshReceipt = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        assert_eq!(from_borsh.0, receipt);
+
+        let mut unknown_version = to_borsh;
+        unknown_version[0] = RECEIPT_ENCODING_VERSION + 1;
+        assert!(borsh::from_slice::<BorshReceipt>(&unknown_version).is_err());
+    }
+}
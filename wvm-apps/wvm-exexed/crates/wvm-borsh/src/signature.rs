@@ -1,17 +1,19 @@
-use alloy_primitives::{Parity, Signature, U256};
+use crate::b256::BorshU256;
+use alloy_primitives::{Parity, Signature};
 use borsh::{BorshDeserialize, BorshSerialize};
 use std::io::{Error, ErrorKind, Read, Write};
 
 pub struct BorshSignature(pub Signature);
 
+/// Signature as `r`, `s` and the parity as is, so that EIP-155 legacy signatures round-trip.
+pub struct BorshSignatureWithParity(pub Signature);
+
 pub fn to_signature(bytes: &[u8]) -> std::io::Result<Signature> {
     if bytes.len() != 65 {
         return Err(Error::from(ErrorKind::UnexpectedEof));
     }
 
-    let signature = Signature::try_from(bytes).unwrap();
-
-    Ok(signature)
+    Signature::try_from(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
 }
 
 impl BorshSerialize for BorshSignature {
@@ -23,14 +25,47 @@ impl BorshSerialize for BorshSignature {
 impl BorshDeserialize for BorshSignature {
     fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
         let sig_vec = Vec::<u8>::deserialize_reader(reader)?;
-        let sig = to_signature(sig_vec.as_slice()).unwrap();
+        let sig = to_signature(sig_vec.as_slice())?;
         Ok(BorshSignature(sig))
     }
 }
 
+impl BorshSerialize for BorshSignatureWithParity {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshU256(self.0.r()).serialize(writer)?;
+        BorshU256(self.0.s()).serialize(writer)?;
+
+        let (kind, value) = match self.0.v() {
+            Parity::Eip155(v) => (0u8, v),
+            Parity::NonEip155(odd) => (1u8, odd as u64),
+            Parity::Parity(odd) => (2u8, odd as u64),
+        };
+        kind.serialize(writer)?;
+        value.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for BorshSignatureWithParity {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let r = BorshU256::deserialize_reader(reader)?;
+        let s = BorshU256::deserialize_reader(reader)?;
+        let kind = u8::deserialize_reader(reader)?;
+        let value = u64::deserialize_reader(reader)?;
+
+        let parity = match (kind, value) {
+            (0, v) => Parity::Eip155(v),
+            (1, odd @ (0 | 1)) => Parity::NonEip155(odd == 1),
+            (2, odd @ (0 | 1)) => Parity::Parity(odd == 1),
+            _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid signature parity")),
+        };
+
+        Ok(BorshSignatureWithParity(Signature::new(r.0, s.0, parity)))
+    }
+}
+
 #[cfg(test)]
 mod signature_tests {
-    use crate::signature::BorshSignature;
+    use crate::signature::{BorshSignature, BorshSignatureWithParity};
     use alloy_primitives::{Parity, Signature};
 
     #[test]
@@ -47,4 +82,18 @@ mod signature_tests {
         let parity = Parity::try_from(from_bytes[64] as u64).unwrap();
         assert_eq!(Parity::NonEip155(false), parity)
     }
+
+    #[test]
+    pub fn test_signature_with_parity() {
+        for parity in [Parity::Eip155(37), Parity::NonEip155(true), Parity::Parity(false)] {
+            let data = Signature::test_signature().with_parity(parity);
+            let to_borsh = borsh::to_vec(&BorshSignatureWithParity(data)).unwrap();
+            let from_borsh: BorshSignatureWithParity =
+                borsh::from_slice(to_borsh.as_slice()).unwrap();
+            assert_eq!(data, from_borsh.0);
+        }
+
+        let truncated = borsh::to_vec(&vec![0u8; 64]).unwrap();
+        assert!(borsh::from_slice::<BorshSignature>(&truncated).is_err());
+    }
 }
@@ -0,0 +1,117 @@
+use crate::{
+    address::BorshAddress,
+    b256::{BorshB256, BorshU256},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::io::{Error, ErrorKind, Read, Write};
+use wvm_tx::wvm::v2::state_diff::{WvmAccountDiff, WvmAccountInfo, WvmStateDiff};
+
+/// Version of the state diff encoding, written before the state diff.
+pub const STATE_DIFF_ENCODING_VERSION: u8 = 0;
+
+pub struct BorshStateDiff(pub WvmStateDiff);
+
+impl BorshSerialize for BorshStateDiff {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        STATE_DIFF_ENCODING_VERSION.serialize(writer)?;
+        (self.0.accounts.len() as u32).serialize(writer)?;
+        for account in &self.0.accounts {
+            BorshAddress(account.address).serialize(writer)?;
+            let info = account
+                .info
+                .as_ref()
+                .map(|info| (BorshU256(info.balance), info.nonce, BorshB256(info.code_hash)));
+            info.serialize(writer)?;
+            account.wiped_storage.serialize(writer)?;
+            let storage: Vec<(BorshU256, BorshU256)> = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (BorshU256(*slot), BorshU256(*value)))
+                .collect();
+            storage.serialize(writer)?;
+        }
+
+        let contracts: Vec<(BorshB256, Vec<u8>)> = self
+            .0
+            .contracts
+            .iter()
+            .map(|(code_hash, code)| (BorshB256(*code_hash), code.to_vec()))
+            .collect();
+        contracts.serialize(writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshStateDiff {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = u8::deserialize_reader(reader)?;
+        if version != STATE_DIFF_ENCODING_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, "Unknown state diff encoding version"));
+        }
+
+        let len = u32::deserialize_reader(reader)?;
+        let mut accounts = Vec::new();
+        for _ in 0..len {
+            let address = BorshAddress::deserialize_reader(reader)?;
+            let info = Option::<(BorshU256, u64, BorshB256)>::deserialize_reader(reader)?;
+            let wiped_storage = bool::deserialize_reader(reader)?;
+            let storage = Vec::<(BorshU256, BorshU256)>::deserialize_reader(reader)?;
+
+            accounts.push(WvmAccountDiff {
+                address: address.0,
+                info: info.map(|(balance, nonce, code_hash)| WvmAccountInfo {
+                    balance: balance.0,
+                    nonce,
+                    code_hash: code_hash.0,
+                }),
+                wiped_storage,
+                storage: storage.into_iter().map(|(slot, value)| (slot.0, value.0)).collect(),
+            });
+        }
+
+        let contracts = Vec::<(BorshB256, Vec<u8>)>::deserialize_reader(reader)?;
+
+        Ok(BorshStateDiff(WvmStateDiff {
+            accounts,
+            contracts: contracts
+                .into_iter()
+                .map(|(code_hash, code)| (code_hash.0, code.into()))
+                .collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod state_diff_tests {
+    use crate::state_diff::BorshStateDiff;
+    use alloy_primitives::{Address, Bytes, B256, U256};
+    use wvm_tx::wvm::v2::state_diff::{WvmAccountDiff, WvmAccountInfo, WvmStateDiff};
+
+    #[test]
+    pub fn test_state_diff() {
+        let data = WvmStateDiff {
+            accounts: vec![
+                WvmAccountDiff {
+                    address: Address::random(),
+                    info: Some(WvmAccountInfo {
+                        balance: U256::from(1_000_000),
+                        nonce: 7,
+                        code_hash: B256::random(),
+                    }),
+                    wiped_storage: false,
+                    storage: vec![(U256::from(1), U256::from(2)), (U256::from(3), U256::ZERO)],
+                },
+                WvmAccountDiff {
+                    address: Address::random(),
+                    info: None,
+                    wiped_storage: true,
+                    storage: vec![],
+                },
+            ],
+            contracts: vec![(B256::random(), Bytes::from(vec![0x60, 0x80, 0x60, 0x40]))],
+        };
+        let to_borsh = borsh::to_vec(&BorshStateDiff(data.clone())).unwrap();
+        let from_borsh: BorshStateDiff = borsh::from_slice(to_borsh.as_slice()).unwrap();
+        assert_eq!(data, from_borsh.0);
+    }
+}
@@ -1,28 +1,218 @@
-use crate::{b256::BorshB256, signature::BorshSignature};
+use crate::{
+    access_list::BorshAccessList,
+    address::{BorshAddress, BorshTxKind},
+    authorization::BorshSignedAuthorization,
+    b256::{BorshB256, BorshU256},
+    signature::{BorshSignature, BorshSignatureWithParity},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
-use reth::primitives::{Transaction, TransactionSigned};
-
-use std::io::{Read, Write};
+use std::io::{Error, ErrorKind, Read, Write};
 use wvm_tx::{
-    wvm::{v1::transaction::V1WvmTransactionSigned, MagicIdentifier, WvmTransactionSigned},
-    WvmTransaction,
+    wvm::{
+        v1::transaction::V1WvmTransactionSigned, v2::transaction::V2WvmTransactionSigned,
+        MagicIdentifier, WvmTransactionSigned,
+    },
+    TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxLegacy, WvmTransaction,
 };
 
 pub struct BorshTransactionSigned(pub WvmTransactionSigned);
+
+/// Field level encoding of a transaction, used from [`WvmTransactionSigned::V2`] on.
 pub struct BorshTransaction(pub WvmTransaction);
 
-impl BorshSerialize for BorshTransaction {
+/// JSON encoding of a transaction, as found in [`WvmTransactionSigned::V1`] archives.
+pub struct BorshJsonTransaction(pub WvmTransaction);
+
+// Transaction types, same as their EIP-2718 type.
+const LEGACY_TX_TYPE: u8 = 0;
+const EIP2930_TX_TYPE: u8 = 1;
+const EIP1559_TX_TYPE: u8 = 2;
+const EIP4844_TX_TYPE: u8 = 3;
+const EIP7702_TX_TYPE: u8 = 4;
+#[cfg(feature = "optimism")]
+const DEPOSIT_TX_TYPE: u8 = 126;
+
+impl BorshSerialize for BorshJsonTransaction {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        let buff: Vec<u8> = serde_json::to_vec(&self.0).unwrap();
+        let buff: Vec<u8> = serde_json::to_vec(&self.0)?;
         buff.serialize(writer)?;
         Ok(())
     }
 }
 
-impl BorshDeserialize for BorshTransaction {
+impl BorshDeserialize for BorshJsonTransaction {
     fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
         let bytes = Vec::<u8>::deserialize_reader(reader)?;
-        let tx: WvmTransaction = serde_json::from_slice(bytes.as_slice()).unwrap();
+        let tx: WvmTransaction = serde_json::from_slice(bytes.as_slice())?;
+        Ok(BorshJsonTransaction(tx))
+    }
+}
+
+impl BorshSerialize for BorshTransaction {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match &self.0 {
+            WvmTransaction::Legacy(tx) => {
+                LEGACY_TX_TYPE.serialize(writer)?;
+                tx.chain_id.serialize(writer)?;
+                tx.nonce.serialize(writer)?;
+                tx.gas_price.serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                BorshTxKind(tx.to).serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+            WvmTransaction::Eip2930(tx) => {
+                EIP2930_TX_TYPE.serialize(writer)?;
+                tx.chain_id.serialize(writer)?;
+                tx.nonce.serialize(writer)?;
+                tx.gas_price.serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                BorshTxKind(tx.to).serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                BorshAccessList(tx.access_list.clone()).serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+            WvmTransaction::Eip1559(tx) => {
+                EIP1559_TX_TYPE.serialize(writer)?;
+                tx.chain_id.serialize(writer)?;
+                tx.nonce.serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                tx.max_fee_per_gas.serialize(writer)?;
+                tx.max_priority_fee_per_gas.serialize(writer)?;
+                BorshTxKind(tx.to).serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                BorshAccessList(tx.access_list.clone()).serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+            WvmTransaction::Eip4844(tx) => {
+                EIP4844_TX_TYPE.serialize(writer)?;
+                tx.chain_id.serialize(writer)?;
+                tx.nonce.serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                tx.max_fee_per_gas.serialize(writer)?;
+                tx.max_priority_fee_per_gas.serialize(writer)?;
+                BorshAddress(tx.to).serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                BorshAccessList(tx.access_list.clone()).serialize(writer)?;
+                let blob_versioned_hashes: Vec<BorshB256> =
+                    tx.blob_versioned_hashes.iter().copied().map(BorshB256).collect();
+                blob_versioned_hashes.serialize(writer)?;
+                tx.max_fee_per_blob_gas.serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+            WvmTransaction::Eip7702(tx) => {
+                EIP7702_TX_TYPE.serialize(writer)?;
+                tx.chain_id.serialize(writer)?;
+                tx.nonce.serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                tx.max_fee_per_gas.serialize(writer)?;
+                tx.max_priority_fee_per_gas.serialize(writer)?;
+                BorshAddress(tx.to).serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                BorshAccessList(tx.access_list.clone()).serialize(writer)?;
+                let authorization_list: Vec<BorshSignedAuthorization> =
+                    tx.authorization_list.iter().cloned().map(BorshSignedAuthorization).collect();
+                authorization_list.serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+            #[cfg(feature = "optimism")]
+            WvmTransaction::Deposit(tx) => {
+                DEPOSIT_TX_TYPE.serialize(writer)?;
+                BorshB256(tx.source_hash).serialize(writer)?;
+                BorshAddress(tx.from).serialize(writer)?;
+                BorshTxKind(tx.to).serialize(writer)?;
+                tx.mint.serialize(writer)?;
+                BorshU256(tx.value).serialize(writer)?;
+                tx.gas_limit.serialize(writer)?;
+                tx.is_system_transaction.serialize(writer)?;
+                tx.input.0.serialize(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for BorshTransaction {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let tx_type: u8 = u8::deserialize_reader(reader)?;
+
+        let tx = match tx_type {
+            LEGACY_TX_TYPE => WvmTransaction::Legacy(TxLegacy {
+                chain_id: BorshDeserialize::deserialize_reader(reader)?,
+                nonce: BorshDeserialize::deserialize_reader(reader)?,
+                gas_price: BorshDeserialize::deserialize_reader(reader)?,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                to: BorshTxKind::deserialize_reader(reader)?.0,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            EIP2930_TX_TYPE => WvmTransaction::Eip2930(TxEip2930 {
+                chain_id: BorshDeserialize::deserialize_reader(reader)?,
+                nonce: BorshDeserialize::deserialize_reader(reader)?,
+                gas_price: BorshDeserialize::deserialize_reader(reader)?,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                to: BorshTxKind::deserialize_reader(reader)?.0,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                access_list: BorshAccessList::deserialize_reader(reader)?.0,
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            EIP1559_TX_TYPE => WvmTransaction::Eip1559(TxEip1559 {
+                chain_id: BorshDeserialize::deserialize_reader(reader)?,
+                nonce: BorshDeserialize::deserialize_reader(reader)?,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                max_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                max_priority_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                to: BorshTxKind::deserialize_reader(reader)?.0,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                access_list: BorshAccessList::deserialize_reader(reader)?.0,
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            EIP4844_TX_TYPE => WvmTransaction::Eip4844(TxEip4844 {
+                chain_id: BorshDeserialize::deserialize_reader(reader)?,
+                nonce: BorshDeserialize::deserialize_reader(reader)?,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                max_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                max_priority_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                to: BorshAddress::deserialize_reader(reader)?.0,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                access_list: BorshAccessList::deserialize_reader(reader)?.0,
+                blob_versioned_hashes: Vec::<BorshB256>::deserialize_reader(reader)?
+                    .into_iter()
+                    .map(|i| i.0)
+                    .collect(),
+                max_fee_per_blob_gas: BorshDeserialize::deserialize_reader(reader)?,
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            EIP7702_TX_TYPE => WvmTransaction::Eip7702(TxEip7702 {
+                chain_id: BorshDeserialize::deserialize_reader(reader)?,
+                nonce: BorshDeserialize::deserialize_reader(reader)?,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                max_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                max_priority_fee_per_gas: BorshDeserialize::deserialize_reader(reader)?,
+                to: BorshAddress::deserialize_reader(reader)?.0,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                access_list: BorshAccessList::deserialize_reader(reader)?.0,
+                authorization_list: Vec::<BorshSignedAuthorization>::deserialize_reader(reader)?
+                    .into_iter()
+                    .map(|i| i.0)
+                    .collect(),
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            #[cfg(feature = "optimism")]
+            DEPOSIT_TX_TYPE => WvmTransaction::Deposit(wvm_tx::TxDeposit {
+                source_hash: BorshB256::deserialize_reader(reader)?.0,
+                from: BorshAddress::deserialize_reader(reader)?.0,
+                to: BorshTxKind::deserialize_reader(reader)?.0,
+                mint: BorshDeserialize::deserialize_reader(reader)?,
+                value: BorshU256::deserialize_reader(reader)?.0,
+                gas_limit: BorshDeserialize::deserialize_reader(reader)?,
+                is_system_transaction: BorshDeserialize::deserialize_reader(reader)?,
+                input: Vec::<u8>::deserialize_reader(reader)?.into(),
+            }),
+            _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid transaction type")),
+        };
+
         Ok(BorshTransaction(tx))
     }
 }
@@ -35,6 +225,11 @@ impl BorshSerialize for BorshTransactionSigned {
             WvmTransactionSigned::V1(transaction_signed) => {
                 BorshB256(transaction_signed.hash).serialize(writer)?;
                 BorshSignature(transaction_signed.signature).serialize(writer)?;
+                BorshJsonTransaction(transaction_signed.transaction.clone()).serialize(writer)?;
+            }
+            WvmTransactionSigned::V2(transaction_signed) => {
+                BorshB256(transaction_signed.hash).serialize(writer)?;
+                BorshSignatureWithParity(transaction_signed.signature).serialize(writer)?;
                 BorshTransaction(transaction_signed.transaction.clone()).serialize(writer)?;
             }
         }
@@ -51,7 +246,7 @@ impl BorshDeserialize for BorshTransactionSigned {
             0u8 => {
                 let hash = BorshB256::deserialize_reader(reader)?;
                 let bytes_signature = BorshSignature::deserialize_reader(reader)?;
-                let tx = BorshTransaction::deserialize_reader(reader)?;
+                let tx = BorshJsonTransaction::deserialize_reader(reader)?;
 
                 Ok(BorshTransactionSigned(WvmTransactionSigned::V1(V1WvmTransactionSigned {
                     hash: hash.0,
@@ -59,6 +254,17 @@ impl BorshDeserialize for BorshTransactionSigned {
                     transaction: tx.0,
                 })))
             }
+            1u8 => {
+                let hash = BorshB256::deserialize_reader(reader)?;
+                let signature = BorshSignatureWithParity::deserialize_reader(reader)?;
+                let tx = BorshTransaction::deserialize_reader(reader)?;
+
+                Ok(BorshTransactionSigned(WvmTransactionSigned::V2(V2WvmTransactionSigned {
+                    hash: hash.0,
+                    signature: signature.0,
+                    transaction: tx.0,
+                })))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::Other, "Invalid Magic Identifier")),
         }
     }
@@ -67,15 +273,125 @@ impl BorshDeserialize for BorshTransactionSigned {
 #[cfg(test)]
 mod txs_tests {
     use crate::transaction::BorshTransactionSigned;
+    use alloy_eips::{
+        eip2718::Decodable2718,
+        eip2930::{AccessList, AccessListItem},
+        eip7702::{Authorization, SignedAuthorization},
+    };
+    use alloy_primitives::{hex, Address, Parity, Signature, TxKind, B256, U256};
     use reth::primitives::TransactionSigned;
 
     use reth::primitives::Transaction;
     use serde_json::Value;
     use wvm_tx::{
-        wvm::{v1::transaction::V1WvmTransactionSigned, WvmTransactionSigned},
+        wvm::{
+            v1::transaction::V1WvmTransactionSigned, v2::transaction::V2WvmTransactionSigned,
+            WvmTransactionSigned,
+        },
         WvmTransaction,
     };
 
+    fn sample_transactions() -> Vec<TransactionSigned> {
+        let raw_txs = [
+            // Legacy, EIP-155
+            "f86b02843b9aca00830186a094d3e8763675e4c425df46cc3b5c0f6cbdac39604687038d7ea4c68000802ba00eb96ca19e8a77102767a41fc85a36afd5c61ccb09911cec5d3e86e193d9c5aea03a456401896b1b6055311536bf00a718568c744d8c1f9df59879e8350220ca18",
+            // EIP-1559
+            "02f872018307910d808507204d2cb1827d0094388c818ca8b9251b393131c08a736a67ccb19297880320d04823e2701c80c001a0cf024f4815304df2867a1a74e9d2707b6abda0337d2d54a4438d453f4160f190a07ac0e6b3bc9395b5b9c8b9e6d77204a236577a5b18467b9175c01de4faa208d9",
+            // EIP-4844
+            "03f9011d83aa36a7820fa28477359400852e90edd0008252089411e9ca82a3a762b4b5bd264d4173a242e7a770648080c08504a817c800f8a5a0012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffce0dc1c57921aa00152d8e24762ff22b1cfd9f8c0683786a7ca63ba49973818b3d1e9512cd2cec4a0013b98c6c83e066d5b14af2b85199e3d4fc7d1e778dd53130d180f5077e2d1c7a001148b495d6e859114e670ca54fb6e2657f0cbae5b08063605093a4b3dc9f8f1a0011ac212f13c5dff2b2c6b600a79635103d6f580a4221079951181b25c7e654901a0c8de4cced43169f9aa3d36506363b2d2c44f6c49fc1fd91ea114c86f3757077ea01e11fdd0d1934eda0492606ee0bb80a7bf8f35cc5f86ec60fe5031ba48bfd544",
+        ];
+        let mut txs: Vec<TransactionSigned> = raw_txs
+            .iter()
+            .map(|raw| TransactionSigned::decode_2718(&mut hex::decode(raw).unwrap().as_slice()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::with_last_byte(1),
+            storage_keys: vec![B256::with_last_byte(2), B256::with_last_byte(3)],
+        }]);
+        let signature = Signature::new(U256::from(1), U256::from(2), Parity::Parity(true));
+
+        txs.push(TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip2930(alloy_consensus::TxEip2930 {
+                chain_id: 9496,
+                nonce: 1,
+                gas_price: 1_000_000_007,
+                gas_limit: 21_000,
+                to: TxKind::Create,
+                value: U256::from(10),
+                access_list: access_list.clone(),
+                input: vec![0x60, 0x80].into(),
+            }),
+            signature,
+        ));
+        txs.push(TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip7702(alloy_consensus::TxEip7702 {
+                chain_id: 9496,
+                nonce: 2,
+                gas_limit: 50_000,
+                max_fee_per_gas: 2_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                to: Address::with_last_byte(4),
+                value: U256::ZERO,
+                access_list,
+                authorization_list: vec![SignedAuthorization::new_unchecked(
+                    Authorization { chain_id: 9496, address: Address::with_last_byte(5), nonce: 3 },
+                    1,
+                    U256::from(6),
+                    U256::from(7),
+                )],
+                input: Default::default(),
+            }),
+            signature,
+        ));
+
+        txs
+    }
+
+    #[test]
+    pub fn test_v2_roundtrip() {
+        for tx in sample_transactions() {
+            let borsh_data = BorshTransactionSigned(WvmTransactionSigned::V2(
+                V2WvmTransactionSigned::from(tx.clone()),
+            ));
+            let to_borsh = borsh::to_vec(&borsh_data).unwrap();
+            assert_eq!(to_borsh[0], 1u8);
+
+            let from_borsh: BorshTransactionSigned =
+                borsh::from_slice(to_borsh.as_slice()).unwrap();
+            let decoded: TransactionSigned = from_borsh.0.into_v1().into();
+            assert_eq!(decoded, tx);
+            assert_eq!(decoded.recalculate_hash(), tx.hash);
+        }
+    }
+
+    #[test]
+    pub fn test_v1_still_decodes() {
+        for tx in sample_transactions().into_iter().take(2) {
+            let borsh_data = BorshTransactionSigned(WvmTransactionSigned::V1(
+                V1WvmTransactionSigned::from(tx.clone()),
+            ));
+            let to_borsh = borsh::to_vec(&borsh_data).unwrap();
+            let from_borsh: BorshTransactionSigned =
+                borsh::from_slice(to_borsh.as_slice()).unwrap();
+            assert!(from_borsh.0.as_v1().is_some());
+            assert_eq!(from_borsh.0.into_v1().hash, tx.hash);
+        }
+    }
+
+    #[test]
+    pub fn test_invalid_tx_type() {
+        let tx = sample_transactions().remove(1);
+        let mut to_borsh = borsh::to_vec(&BorshTransactionSigned(WvmTransactionSigned::V2(
+            V2WvmTransactionSigned::from(tx),
+        )))
+        .unwrap();
+        // magic, hash, signature
+        to_borsh[1 + 36 + 81] = 42;
+        assert!(borsh::from_slice::<BorshTransactionSigned>(to_borsh.as_slice()).is_err());
+    }
+
     #[test]
     pub fn test_sealed_header() {
         let data = TransactionSigned::default();